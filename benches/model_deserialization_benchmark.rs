@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use dash_rs::{
+    model::{comment::level::LevelComment, level::Level, song::NewgroundsSong, user::profile::Profile},
+    response::{parse_get_gj_levels_response, parse_get_gj_levels_response_lenient},
+    GJFormat,
+};
+use std::fs::read_to_string;
+
+pub fn listed_level_benchmark(c: &mut Criterion) {
+    let raw = read_to_string("./tests/artifacts/listed_level/63355989/raw").unwrap();
+
+    c.bench_function("deserialize listed level", |b| {
+        b.iter(|| {
+            let _: Level<()> = Level::from_gj_str(&raw).unwrap();
+        })
+    });
+}
+
+pub fn profile_benchmark(c: &mut Criterion) {
+    let raw = read_to_string("./tests/artifacts/profile/8451/raw").unwrap();
+
+    c.bench_function("deserialize profile", |b| {
+        b.iter(|| {
+            Profile::from_gj_str(&raw).unwrap();
+        })
+    });
+}
+
+pub fn newgrounds_song_benchmark(c: &mut Criterion) {
+    let raw = read_to_string("./tests/artifacts/song/771517/raw").unwrap();
+
+    c.bench_function("deserialize newgrounds song", |b| {
+        b.iter(|| {
+            NewgroundsSong::from_gj_str(&raw).unwrap();
+        })
+    });
+}
+
+pub fn level_comment_benchmark(c: &mut Criterion) {
+    let raw = read_to_string("./tests/artifacts/level_comment/260007/raw").unwrap();
+
+    c.bench_function("deserialize level comment", |b| {
+        b.iter(|| {
+            LevelComment::from_gj_str(&raw).unwrap();
+        })
+    });
+}
+
+pub fn get_gj_levels_benchmark(c: &mut Criterion) {
+    let response = read_to_string("./benches/data/get_gj_levels_response").unwrap();
+
+    c.bench_function("parse getGJLevels response", |b| {
+        b.iter(|| {
+            parse_get_gj_levels_response(&response).unwrap();
+        })
+    });
+}
+
+pub fn get_gj_levels_lenient_benchmark(c: &mut Criterion) {
+    let response = read_to_string("./benches/data/get_gj_levels_response").unwrap();
+
+    c.bench_function("parse getGJLevels response leniently", |b| {
+        b.iter(|| {
+            parse_get_gj_levels_response_lenient(&response).unwrap();
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    listed_level_benchmark,
+    profile_benchmark,
+    newgrounds_song_benchmark,
+    level_comment_benchmark,
+    get_gj_levels_benchmark,
+    get_gj_levels_lenient_benchmark
+);
+criterion_main!(benches);
@@ -13,7 +13,7 @@ use crate::utils;
 
 pub enum FieldMapping {
     /// An internal field that is mapped 1:1 to an API field
-    OneToOne(OneToOne),
+    OneToOne(Box<OneToOne>),
 
     /// An API field that has no corresponding internal field.
     ///
@@ -109,6 +109,31 @@ impl OneToOne {
         }
     }
 
+    pub fn schema(&self) -> proc_macro2::TokenStream {
+        let index = self.index();
+        let field = self.field.to_string();
+        let rust_type = utils::type_to_string(&self.api_type);
+        let optional = utils::is_option(&self.api_type);
+        let processor = match utils::thunk_processor(&self.api_type) {
+            Some(processor) => {
+                let processor = utils::type_to_string(processor);
+
+                quote!(Some(#processor))
+            },
+            None => quote!(None),
+        };
+
+        quote! {
+            crate::schema::FieldSchema {
+                index: #index,
+                field: #field,
+                rust_type: #rust_type,
+                optional: #optional,
+                processor: #processor,
+            }
+        }
+    }
+
     pub fn deserialize(&self) -> proc_macro2::TokenStream {
         let field_name = self.internal_name();
         let field = &self.field;
@@ -121,6 +146,23 @@ impl OneToOne {
 }
 
 impl FieldMapping {
+    /// The index of the API field this field is mapped to, if any
+    pub fn index(&self) -> Option<String> {
+        match self {
+            FieldMapping::OneToOne(inner) => Some(inner.index()),
+            FieldMapping::NoIndex { .. } => None,
+        }
+    }
+
+    /// The description of this field in the model's schema, if it is mapped to an API
+    /// field
+    pub fn schema(&self) -> Option<TokenStream> {
+        match self {
+            FieldMapping::OneToOne(inner) => Some(inner.schema()),
+            FieldMapping::NoIndex { .. } => None,
+        }
+    }
+
     pub fn ser_field_tokens(&self, lifetime: &Lifetime) -> TokenStream {
         match self {
             FieldMapping::OneToOne(inner) => inner.ser_field_tokens(lifetime),
@@ -231,6 +273,7 @@ impl TryFrom<Field> for FieldMapping {
                 DashAttribute::Index(idx) => builder.with_index(idx),
                 DashAttribute::PassthroughToSerde(tokens) => builder.with_passthrough(tokens),
                 DashAttribute::NoIndex => builder.no_index(),
+                DashAttribute::CompareWith(_) => true,
             };
 
             if !build_success {
@@ -244,22 +287,50 @@ impl TryFrom<Field> for FieldMapping {
             FieldMappingBuilder::Initial => Err(Error::new_spanned(field, "missing #[dash(...)] attribute")),
             FieldMappingBuilder::OneToOne {
                 index: Some(index),
-                passthrough,
-            } => Ok(FieldMapping::OneToOne(OneToOne {
-                index,
-                field,
-                api_type,
-                passthrough,
-            })),
+                mut passthrough,
+            } => {
+                if utils::is_optional_thunk(&api_type) {
+                    optional_thunk_defaults(&mut passthrough);
+                }
+
+                Ok(FieldMapping::OneToOne(Box::new(OneToOne {
+                    index,
+                    field,
+                    api_type,
+                    passthrough,
+                })))
+            },
             FieldMappingBuilder::OneToOne { index: None, .. } => Err(Error::new_spanned(field, "missing #[dash(index = ...)] attribute")),
             FieldMappingBuilder::NoIndex => Ok(FieldMapping::NoIndex { field }),
         }
     }
 }
 
-enum DashAttribute {
+/// Adds the serde attributes every optional thunk needs, unless they were given explicitly
+///
+/// Optional thunks are values that are not present in every response (or not for every object), so
+/// a missing index deserializes to `None`, and `None` is not serialized at all instead of as an
+/// empty value.
+fn optional_thunk_defaults(passthrough: &mut Vec<TokenStream>) {
+    let has_attribute = |passthrough: &[TokenStream], name: &str| {
+        passthrough
+            .iter()
+            .any(|tokens| matches!(tokens.clone().into_iter().next(), Some(proc_macro2::TokenTree::Ident(ident)) if ident == name))
+    };
+
+    if !has_attribute(passthrough, "default") {
+        passthrough.push(quote!(default));
+    }
+    if !has_attribute(passthrough, "skip_serializing_if") {
+        passthrough.push(quote!(skip_serializing_if = "Option::is_none"));
+    }
+}
+
+pub enum DashAttribute {
     Index(LitIndex),
     NoIndex,
+    /// Only relevant to `#[derive(DashPartialEq)]`
+    CompareWith(LitStr),
     PassthroughToSerde(TokenStream),
 }
 
@@ -273,6 +344,14 @@ impl Parse for DashAttribute {
 
                 return Ok(DashAttribute::NoIndex);
             }
+            if key == "compare_with" {
+                let _ = fork.parse::<Token![=]>()?;
+                let lit = fork.parse()?;
+
+                input.advance_to(&fork);
+
+                return Ok(DashAttribute::CompareWith(lit));
+            }
             if key == "index" {
                 let _ = fork.parse::<Token![=]>()?;
                 let lookahead = fork.lookahead1();
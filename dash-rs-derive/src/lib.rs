@@ -5,12 +5,23 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::ToTokens;
 use struct_gen::InternalStruct;
-use syn::{parse_macro_input, spanned::Spanned, Data, DataStruct, DeriveInput, Error, Fields, Result};
+use syn::{parse_macro_input, spanned::Spanned, Data, DataStruct, DeriveInput, Error, Fields, Ident, Meta, MetaList, Result};
 
 mod field;
+mod partial_eq;
 mod struct_gen;
 mod utils;
 
+/// Derives `Dash` for a struct whose fields are annotated with `#[dash(index = ...)]` (or
+/// `#[dash(no_index)]`)
+///
+/// Any other `#[dash(...)]` attribute is passed through to serde. Fields of type
+/// `Option<Thunk<'a, P>>` implicitly get `#[serde(default)]` and
+/// `#[serde(skip_serializing_if = "Option::is_none")]`, unless these are given explicitly.
+///
+/// With dash-rs' `strict-format` feature enabled, deserialization fails on indices not mapped to any
+/// field. Structs whose data routinely contains indices dash-rs does not model can opt out of this
+/// by being annotated with `#[dash(allow_unknown_indices)]`.
 #[proc_macro_derive(Dash, attributes(dash))]
 pub fn derive_dash(ts: TokenStream) -> TokenStream {
     let input = parse_macro_input!(ts as DeriveInput);
@@ -20,8 +31,43 @@ pub fn derive_dash(ts: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derives `PartialEq` between all instantiations of a struct's lifetime and type parameters
+///
+/// This allows e.g. comparing a `Level<'a>` that borrows from some response to a `Level<'static>`
+/// loaded from elsewhere. Fields are compared using `==`, unless they are annotated with
+/// `#[dash(compare_with = "path::to::function")]`, in which case the given function is called
+/// with references to both fields.
+#[proc_macro_derive(DashPartialEq, attributes(dash))]
+pub fn derive_dash_partial_eq(ts: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(ts as DeriveInput);
+    partial_eq::expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
 fn expand_dash_derive(input: DeriveInput) -> Result<InternalStruct> {
-    let DeriveInput { ident, generics, data, .. } = input;
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        attrs,
+        ..
+    } = input;
+
+    let mut allow_unknown_indices = false;
+
+    for attr in attrs {
+        let Meta::List(MetaList { path, tokens, .. }) = attr.meta else {
+            continue;
+        };
+
+        if !path.is_ident("dash") {
+            continue;
+        }
+
+        match syn::parse2::<Ident>(tokens)? {
+            ident if ident == "allow_unknown_indices" => allow_unknown_indices = true,
+            ident => return Err(Error::new(ident.span(), "unexpected #[dash(...)] attribute")),
+        }
+    }
 
     let Data::Struct(DataStruct { fields, .. }) = data else {
         return Err(Error::new(Span::call_site(), "#[derive(Dash)] only support structs"));
@@ -55,5 +101,6 @@ fn expand_dash_derive(input: DeriveInput) -> Result<InternalStruct> {
         fields,
         generics,
         lifetime: primary_lifetime,
+        allow_unknown_indices,
     })
 }
@@ -0,0 +1,105 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DataStruct, DeriveInput, Error, Fields, GenericParam, Lifetime, Meta, MetaList, Path, Result};
+
+use crate::field::DashAttribute;
+
+/// Generates an implementation of `PartialEq<Name<'b, B...>>` for `Name<'a, A...>`, comparing the
+/// structs field by field
+///
+/// Each lifetime and type parameter of the struct is allowed to differ between the two sides of
+/// the comparison, as long as the type parameters themselves can be compared. Fields annotated with
+/// `#[dash(compare_with = "path")]` are compared using the function at the given path, which is
+/// called with references to both fields.
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let DeriveInput { ident, generics, data, .. } = input;
+
+    let Data::Struct(DataStruct { fields, .. }) = data else {
+        return Err(Error::new(ident.span(), "#[derive(DashPartialEq)] only supports structs"));
+    };
+
+    let Fields::Named(fields_named) = fields else {
+        return Err(Error::new(
+            fields.span(),
+            "#[derive(DashPartialEq)] only supports structs with named fields",
+        ));
+    };
+
+    let mut self_params = Vec::new();
+    let mut other_params = Vec::new();
+    let mut impl_params = Vec::new();
+    let mut bounds = Vec::new();
+
+    for param in &generics.params {
+        match param {
+            GenericParam::Lifetime(lifetime_param) => {
+                let lifetime = &lifetime_param.lifetime;
+                let other = Lifetime::new(&format!("'__other_{}", lifetime.ident), lifetime.span());
+
+                self_params.push(quote!(#lifetime));
+                other_params.push(quote!(#other));
+                impl_params.push(quote!(#lifetime));
+                impl_params.push(quote!(#other));
+            },
+            GenericParam::Type(type_param) => {
+                let ty = &type_param.ident;
+                let other = format_ident!("__Other{}", ty);
+
+                self_params.push(quote!(#ty));
+                other_params.push(quote!(#other));
+                impl_params.push(quote!(#ty));
+                impl_params.push(quote!(#other));
+                bounds.push(quote!(#ty: PartialEq<#other>));
+            },
+            GenericParam::Const(const_param) => {
+                return Err(Error::new(
+                    const_param.span(),
+                    "#[derive(DashPartialEq)] does not support const generics",
+                ))
+            },
+        }
+    }
+
+    let mut comparisons = Vec::new();
+
+    for field in fields_named.named {
+        let mut compare_with: Option<Path> = None;
+
+        for attr in &field.attrs {
+            let Meta::List(MetaList { path, tokens, .. }) = &attr.meta else {
+                continue;
+            };
+
+            if !path.is_ident("dash") {
+                continue;
+            }
+
+            if let DashAttribute::CompareWith(lit) = syn::parse2::<DashAttribute>(tokens.clone())? {
+                if compare_with.is_some() {
+                    return Err(Error::new(lit.span(), "duplicate #[dash(compare_with = ...)] attribute"));
+                }
+
+                compare_with = Some(lit.parse()?);
+            }
+        }
+
+        let field = field.ident.unwrap();
+
+        comparisons.push(match compare_with {
+            Some(path) => quote!(#path(&self.#field, &other.#field)),
+            None => quote!(self.#field == other.#field),
+        });
+    }
+
+    Ok(quote! {
+        #[allow(non_camel_case_types)]
+        impl<#(#impl_params),*> PartialEq<#ident<#(#other_params),*>> for #ident<#(#self_params),*>
+        where
+            #(#bounds),*
+        {
+            fn eq(&self, other: &#ident<#(#other_params),*>) -> bool {
+                true #(&& #comparisons)*
+            }
+        }
+    })
+}
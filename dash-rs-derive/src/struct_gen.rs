@@ -11,6 +11,8 @@ pub struct InternalStruct {
     pub generics: Generics,
     /// The unique lifetime of the struct for which we are deriving `Dash`, if it exists.
     pub lifetime: Option<LifetimeParam>,
+    /// Whether unknown indices should be ignored even with the `strict-format` feature enabled
+    pub allow_unknown_indices: bool,
 }
 
 impl InternalStruct {
@@ -42,17 +44,45 @@ impl InternalStruct {
 
     fn de_struct(&self) -> proc_macro2::TokenStream {
         let name = self.deserialize_struct_name();
+        // Deserializers get to see the name of the struct, so make sure it is the one from the public API
+        let api_name = self.name.to_string();
         let fields = self.fields.iter().map(|ifield| ifield.de_field_tokens());
         let generics = &self.generics;
+        // The generated code is compiled as part of dash-rs, so the feature refers to dash-rs' `strict-format` feature
+        let strictness = if self.allow_unknown_indices {
+            quote! {}
+        } else {
+            quote! { #[cfg_attr(feature = "strict-format", serde(deny_unknown_fields))] }
+        };
 
         quote! {
             #[derive(Deserialize)]
+            #[serde(rename = #api_name)]
+            #strictness
             struct #name#generics {
                 #(#fields)*
             }
         }
     }
 
+    /// The indices are extracted from [`InternalStruct::schema`] instead of being listed a
+    /// second time, so that the two cannot get out of sync
+    fn indices(&self) -> proc_macro2::TokenStream {
+        let count = self.fields.iter().filter(|field| field.index().is_some()).count();
+
+        quote! {
+            &crate::schema::indices::<#count>(Self::FIELDS)
+        }
+    }
+
+    fn schema(&self) -> proc_macro2::TokenStream {
+        let fields = self.fields.iter().filter_map(FieldMapping::schema);
+
+        quote! {
+            &[#(#fields),*]
+        }
+    }
+
     fn serialize_implementation(&self) -> proc_macro2::TokenStream {
         // assume a `Serializer` is in scope, named serializer
         let serialize_struct = self.serialize_struct_name();
@@ -98,6 +128,8 @@ impl ToTokens for InternalStruct {
 
         let deserialize_impl = self.deserialize_implementation();
         let serialize_impl = self.serialize_implementation();
+        let indices = self.indices();
+        let schema = self.schema();
 
         tokens.extend(quote! {
             const _: () = {
@@ -111,6 +143,9 @@ impl ToTokens for InternalStruct {
                 impl#generic_arg_list Dash<#lifetime> for #name<#existing_params>
                     #where_clause
                 {
+                    const INDICES: &'static [&'static str] = #indices;
+                    const FIELDS: &'static [crate::schema::FieldSchema] = #schema;
+
                     fn dash_deserialize<D: Deserializer<#lifetime>>(deserializer: D) -> Result<Self, D::Error> {
                         #deserialize_impl
                     }
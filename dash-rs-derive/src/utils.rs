@@ -5,7 +5,7 @@ use syn::{spanned::Spanned, Error, Generics, LifetimeParam, Result, Type};
 /// lifetimes, or too many lifetimes
 pub fn find_unique_lifetime(generics: &Generics) -> Result<Option<LifetimeParam>> {
     let mut lifetime_iter = generics.lifetimes();
-    let first_lifetime = lifetime_iter.next().map(Clone::clone);
+    let first_lifetime = lifetime_iter.next().cloned();
 
     if let Some(lifetime_param) = lifetime_iter.next() {
         return Err(Error::new(lifetime_param.span(), "Expected exactly one lifetime, found multiple"));
@@ -54,3 +54,80 @@ pub fn type_contains_lifetime(ty: &Type) -> bool {
         _ => todo!(),
     }
 }
+
+/// Renders the given type the way it would be written in source code, e.g. `Option<Cow<'a, str>>`
+pub fn type_to_string(ty: &Type) -> String {
+    let mut rendered = quote::quote!(#ty).to_string();
+
+    for (spaced, compact) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        (" ::", "::"),
+        (":: ", "::"),
+    ] {
+        rendered = rendered.replace(spaced, compact);
+    }
+
+    rendered
+}
+
+/// Whether the given type is an `Option<...>`
+pub fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// If the given type contains a `Thunk<'a, P>`, returns the processor `P`
+pub fn thunk_processor(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    type_path.path.segments.iter().find_map(|segment| {
+        let syn::PathArguments::AngleBracketed(generic_args) = &segment.arguments else {
+            return None;
+        };
+
+        let mut type_args = generic_args.args.iter().filter_map(|generic| match generic {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        });
+
+        if segment.ident == "Thunk" {
+            type_args.next()
+        } else {
+            type_args.find_map(thunk_processor)
+        }
+    })
+}
+
+/// Whether the given type is an `Option<Thunk<'a, P>>`
+pub fn is_optional_thunk(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    let syn::PathArguments::AngleBracketed(generic_args) = &segment.arguments else {
+        return false;
+    };
+
+    segment.ident == "Option"
+        && generic_args.args.iter().any(|generic| match generic {
+            syn::GenericArgument::Type(Type::Path(inner)) => {
+                inner.path.segments.last().map(|segment| segment.ident == "Thunk").unwrap_or(false)
+            },
+            _ => false,
+        })
+}
@@ -0,0 +1,132 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, Ident, LitStr, Result, Token, Type,
+};
+
+/// The checks performed on each artifact if none are given explicitly
+const DEFAULT_CHECKS: &[&str] = &["test_consistency", "test_load_save_roundtrip", "test_save_load_roundtrip"];
+
+/// Input of the `golden_roundtrip!` macro: `Tester, "path/to/artifacts/*"`, optionally followed by
+/// `, [check, ...]`
+pub struct GoldenRoundtrip {
+    tester: Type,
+    pattern: LitStr,
+    checks: Option<Punctuated<Ident, Token![,]>>,
+}
+
+impl Parse for GoldenRoundtrip {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let tester = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let pattern = input.parse()?;
+
+        let checks = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let content;
+            bracketed!(content in input);
+
+            Some(content.parse_terminated(Ident::parse, Token![,])?)
+        } else {
+            None
+        };
+
+        Ok(GoldenRoundtrip { tester, pattern, checks })
+    }
+}
+
+impl GoldenRoundtrip {
+    pub fn expand(self) -> Result<TokenStream> {
+        let pattern = self.pattern.value();
+
+        let Some(directory) = pattern.strip_suffix("/*") else {
+            return Err(Error::new(
+                self.pattern.span(),
+                "artifact pattern must be of the form \"path/to/artifacts/*\"",
+            ));
+        };
+
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|err| Error::new(Span::call_site(), err))?;
+        let read_dir = std::fs::read_dir(std::path::Path::new(&manifest_dir).join(directory)).map_err(|err| {
+            Error::new(
+                self.pattern.span(),
+                format!("cannot read artifact directory {}: {}", directory, err),
+            )
+        })?;
+
+        let mut artifacts = Vec::new();
+
+        for entry in read_dir {
+            let entry = entry.map_err(|err| Error::new(self.pattern.span(), err))?;
+
+            if entry.path().is_dir() {
+                artifacts.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+
+        artifacts.sort();
+
+        let category = directory.rsplit('/').next().unwrap_or(directory);
+        let checks = match self.checks {
+            Some(checks) => checks.into_iter().collect(),
+            None => DEFAULT_CHECKS
+                .iter()
+                .map(|check| Ident::new(check, Span::call_site()))
+                .collect::<Vec<_>>(),
+        };
+        let tester = &self.tester;
+
+        let tests = artifacts.iter().map(|artifact| {
+            let name = format_ident!("{}_{}", sanitize(category), sanitize(artifact));
+            let unit = format!("/{}/{}", directory, artifact);
+            let raw = format!("{}/raw", unit);
+            let processed = format!("{}/processed", unit);
+
+            quote! {
+                #[test]
+                fn #name() {
+                    // Makes changes to the artifact files trigger recompilation of the test
+                    const _: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), #raw));
+                    const _: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), #processed));
+
+                    let unit = framework::TestUnit::<#tester>::new(concat!(env!("CARGO_MANIFEST_DIR"), #unit));
+
+                    #(unit.#checks();)*
+                }
+            }
+        });
+
+        let catch_all = format_ident!("{}_new_artifacts", sanitize(category));
+        let directory = format!("/{}", directory);
+
+        Ok(quote! {
+            #(#tests)*
+
+            #[test]
+            fn #catch_all() {
+                const KNOWN: &[&str] = &[#(#artifacts),*];
+
+                for entry in std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), #directory)).unwrap() {
+                    let path = entry.unwrap().path();
+
+                    if path.is_dir() && !KNOWN.iter().any(|known| path.file_name().unwrap() == *known) {
+                        println!("Testing new artifact {:?}", path);
+
+                        let unit = framework::TestUnit::<#tester>::new(&path);
+
+                        #(unit.#checks();)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Turns `name` into something usable as part of an identifier
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
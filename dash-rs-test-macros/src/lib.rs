@@ -0,0 +1,24 @@
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+
+mod golden;
+
+/// Generates one `#[test]` per artifact directory in the given directory of dash-rs' test
+/// artifacts
+///
+/// Takes a type implementing the test framework's `Testable` trait and a pattern of the form
+/// `"tests/artifacts/<kind>/*"`, relative to the crate root. Each generated test loads the
+/// artifact's `raw` and `processed` files into a `framework::TestUnit` and runs the round trip
+/// checks on it. A list of the checks to run can optionally be given as a third argument, e.g.
+/// `[test_consistency]`.
+///
+/// The artifact files are included via `include_bytes!`, so cargo recompiles the tests when they
+/// change. Cargo cannot track the directory listing itself though, so an additional
+/// `<kind>_new_artifacts` test lists the directory at runtime and runs the checks on every artifact
+/// added since the macro was last expanded. Such artifacts get their own test once the test file is
+/// recompiled.
+#[proc_macro]
+pub fn golden_roundtrip(ts: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(ts as golden::GoldenRoundtrip);
+    input.expand().unwrap_or_else(syn::Error::into_compile_error).into()
+}
@@ -35,7 +35,7 @@ async fn main() {
     for page in 1.. {
         let request = LevelsRequest::default().request_type(LevelRequestType::Featured).page(page);
 
-        let response = make_request(&http_client, &request.to_url(), request.to_string()).await;
+        let response = make_request(&http_client, &request.to_url().unwrap(), request.to_string()).await;
         let response_text = response.text().await.unwrap();
 
         let levels = parse_get_gj_levels_response(&response_text).unwrap();
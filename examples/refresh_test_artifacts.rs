@@ -1,7 +1,4 @@
-use std::{
-    fs::OpenOptions,
-    path::{Path, PathBuf},
-};
+use std::{fs::OpenOptions, path::Path};
 
 use dash_rs::{
     model::{creator::Creator, level::Level, song::NewgroundsSong},
@@ -9,7 +6,9 @@ use dash_rs::{
         level::{CompletionFilter, LevelRequest, LevelsRequest, SearchFilters},
         user::{UserRequest, UserSearchRequest},
     },
-    response::{parse_download_gj_level_response, parse_get_gj_user_info_response, parse_get_gj_users_response},
+    response::{
+        parse_download_gj_level_response, parse_get_gj_user_info_response, parse_get_gj_users_response, sections::ResponseSections,
+    },
     GJFormat,
 };
 use reqwest::{
@@ -39,7 +38,7 @@ async fn main() {
     std::fs::remove_dir_all(&artifacts_backup).unwrap();
 }
 
-async fn refresh_full_levels(artifacts_path: &PathBuf, http_client: &Client) {
+async fn refresh_full_levels(artifacts_path: &Path, http_client: &Client) {
     println!("Downloading full levels");
 
     let levels_path = artifacts_path.join("level");
@@ -57,12 +56,16 @@ async fn refresh_full_levels(artifacts_path: &PathBuf, http_client: &Client) {
         let level_artifact_path = levels_path.join(level_id.to_string());
         let _ = std::fs::create_dir_all(&level_artifact_path);
 
-        std::fs::write(level_artifact_path.join("raw"), response_text.split('#').next().unwrap()).unwrap();
+        std::fs::write(
+            level_artifact_path.join("raw"),
+            ResponseSections::new(&response_text).require(0).unwrap(),
+        )
+        .unwrap();
         dump_deserialized_artifact(level_artifact_path, &level);
     }
 }
 
-async fn refresh_listed_levels(artifacts_path: &PathBuf, http_client: &Client) {
+async fn refresh_listed_levels(artifacts_path: &Path, http_client: &Client) {
     println!("Downloading listed levels");
 
     let listed_levels_path = artifacts_path.join("listed_level");
@@ -79,48 +82,45 @@ async fn refresh_listed_levels(artifacts_path: &PathBuf, http_client: &Client) {
     let request = LevelsRequest::default()
         .search_filters(SearchFilters::default().completion_filter(CompletionFilter::limit_search(levels_to_dowload)));
 
-    let response = make_request(&http_client, &request.to_url(), request.to_string()).await;
+    let response = make_request(http_client, &request.to_url().unwrap(), request.to_string()).await;
     let response_text = response.text().await.unwrap();
 
-    // We'll have to reimplement part of the response parsing here, `parse_get_gj_levels_response` is
-    // too ergonomic for what we want
-    let mut section_iter = response_text.split('#');
-    let raw_levels = section_iter.next().unwrap();
-    let raw_creators = section_iter.next().unwrap();
-    let raw_songs = section_iter.next().unwrap();
+    // `parse_get_gj_levels_response` associates levels with their creators and songs, but we want
+    // to archive each object exactly as it appeared in the response
+    let sections = ResponseSections::new(&response_text);
 
-    for raw_level in raw_levels.split('|') {
+    for raw_level in sections.levels().unwrap() {
         let level = Level::<()>::from_gj_str(raw_level).unwrap();
 
         let level_artifact_path = listed_levels_path.join(level.level_id.to_string());
         let _ = std::fs::create_dir_all(&level_artifact_path);
 
-        std::fs::write(level_artifact_path.join("raw"), &raw_level).unwrap();
+        std::fs::write(level_artifact_path.join("raw"), raw_level).unwrap();
         dump_deserialized_artifact(level_artifact_path, &level);
     }
 
-    for raw_creator in raw_creators.split('|') {
+    for raw_creator in sections.creators().unwrap() {
         let creator = Creator::from_gj_str(raw_creator).unwrap();
 
         let creator_artifact_path = creators_path.join(creator.user_id.to_string());
         let _ = std::fs::create_dir_all(&creator_artifact_path);
 
-        std::fs::write(creator_artifact_path.join("raw"), &raw_creator).unwrap();
+        std::fs::write(creator_artifact_path.join("raw"), raw_creator).unwrap();
         dump_deserialized_artifact(creator_artifact_path, &creator);
     }
 
-    for raw_song in raw_songs.split("~:~") {
+    for raw_song in sections.songs().unwrap() {
         let song = NewgroundsSong::from_gj_str(raw_song).unwrap();
 
         let song_artifact_path = songs_path.join(song.song_id.to_string());
         let _ = std::fs::create_dir_all(&song_artifact_path);
 
-        std::fs::write(song_artifact_path.join("raw"), &raw_song).unwrap();
+        std::fs::write(song_artifact_path.join("raw"), raw_song).unwrap();
         dump_deserialized_artifact(song_artifact_path, &song);
     }
 }
 
-async fn refresh_profiles(artifacts_path: &PathBuf, http_client: &Client) {
+async fn refresh_profiles(artifacts_path: &Path, http_client: &Client) {
     println!("Downloading profiles");
 
     let profiles_path = artifacts_path.join("profile");
@@ -128,7 +128,7 @@ async fn refresh_profiles(artifacts_path: &PathBuf, http_client: &Client) {
 
     for account_id in profiles_to_download {
         let request = UserRequest::new(account_id);
-        let response = make_request(&http_client, &request.to_url(), request.to_string()).await;
+        let response = make_request(http_client, &request.to_url(), request.to_string()).await;
         let response_text = response.text().await.unwrap();
 
         let profile = parse_get_gj_user_info_response(&response_text).unwrap();
@@ -141,7 +141,7 @@ async fn refresh_profiles(artifacts_path: &PathBuf, http_client: &Client) {
     }
 }
 
-async fn refresh_searched_users(artifacts_path: &PathBuf, http_client: &Client) {
+async fn refresh_searched_users(artifacts_path: &Path, http_client: &Client) {
     println!("Downloading search-listed users");
 
     let searched_users_path = artifacts_path.join("searched_user");
@@ -149,15 +149,19 @@ async fn refresh_searched_users(artifacts_path: &PathBuf, http_client: &Client)
 
     for username in users_to_search {
         let request = UserSearchRequest::new(username);
-        let response = make_request(&http_client, &request.to_url(), request.to_string()).await;
+        let response = make_request(http_client, &request.to_url(), request.to_string()).await;
         let response_text = response.text().await.unwrap();
 
         let searched_user = parse_get_gj_users_response(&response_text).unwrap();
 
-        let searched_user_artifact_path = searched_users_path.join(username.to_string());
+        let searched_user_artifact_path = searched_users_path.join(username);
         let _ = std::fs::create_dir_all(&searched_user_artifact_path);
 
-        std::fs::write(searched_user_artifact_path.join("raw"), &response_text.split('#').next().unwrap()).unwrap();
+        std::fs::write(
+            searched_user_artifact_path.join("raw"),
+            ResponseSections::new(&response_text).require(0).unwrap(),
+        )
+        .unwrap();
         dump_deserialized_artifact(searched_user_artifact_path, &searched_user);
     }
 }
@@ -0,0 +1,13 @@
+#![no_main]
+
+use dash_rs::{
+    model::level::{Level, LevelData},
+    GJFormat,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Both the variant with level data (from downloadGJLevel) and the one without (from getGJLevels)
+    let _ = Level::<LevelData>::from_gj_str(input);
+    let _ = Level::<()>::from_gj_str(input);
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+use std::{borrow::Cow, io::Read};
+
+use base64::{engine::general_purpose::URL_SAFE, Engine};
+use dash_rs::{model::level::Objects, ThunkProcessor};
+use flate2::{read::GzEncoder, Compression};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Feed the input as-is, to exercise the base64 decoding and decompression
+    let _ = Objects::from_unprocessed(Cow::Borrowed(input));
+
+    // Feed the input as the decompressed level string, to exercise the parsing of the level's
+    // metadata and objects
+    let mut compressed = Vec::new();
+    GzEncoder::new(input.as_bytes(), Compression::fast())
+        .read_to_end(&mut compressed)
+        .unwrap();
+
+    let _ = Objects::from_unprocessed(Cow::Owned(URL_SAFE.encode(compressed)));
+});
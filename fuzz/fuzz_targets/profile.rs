@@ -0,0 +1,8 @@
+#![no_main]
+
+use dash_rs::{model::user::profile::Profile, GJFormat};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Profile::from_gj_str(input);
+});
@@ -0,0 +1,264 @@
+//! Module containing helpers for archiving parsed responses in SQLite
+//!
+//! Each [`Archivable`] model is stored in its own table. A few columns hold the data most commonly
+//! queried (ids, names, ratings). A final `raw` column holds the model in RobTop's data format, and
+//! loading always goes through this column. This makes archiving lossless, even for fields that are
+//! not broken out into columns.
+//!
+//! Since dash-rs' models borrow from their input, loading a row produces an [`Archived`] value. It
+//! owns the raw data, and the model is then parsed from it.
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::convert::TryFrom;
+
+use rusqlite::{
+    types::{FromSql, FromSqlResult, ToSqlOutput, Value, ValueRef},
+    Connection, OptionalExtension, ToSql,
+};
+use thiserror::Error;
+
+use crate::{
+    model::{creator::Creator, level::Level, song::NewgroundsSong, user::profile::Profile},
+    DeError, GJFormat, SerError,
+};
+
+/// Errors that can occur while archiving objects
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    /// Some SQLite operation failed
+    #[error("{0}")]
+    Sql(#[from] rusqlite::Error),
+
+    /// The object to archive could not be serialized into RobTop's data format
+    #[error("{0}")]
+    Serialize(#[from] SerError),
+}
+
+/// A column of an [`Archivable`] model's table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    /// The column's name
+    pub name: &'static str,
+
+    /// The column's SQL type, including constraints such as `NOT NULL`
+    pub sql_type: &'static str,
+}
+
+impl Column {
+    /// Creates a new column with the given name and SQL type
+    pub const fn new(name: &'static str, sql_type: &'static str) -> Self {
+        Column { name, sql_type }
+    }
+}
+
+/// Trait implemented by models that can be stored in SQLite
+pub trait Archivable<'a>: GJFormat<'a> {
+    /// The name of the table objects of this type are stored in
+    const TABLE: &'static str;
+
+    /// The columns of [`Archivable::TABLE`], apart from the `raw` column. The first column is the
+    /// table's primary key
+    const COLUMNS: &'static [Column];
+
+    /// The values of this object's [`Archivable::COLUMNS`], in order
+    ///
+    /// Fails if an integer value does not fit into SQLite's signed 64 bit integers.
+    fn column_values(&self) -> rusqlite::Result<Vec<Value>>;
+}
+
+/// The raw data of an archived object, in RobTop's data format
+///
+/// This is the owned representation objects are loaded into. It can be used with [`ToSql`] and
+/// [`FromSql`] directly, for storing objects in tables not managed by this module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archived {
+    raw: String,
+}
+
+impl Archived {
+    /// Serializes the given object for archiving
+    pub fn new<'a, T: GJFormat<'a>>(object: &T) -> Result<Self, SerError> {
+        object.to_gj_string().map(|raw| Archived { raw })
+    }
+
+    /// The archived object in RobTop's data format
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Parses the archived object, borrowing from this [`Archived`]
+    pub fn parse<'a, T: GJFormat<'a>>(&'a self) -> Result<T, DeError<'a>> {
+        T::from_gj_str(&self.raw)
+    }
+}
+
+impl ToSql for Archived {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(self.raw.as_bytes())))
+    }
+}
+
+impl FromSql for Archived {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        String::column_result(value).map(|raw| Archived { raw })
+    }
+}
+
+/// The `CREATE TABLE` statement for the table storing objects of type `T`
+pub fn schema<'a, T: Archivable<'a>>() -> String {
+    let mut columns = Vec::new();
+
+    for (idx, column) in T::COLUMNS.iter().enumerate() {
+        match idx {
+            0 => columns.push(format!("{} {} PRIMARY KEY", column.name, column.sql_type)),
+            _ => columns.push(format!("{} {}", column.name, column.sql_type)),
+        }
+    }
+
+    columns.push("raw TEXT NOT NULL".to_string());
+
+    format!("CREATE TABLE IF NOT EXISTS {} ({})", T::TABLE, columns.join(", "))
+}
+
+/// Creates the tables for all models supported by this module, if they do not exist yet
+pub fn create_tables(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute(&schema::<Level>(), [])?;
+    connection.execute(&schema::<Creator>(), [])?;
+    connection.execute(&schema::<NewgroundsSong>(), [])?;
+    connection.execute(&schema::<Profile>(), [])?;
+
+    Ok(())
+}
+
+/// Stores the given object in its table, replacing any previously archived object with the same
+/// primary key
+pub fn archive<'a, T: Archivable<'a>>(connection: &Connection, object: &T) -> Result<(), ArchiveError> {
+    let mut values = object.column_values()?;
+
+    values.push(Value::Text(Archived::new(object)?.raw));
+
+    let names: Vec<&str> = T::COLUMNS.iter().map(|column| column.name).collect();
+    let placeholders: Vec<String> = (1..=values.len()).map(|idx| format!("?{}", idx)).collect();
+    let statement = format!(
+        "INSERT OR REPLACE INTO {} ({}, raw) VALUES ({})",
+        T::TABLE,
+        names.join(", "),
+        placeholders.join(", ")
+    );
+
+    connection.execute(&statement, rusqlite::params_from_iter(values))?;
+
+    Ok(())
+}
+
+/// Loads the object of type `T` with the given primary key, if it has been archived
+pub fn load<'a, T: Archivable<'a>>(connection: &Connection, key: u64) -> rusqlite::Result<Option<Archived>> {
+    let statement = format!("SELECT raw FROM {} WHERE {} = ?1", T::TABLE, T::COLUMNS[0].name);
+
+    connection.query_row(&statement, [integer(key)?], |row| row.get(0)).optional()
+}
+
+// SQLite only has signed 64 bit integers. All ids handed out by the servers fit into those, but
+// ids from other sources (e.g. private servers) might not, and are rejected rather than wrapped.
+fn integer(value: impl Into<u64>) -> rusqlite::Result<Value> {
+    i64::try_from(value.into())
+        .map(Value::Integer)
+        .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))
+}
+
+fn optional_integer(value: Option<impl Into<u64>>) -> rusqlite::Result<Value> {
+    value.map(integer).unwrap_or(Ok(Value::Null))
+}
+
+impl<'a> Archivable<'a> for Level<'a> {
+    const TABLE: &'static str = "levels";
+    const COLUMNS: &'static [Column] = &[
+        Column::new("level_id", "INTEGER NOT NULL"),
+        Column::new("name", "TEXT NOT NULL"),
+        Column::new("creator", "INTEGER NOT NULL"),
+        Column::new("version", "INTEGER NOT NULL"),
+        Column::new("downloads", "INTEGER NOT NULL"),
+        Column::new("likes", "INTEGER NOT NULL"),
+        Column::new("stars", "INTEGER NOT NULL"),
+        Column::new("custom_song", "INTEGER"),
+    ];
+
+    fn column_values(&self) -> rusqlite::Result<Vec<Value>> {
+        Ok(vec![
+            integer(self.level_id)?,
+            Value::Text(self.name.to_string()),
+            integer(self.creator)?,
+            integer(self.version)?,
+            integer(self.downloads)?,
+            Value::Integer(self.likes.into()),
+            integer(self.stars)?,
+            optional_integer(self.custom_song)?,
+        ])
+    }
+}
+
+impl<'a> Archivable<'a> for Creator<'a> {
+    const TABLE: &'static str = "creators";
+    const COLUMNS: &'static [Column] = &[
+        Column::new("user_id", "INTEGER NOT NULL"),
+        Column::new("name", "TEXT NOT NULL"),
+        Column::new("account_id", "INTEGER"),
+    ];
+
+    fn column_values(&self) -> rusqlite::Result<Vec<Value>> {
+        Ok(vec![
+            integer(self.user_id)?,
+            Value::Text(self.name.to_string()),
+            optional_integer(self.account_id)?,
+        ])
+    }
+}
+
+impl<'a> Archivable<'a> for NewgroundsSong<'a> {
+    const TABLE: &'static str = "songs";
+    const COLUMNS: &'static [Column] = &[
+        Column::new("song_id", "INTEGER NOT NULL"),
+        Column::new("name", "TEXT NOT NULL"),
+        Column::new("artist", "TEXT NOT NULL"),
+        Column::new("filesize", "REAL NOT NULL"),
+    ];
+
+    fn column_values(&self) -> rusqlite::Result<Vec<Value>> {
+        Ok(vec![
+            integer(self.song_id)?,
+            Value::Text(self.name.to_string()),
+            Value::Text(self.artist.to_string()),
+            Value::Real(self.filesize),
+        ])
+    }
+}
+
+impl<'a> Archivable<'a> for Profile<'a> {
+    const TABLE: &'static str = "profiles";
+    const COLUMNS: &'static [Column] = &[
+        Column::new("account_id", "INTEGER NOT NULL"),
+        Column::new("user_id", "INTEGER NOT NULL"),
+        Column::new("name", "TEXT NOT NULL"),
+        Column::new("stars", "INTEGER NOT NULL"),
+        Column::new("demons", "INTEGER NOT NULL"),
+        Column::new("creator_points", "INTEGER NOT NULL"),
+        Column::new("global_rank", "INTEGER"),
+    ];
+
+    fn column_values(&self) -> rusqlite::Result<Vec<Value>> {
+        Ok(vec![
+            integer(self.account_id)?,
+            integer(self.user_id)?,
+            Value::Text(self.name.to_string()),
+            integer(self.stars)?,
+            integer(self.demons)?,
+            integer(self.creator_points)?,
+            optional_integer(self.global_rank)?,
+        ])
+    }
+}
@@ -0,0 +1,45 @@
+//! Module containing helpers for parsing large batches of raw objects on multiple threads
+//!
+//! These are meant for ingestion workloads, such as importing dumps of thousands of levels into
+//! an archive, where parsing on a single thread is the bottleneck. Work is distributed over
+//! [`rayon`]'s global thread pool.
+
+use alloc::vec::Vec;
+use rayon::prelude::*;
+use thiserror::Error;
+
+use crate::{model::level::Level, DeError, GJFormat};
+
+/// Error describing why a single fragment of a batch failed to parse
+#[derive(Debug, Error)]
+#[error("fragment {index}: {error}")]
+pub struct FragmentError<'a> {
+    /// The index of the fragment in the batch
+    pub index: usize,
+
+    /// The error that occurred while parsing the fragment
+    pub error: DeError<'a>,
+}
+
+/// Parses each of the given raw fragments into a `T` on rayon's global thread pool
+///
+/// The results are returned in the order of the fragments. Fragments that fail to parse do not
+/// affect the other fragments of the batch.
+pub fn parse_parallel<'a, T>(raw_fragments: &[&'a str]) -> Vec<Result<T, FragmentError<'a>>>
+where
+    T: GJFormat<'a> + Send,
+{
+    raw_fragments
+        .par_iter()
+        .enumerate()
+        .map(|(index, raw)| T::from_gj_str(raw).map_err(|error| FragmentError { index, error }))
+        .collect()
+}
+
+/// Parses each of the given raw level rows, e.g. the `|`-separated entries of the first section of
+/// a `getGJLevels` response, on rayon's global thread pool
+///
+/// See [`parse_parallel`] for details.
+pub fn parse_levels_parallel<'a>(raw_fragments: &[&'a str]) -> Vec<Result<Level<'a, ()>, FragmentError<'a>>> {
+    parse_parallel(raw_fragments)
+}
@@ -0,0 +1,178 @@
+//! Module containing caches for raw server responses
+//!
+//! Responses are keyed by [`request_key`], a hash of the endpoint and the request body. Wrapping a
+//! [`Transport`] in a [`CachingTransport`] avoids repeating identical requests while the cached
+//! response is still valid, which is mostly useful during development.
+
+use alloc::string::{String, ToString};
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::client::{TransientFailure, Transport};
+
+/// Trait for storages of raw server responses
+pub trait ResponseCache {
+    /// Retrieves the response stored under the given key, if it exists and has not expired yet
+    fn get(&mut self, key: u64) -> Option<String>;
+
+    /// Stores a response under the given key, to be valid for the given duration
+    fn put(&mut self, key: u64, response: &str, ttl: Duration);
+}
+
+/// Computes the key under which the response to the request with the given body to the given URL
+/// is cached
+///
+/// This is the 64-bit FNV-1a hash of the URL and the body. Unlike [`std::hash::Hash`], it is stable
+/// across compilations, so it can be used for persistent caches.
+pub fn request_key(url: &str, body: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    url.bytes()
+        .chain(core::iter::once(0))
+        .chain(body.bytes())
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+/// [`ResponseCache`] keeping responses in memory
+///
+/// Responses whose time-to-live is too large to be represented as an [`Instant`] (such as
+/// [`Duration::MAX`]) never expire.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: HashMap<u64, (Option<Instant>, String)>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        MemoryCache::default()
+    }
+
+    /// Removes all expired responses
+    pub fn evict_expired(&mut self) {
+        let now = Instant::now();
+
+        self.entries
+            .retain(|_, (expires_at, _)| expires_at.is_none_or(|expires_at| expires_at > now))
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get(&mut self, key: u64) -> Option<String> {
+        match self.entries.get(&key) {
+            Some((expires_at, response)) if expires_at.is_none_or(|expires_at| expires_at > Instant::now()) => Some(response.clone()),
+            _ => None,
+        }
+    }
+
+    fn put(&mut self, key: u64, response: &str, ttl: Duration) {
+        self.entries.insert(key, (Instant::now().checked_add(ttl), response.to_string()));
+    }
+}
+
+/// [`ResponseCache`] storing each response in a file inside some directory
+///
+/// Files are named after the hexadecimal representation of the response's key. The first line of
+/// each file holds the time at which the response expires, as seconds since the unix epoch, which
+/// saturates at [`u64::MAX`] (meaning the response never expires). I/O errors are logged and
+/// otherwise treated as cache misses.
+#[derive(Debug, Clone)]
+pub struct DirectoryCache {
+    directory: PathBuf,
+}
+
+impl DirectoryCache {
+    /// Constructs a new [`DirectoryCache`] storing responses in the given directory, which is
+    /// created if it does not exist yet
+    pub fn new(directory: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let directory = directory.into();
+
+        fs::create_dir_all(&directory)?;
+
+        Ok(DirectoryCache { directory })
+    }
+
+    fn path_for(&self, key: u64) -> PathBuf {
+        self.directory.join(format!("{:016x}", key))
+    }
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+impl ResponseCache for DirectoryCache {
+    fn get(&mut self, key: u64) -> Option<String> {
+        let path = self.path_for(key);
+        let contents = fs::read_to_string(&path).ok()?;
+        let (expires_at, response) = contents.split_once('\n')?;
+
+        if expires_at.parse::<u64>().ok()? > unix_time() {
+            Some(response.to_string())
+        } else {
+            None
+        }
+    }
+
+    fn put(&mut self, key: u64, response: &str, ttl: Duration) {
+        let contents = format!("{}\n{}", unix_time().saturating_add(ttl.as_secs()), response);
+
+        if let Err(err) = fs::write(self.path_for(key), contents) {
+            log::warn!("Failed to write response to cache: {}", err)
+        }
+    }
+}
+
+/// [`Transport`] that answers requests from a [`ResponseCache`] if possible, and only forwards
+/// them to the wrapped transport on cache misses
+///
+/// Responses indicating errors are not cached. These are `-1` responses and all
+/// [`TransientFailure`]s (such as empty responses and Cloudflare's rate limiting error), i.e.
+/// everything a [`RetryingTransport`](crate::client::retry::RetryingTransport) would retry.
+#[derive(Debug)]
+pub struct CachingTransport<T, C> {
+    transport: T,
+    cache: C,
+    ttl: Duration,
+}
+
+impl<T: Transport, C: ResponseCache> CachingTransport<T, C> {
+    /// Wraps `transport`, caching its responses in `cache` for the given duration
+    pub fn new(transport: T, cache: C, ttl: Duration) -> Self {
+        CachingTransport { transport, cache, ttl }
+    }
+
+    /// Gets a reference to the underlying cache
+    pub fn cache(&mut self) -> &mut C {
+        &mut self.cache
+    }
+
+    /// Unwraps this [`CachingTransport`], returning the underlying transport and cache
+    pub fn into_inner(self) -> (T, C) {
+        (self.transport, self.cache)
+    }
+}
+
+impl<T: Transport, C: ResponseCache> Transport for CachingTransport<T, C> {
+    type Error = T::Error;
+
+    fn post(&mut self, url: &str, body: String) -> Result<String, Self::Error> {
+        let key = request_key(url, &body);
+
+        if let Some(response) = self.cache.get(key) {
+            return Ok(response);
+        }
+
+        let response = self.transport.post(url, body)?;
+
+        if response.trim() != "-1" && TransientFailure::of(&response).is_none() {
+            self.cache.put(key, &response, self.ttl);
+        }
+
+        Ok(response)
+    }
+}
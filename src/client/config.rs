@@ -0,0 +1,158 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    client::{BatchError, Transport},
+    request::{GdRequest, REQUEST_BASE_URL},
+};
+
+/// Configuration for the HTTP connection to a Geometry Dash server
+///
+/// Since dash-rs does not make HTTP requests itself, this configuration is handed to the function
+/// constructing the [`Transport`] in [`ClientBuilder::build`], which is responsible for applying
+/// it to the HTTP stack in use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// The URL all endpoint paths are relative to. Defaults to [`REQUEST_BASE_URL`]
+    pub base_url: String,
+
+    /// The URL of the HTTP proxy to send requests through, if any
+    pub proxy: Option<String>,
+
+    /// Additional headers to send along with every request
+    pub headers: Vec<(String, String)>,
+
+    /// The `User-Agent` header to send, if any
+    ///
+    /// Note that boomlings rejects all requests that have a `User-Agent` set, while some private
+    /// servers require one.
+    pub user_agent: Option<String>,
+
+    /// Additional PEM encoded root certificates to trust, e.g. for private servers using
+    /// self-signed certificates
+    pub root_certificates: Vec<Vec<u8>>,
+
+    /// Whether to accept invalid TLS certificates. This is dangerous and should only be used for
+    /// testing
+    pub accept_invalid_certificates: bool,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            base_url: REQUEST_BASE_URL.to_string(),
+            proxy: None,
+            headers: Vec::new(),
+            user_agent: None,
+            root_certificates: Vec::new(),
+            accept_invalid_certificates: false,
+        }
+    }
+}
+
+/// Builder for [`Client`]s
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    config: ClientConfig,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        ClientBuilder::default()
+    }
+
+    /// Sets the URL endpoint paths are relative to, e.g. to talk to a private server instead of
+    /// boomlings. Should end with a `/`
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.config.base_url = base_url.into();
+        self
+    }
+
+    /// Sets the HTTP proxy to send requests through
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.config.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Adds a header to send along with every request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.config.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the `User-Agent` header to send. See [`ClientConfig::user_agent`]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a PEM encoded root certificate to trust
+    pub fn root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.config.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Sets whether invalid TLS certificates should be accepted. See
+    /// [`ClientConfig::accept_invalid_certificates`]
+    pub fn accept_invalid_certificates(mut self, accept: bool) -> Self {
+        self.config.accept_invalid_certificates = accept;
+        self
+    }
+
+    /// The configuration built so far
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Builds a [`Client`], constructing its [`Transport`] from the configuration using the given
+    /// function
+    pub fn build<T: Transport, E>(self, make_transport: impl FnOnce(&ClientConfig) -> Result<T, E>) -> Result<Client<T>, E> {
+        let transport = make_transport(&self.config)?;
+
+        Ok(Client {
+            config: self.config,
+            transport,
+        })
+    }
+}
+
+/// A [`Transport`] together with the [`ClientConfig`] it was constructed from
+///
+/// Requests sent through a client go to the configured base URL.
+#[derive(Debug)]
+pub struct Client<T> {
+    config: ClientConfig,
+    transport: T,
+}
+
+impl<T: Transport> Client<T> {
+    /// The configuration this client was built with
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Gets a reference to the underlying transport
+    pub fn transport(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// The full URL the given request is sent to by this client
+    pub fn url<R: GdRequest>(&self, _request: &R) -> String {
+        let mut url = self.config.base_url.clone();
+
+        url.push_str(R::ENDPOINT);
+        url
+    }
+
+    /// Sends the given request to the configured server, returning the raw response
+    ///
+    /// The response can be processed using [`GdRequest::parse_response`].
+    pub fn send<R: GdRequest>(&mut self, request: &R) -> Result<String, BatchError<T::Error>> {
+        let body = request.body()?;
+        let url = self.url(request);
+
+        self.transport.post(&url, body).map_err(BatchError::Transport)
+    }
+}
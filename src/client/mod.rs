@@ -0,0 +1,337 @@
+//! Module containing helpers for driving requests against the boomlings servers (or private
+//! servers)
+//!
+//! dash-rs does not ship an HTTP client. Instead, requests are made through a [`Transport`], which
+//! is implemented for any closure taking the endpoint URL and the request body, and returning the
+//! response body. This allows using whatever HTTP stack the application already depends on. Any
+//! [`GdRequest`] can be sent through a transport using [`send`].
+//! Responses can be cached by wrapping a transport in a [`CachingTransport`](cache::CachingTransport),
+//! and failed requests can be retried by wrapping it in a
+//! [`RetryingTransport`].
+//! All pages of a [`Paginated`] request can be retrieved using a [`PageIterator`].
+//!
+//! Connection settings such as the server's base URL, proxies, additional headers or TLS options
+//! are configured on a [`ClientBuilder`], whose resulting [`ClientConfig`] is handed to the
+//! function constructing the transport.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Debug;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::{
+    client::retry::{Failure, RetryHooks, RetryPolicy, RetryingTransport},
+    request::{level::LevelRequest, GdRequest, Paginated, RequestValidationError},
+    response::ResponseError,
+};
+
+pub use config::{Client, ClientBuilder, ClientConfig};
+pub use rate_limit::RateLimiter;
+
+pub mod cache;
+mod config;
+mod rate_limit;
+pub mod retry;
+
+/// The body of the response Cloudflare sends instead of forwarding a request if the client is
+/// being rate limited (it is Cloudflare's version of `HTTP 429 TOO MANY REQUESTS`)
+pub const CLOUDFLARE_RATE_LIMITED: &str = "error code: 1015";
+
+/// Transient failures indicated by a server's response, after which repeating the request later
+/// might succeed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientFailure {
+    /// Cloudflare blocked the request due to rate limiting, see [`CLOUDFLARE_RATE_LIMITED`]
+    RateLimited,
+
+    /// The server responded with an empty body
+    EmptyResponse,
+}
+
+impl TransientFailure {
+    /// Checks whether the given response body indicates a transient failure
+    ///
+    /// This is the single place deciding which responses are retried, by both
+    /// [`RetryingTransport`] and the batch operations. Note that a `-1`
+    /// response, RobTop's version of "not found", is final and thus never considered transient.
+    pub fn of(response: &str) -> Option<TransientFailure> {
+        let response = response.trim();
+
+        if response.is_empty() {
+            Some(TransientFailure::EmptyResponse)
+        } else if response.starts_with(CLOUDFLARE_RATE_LIMITED) {
+            Some(TransientFailure::RateLimited)
+        } else {
+            None
+        }
+    }
+}
+
+/// Trait describing how requests are sent to the Geometry Dash servers
+pub trait Transport {
+    type Error: Debug;
+
+    /// Sends a `POST` request with the given url-encoded body to the given URL, and returns the
+    /// response body
+    ///
+    /// Note that boomlings rejects requests that have a `User-Agent` header set.
+    fn post(&mut self, url: &str, body: String) -> Result<String, Self::Error>;
+}
+
+impl<F, E> Transport for F
+where
+    F: FnMut(&str, String) -> Result<String, E>,
+    E: Debug,
+{
+    type Error = E;
+
+    fn post(&mut self, url: &str, body: String) -> Result<String, E> {
+        self(url, body)
+    }
+}
+
+/// Errors that can occur for a single request during a batch operation
+#[derive(Debug, Error)]
+pub enum BatchError<E: Debug> {
+    /// The server kept rate limiting us, even after backing off. Retrying later might succeed.
+    ///
+    /// Once this error occurs, the batch operation is aborted and all requests that were not yet
+    /// made are reported with this error as well.
+    #[error("rate limited")]
+    RateLimited,
+
+    /// The server kept responding with an empty body, even after backing off
+    #[error("empty response")]
+    EmptyResponse,
+
+    /// The server responded with an error, such as [`ResponseError::NotFound`] for a `-1`
+    /// response. Such responses are final and are not retried.
+    #[error("{0}")]
+    Response(ResponseError<'static>),
+
+    /// The transport failed to make the request
+    #[error("transport error: {0:?}")]
+    Transport(E),
+
+    /// The request was invalid and was not sent
+    #[error("{0}")]
+    Invalid(#[from] RequestValidationError),
+}
+
+impl<E: Debug> BatchError<E> {
+    /// Whether repeating the request at a later point might succeed
+    pub fn is_retriable(&self) -> bool {
+        !matches!(self, BatchError::Response(_) | BatchError::Invalid(_))
+    }
+}
+
+/// Configuration for batch operations such as [`batch_download_levels`]
+#[derive(Debug, Clone, Copy)]
+pub struct BatchConfig {
+    /// How long to wait before retrying a request after the server indicated that we are being
+    /// rate limited. Doubled after every retry of the same request.
+    pub backoff: Duration,
+
+    /// How often a single request is retried before giving up
+    pub max_retries: u32,
+}
+
+impl From<BatchConfig> for RetryPolicy {
+    /// The policy by which batch operations retry requests. Errors reported by the transport are not
+    /// retried.
+    fn from(config: BatchConfig) -> Self {
+        RetryPolicy {
+            max_attempts: config.max_retries.saturating_add(1),
+            initial_backoff: config.backoff,
+            multiplier: 2.0,
+            max_backoff: Duration::MAX,
+            jitter: 0.0,
+            retry_transport_errors: false,
+        }
+    }
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        BatchConfig {
+            backoff: Duration::from_secs(60),
+            max_retries: 2,
+        }
+    }
+}
+
+/// The (possibly partial) result of a batch operation
+#[derive(Debug)]
+pub struct BatchResult<K, E: Debug> {
+    /// The raw responses of all successful requests, together with the key identifying the request
+    pub succeeded: Vec<(K, String)>,
+
+    /// All requests that failed, together with the reason they failed
+    pub failed: Vec<(K, BatchError<E>)>,
+}
+
+/// Sends the given request through the given transport, returning the raw response
+///
+/// The response can be processed using [`GdRequest::parse_response`]. Note that this does not
+/// interpret the response in any way, meaning that `-1` responses are not turned into errors.
+pub fn send<R: GdRequest, T: Transport>(transport: &mut T, request: &R) -> Result<String, BatchError<T::Error>> {
+    let body = request.body()?;
+
+    transport.post(&request.url(), body).map_err(BatchError::Transport)
+}
+
+/// Iterator sending a [`Paginated`] request for consecutive pages until all pages have been
+/// retrieved
+///
+/// Iteration starts at the request's [current page](Paginated::current_page). The raw responses are
+/// yielded, and can be processed using [`GdRequest::parse_response`]. Iteration stops after the
+/// first error, after a page whose [`PageInfo`](crate::response::PageInfo) indicates that no
+/// further pages exist, or when the server responds with `-1` (which it does for pages past the
+/// last one).
+#[derive(Debug)]
+pub struct PageIterator<'t, R, T> {
+    transport: &'t mut T,
+    request: R,
+    exhausted: bool,
+}
+
+impl<'t, R: Paginated, T: Transport> PageIterator<'t, R, T> {
+    /// Constructs an iterator sending the given request through the given transport
+    pub fn new(transport: &'t mut T, request: R) -> Self {
+        PageIterator {
+            transport,
+            request,
+            exhausted: false,
+        }
+    }
+
+    /// The request that will be sent for the next page
+    pub fn request(&self) -> &R {
+        &self.request
+    }
+}
+
+impl<R: Paginated, T: Transport> Iterator for PageIterator<'_, R, T> {
+    type Item = Result<String, BatchError<T::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let response = match send(self.transport, &self.request) {
+            Ok(response) if response.trim() == "-1" => {
+                self.exhausted = true;
+
+                return None;
+            },
+            Ok(response) => response,
+            Err(err) => {
+                self.exhausted = true;
+
+                return Some(Err(err));
+            },
+        };
+
+        self.exhausted = !R::page_info(&response).is_some_and(|page_info| page_info.has_more());
+        self.request.set_page(self.request.current_page() + 1);
+
+        Some(Ok(response))
+    }
+}
+
+/// Sends the given requests, respecting the given [`RateLimiter`]
+///
+/// Each request is associated with a key identifying it in the returned [`BatchResult`]. The raw
+/// responses are returned, and can be processed using [`GdRequest::parse_response`].
+///
+/// If the server responds with a [`TransientFailure`], such as Cloudflare's rate limiting error,
+/// the request is retried after backing off as described by `config`, using a
+/// [`RetryingTransport`]. A `-1` response is reported
+/// as [`ResponseError::NotFound`] right away, since that is how boomlings responds to requests for
+/// non-existing objects. Being rate limited persistently aborts the batch.
+pub fn batch_send<K, R: GdRequest, T: Transport>(
+    requests: impl IntoIterator<Item = (K, R)>, transport: &mut T, limiter: &mut RateLimiter, config: BatchConfig,
+) -> BatchResult<K, T::Error> {
+    let mut result = BatchResult {
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+    };
+    let mut requests = requests.into_iter();
+    let rate_limited = |url: &str, body: String| {
+        limiter.acquire();
+
+        let response = transport.post(url, body)?;
+
+        // Back off from all further requests, not just the one being retried
+        if TransientFailure::of(&response).is_some() {
+            limiter.drain();
+        }
+
+        Ok(response)
+    };
+    let mut transport = RetryingTransport::new(rate_limited, config.into()).with_hooks(LogRetries);
+
+    for (key, request) in &mut requests {
+        match send_with_retries(&mut transport, &request) {
+            Ok(response) => result.succeeded.push((key, response)),
+            Err(BatchError::RateLimited) => {
+                result.failed.push((key, BatchError::RateLimited));
+                result.failed.extend(requests.map(|(key, _)| (key, BatchError::RateLimited)));
+
+                break;
+            },
+            Err(err) => result.failed.push((key, err)),
+        }
+    }
+
+    result
+}
+
+/// Downloads the levels with the given ids, respecting the given [`RateLimiter`]
+///
+/// The raw responses are returned, and can be processed using
+/// [`parse_download_gj_level_response`](crate::response::parse_download_gj_level_response). See
+/// [`batch_send`] for details on error handling. Ids that cannot be requested (see
+/// [`LevelRequest::try_new`]) are reported as [`BatchError::Invalid`] after all other failures.
+pub fn batch_download_levels<T: Transport>(
+    level_ids: impl IntoIterator<Item = u64>, transport: &mut T, limiter: &mut RateLimiter, config: BatchConfig,
+) -> BatchResult<u64, T::Error> {
+    let mut invalid = Vec::new();
+    let requests = level_ids.into_iter().filter_map(|level_id| match LevelRequest::try_new(level_id) {
+        Ok(request) => Some((level_id, request)),
+        Err(err) => {
+            invalid.push((level_id, BatchError::Invalid(err)));
+
+            None
+        },
+    });
+
+    let mut result = batch_send(requests, transport, limiter, config);
+
+    result.failed.extend(invalid);
+    result
+}
+
+/// Logs retries made during batch operations
+struct LogRetries;
+
+impl<E: Debug> RetryHooks<E> for LogRetries {
+    fn on_retry(&mut self, url: &str, _attempt: u32, failure: Failure<'_, E>, delay: Duration) {
+        log::warn!("Request to {} failed ({:?}), retrying in {:?}", url, failure, delay);
+    }
+}
+
+fn send_with_retries<R: GdRequest, T: Transport>(transport: &mut T, request: &R) -> Result<String, BatchError<T::Error>> {
+    let response = send(transport, request)?;
+
+    if response.trim() == "-1" {
+        return Err(BatchError::Response(ResponseError::NotFound));
+    }
+
+    match TransientFailure::of(&response) {
+        Some(TransientFailure::RateLimited) => Err(BatchError::RateLimited),
+        Some(TransientFailure::EmptyResponse) => Err(BatchError::EmptyResponse),
+        None => Ok(response),
+    }
+}
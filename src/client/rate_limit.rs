@@ -0,0 +1,87 @@
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting the rate at which requests are made
+///
+/// The bucket starts out full. Each request consumes one token, and tokens are refilled
+/// continuously at the configured rate, up to the bucket's capacity (its "burst" size).
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    tokens: f64,
+    tokens_per_second: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Constructs a new [`RateLimiter`] allowing `requests_per_second` requests per second, with a
+    /// burst size of one
+    ///
+    /// ## Panics
+    /// Panics if `requests_per_second` is not a positive, finite number
+    pub fn new(requests_per_second: f64) -> Self {
+        assert!(
+            requests_per_second.is_finite() && requests_per_second > 0.0,
+            "rate limit must be positive and finite"
+        );
+
+        RateLimiter {
+            capacity: 1.0,
+            tokens: 1.0,
+            tokens_per_second: requests_per_second,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Constructs a [`RateLimiter`] matching the rate limit of boomlings' `downloadGJLevel22.php`
+    /// endpoint, which allows 20 downloads per minute
+    ///
+    /// Exceeding this limit results in being blocked for an hour.
+    pub fn boomlings_downloads() -> Self {
+        RateLimiter::new(20.0 / 60.0)
+    }
+
+    /// Sets the number of requests that can be made in quick succession after the limiter was idle
+    /// for a while
+    ///
+    /// Allows builder-style creation of rate limiters
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.capacity = f64::from(burst.max(1));
+        self.tokens = self.capacity;
+        self
+    }
+
+    /// Attempts to consume a token without blocking. On failure, returns how long to wait until a
+    /// token becomes available
+    pub fn try_acquire(&mut self) -> Result<(), Duration> {
+        self.refill();
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.tokens_per_second))
+        }
+    }
+
+    /// Blocks the current thread until a token is available, and consumes it
+    pub fn acquire(&mut self) {
+        while let Err(wait) = self.try_acquire() {
+            std::thread::sleep(wait)
+        }
+    }
+
+    /// Empties the bucket, e.g. after the server indicated that the rate limit was exceeded
+    pub fn drain(&mut self) {
+        self.refill();
+        self.tokens = 0.0;
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.tokens_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+}
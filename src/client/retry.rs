@@ -0,0 +1,288 @@
+//! Module containing a [`Transport`] middleware retrying failed requests
+//!
+//! Failures are classified into retriable ones (errors reported by the underlying transport, such
+//! as timeouts, and [`TransientFailure`]s such as Cloudflare blocking the request) and permanent
+//! ones. Note that a `-1` response, RobTop's version of "not found", is considered a valid response
+//! and returned as-is.
+
+use alloc::string::String;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+use thiserror::Error;
+
+use crate::client::{TransientFailure, Transport};
+
+/// Errors indicating that a [`RetryPolicy`] was configured with values that do not make sense
+#[derive(Debug, Error, Clone, Copy, PartialEq)]
+pub enum RetryPolicyError {
+    /// The multiplier was negative, or not a number
+    #[error("backoff multiplier must be a non-negative number, got {0}")]
+    InvalidMultiplier(f64),
+
+    /// The jitter was outside of the `0.0..=1.0` range, or not a number
+    #[error("jitter must be between 0.0 and 1.0, got {0}")]
+    InvalidJitter(f64),
+}
+
+/// Configuration of how often and after how long failed requests are retried
+///
+/// Policies with invalid values (e.g. created by setting the fields directly) never cause panics:
+/// invalid multipliers make every retry wait for [`RetryPolicy::max_backoff`], and invalid jitter
+/// is ignored. Use the builder-style methods or [`RetryPolicy::validate`] to catch such mistakes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts made for a single request, including the first one
+    pub max_attempts: u32,
+
+    /// The delay before the first retry
+    pub initial_backoff: Duration,
+
+    /// The factor by which the delay grows with each further retry
+    pub multiplier: f64,
+
+    /// The upper bound for the delay between two attempts, before jitter is applied
+    pub max_backoff: Duration,
+
+    /// The fraction (between `0.0` and `1.0`) by which each delay is randomly shortened, to
+    /// avoid multiple clients retrying in lockstep
+    pub jitter: f64,
+
+    /// Whether errors reported by the underlying transport are retried. If `false`, only
+    /// unsuccessful responses are retried
+    pub retry_transport_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+            multiplier: 2.0,
+            max_backoff: Duration::from_secs(60),
+            jitter: 0.1,
+            retry_transport_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub const fn never() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            initial_backoff: Duration::ZERO,
+            multiplier: 1.0,
+            max_backoff: Duration::ZERO,
+            jitter: 0.0,
+            retry_transport_errors: false,
+        }
+    }
+
+    /// Sets the delay before the first retry, the factor by which it grows with each further retry,
+    /// and its upper bound
+    ///
+    /// Allows builder-style creation of policies
+    pub fn with_backoff(mut self, initial: Duration, multiplier: f64, max: Duration) -> Result<Self, RetryPolicyError> {
+        self.initial_backoff = initial;
+        self.multiplier = multiplier;
+        self.max_backoff = max;
+        self.validate().map(|_| self)
+    }
+
+    /// Sets the fraction by which each delay is randomly shortened
+    ///
+    /// Allows builder-style creation of policies
+    pub fn with_jitter(mut self, jitter: f64) -> Result<Self, RetryPolicyError> {
+        self.jitter = jitter;
+        self.validate().map(|_| self)
+    }
+
+    /// Checks that the multiplier is a non-negative number, and that the jitter is between `0.0`
+    /// and `1.0`
+    pub fn validate(&self) -> Result<(), RetryPolicyError> {
+        if self.multiplier.is_nan() || self.multiplier < 0.0 {
+            return Err(RetryPolicyError::InvalidMultiplier(self.multiplier));
+        }
+
+        if !(0.0..=1.0).contains(&self.jitter) {
+            return Err(RetryPolicyError::InvalidJitter(self.jitter));
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the delay before the given retry (starting at `0` for the first retry),
+    /// without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.powi(retry.min(i32::MAX as u32) as i32);
+        let seconds = self.initial_backoff.as_secs_f64() * factor;
+
+        // The factor overflows to infinity for large retry counts, and is negative or NaN for
+        // invalid multipliers, none of which `Duration` can represent
+        match Duration::try_from_secs_f64(seconds) {
+            Ok(backoff) => backoff.min(self.max_backoff),
+            Err(_) if seconds > 0.0 => self.max_backoff,
+            Err(_) if self.initial_backoff.is_zero() => Duration::ZERO,
+            Err(_) => self.max_backoff,
+        }
+    }
+
+    fn jittered_backoff(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+
+        if self.jitter.is_nan() || self.jitter <= 0.0 {
+            return backoff;
+        }
+
+        // We only need some randomness, not quality randomness, so just use the randomly seeded
+        // hasher from the standard library instead of depending on `rand`
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+
+        // Multiplying the `Duration` directly panics if the result rounds up past `Duration::MAX`
+        let seconds = backoff.as_secs_f64() * (1.0 - self.jitter.min(1.0) * random);
+
+        Duration::try_from_secs_f64(seconds).unwrap_or(self.max_backoff)
+    }
+}
+
+/// A failed attempt at making a request
+#[derive(Debug)]
+pub enum Failure<'a, E> {
+    /// The underlying transport reported an error
+    Transport(&'a E),
+
+    /// Cloudflare blocked the request due to rate limiting
+    RateLimited,
+
+    /// The server responded with an empty body
+    EmptyResponse,
+}
+
+/// Hooks invoked by a [`RetryingTransport`], e.g. for collecting metrics
+///
+/// All methods do nothing by default. The unit type implements this trait with no hooks at all.
+pub trait RetryHooks<E> {
+    /// Called before sleeping to retry a request after the given attempt (starting at `1`) failed
+    fn on_retry(&mut self, _url: &str, _attempt: u32, _failure: Failure<'_, E>, _delay: Duration) {}
+
+    /// Called when a request failed after the given number of attempts and will not be retried
+    fn on_give_up(&mut self, _url: &str, _attempts: u32, _failure: Failure<'_, E>) {}
+
+    /// Called when a request succeeded after the given number of attempts
+    fn on_success(&mut self, _url: &str, _attempts: u32) {}
+}
+
+impl<E> RetryHooks<E> for () {}
+
+/// [`Transport`] retrying failed requests made through the wrapped transport according to a
+/// [`RetryPolicy`]
+///
+/// If a request still fails after the last attempt, the outcome of the last attempt is returned
+/// unchanged.
+#[derive(Debug)]
+pub struct RetryingTransport<T, H = ()> {
+    transport: T,
+    policy: RetryPolicy,
+    hooks: H,
+}
+
+impl<T: Transport> RetryingTransport<T> {
+    /// Wraps `transport`, retrying requests according to the given policy
+    pub fn new(transport: T, policy: RetryPolicy) -> Self {
+        RetryingTransport {
+            transport,
+            policy,
+            hooks: (),
+        }
+    }
+}
+
+impl<T: Transport, H: RetryHooks<T::Error>> RetryingTransport<T, H> {
+    /// Sets the hooks to invoke when retrying requests
+    ///
+    /// Allows builder-style creation of transports
+    pub fn with_hooks<H2: RetryHooks<T::Error>>(self, hooks: H2) -> RetryingTransport<T, H2> {
+        RetryingTransport {
+            transport: self.transport,
+            policy: self.policy,
+            hooks,
+        }
+    }
+
+    /// Gets a reference to the hooks of this transport
+    pub fn hooks(&mut self) -> &mut H {
+        &mut self.hooks
+    }
+
+    /// Unwraps this [`RetryingTransport`], returning the underlying transport and hooks
+    pub fn into_inner(self) -> (T, H) {
+        (self.transport, self.hooks)
+    }
+}
+
+impl<T, H> Transport for RetryingTransport<T, H>
+where
+    T: Transport,
+    H: RetryHooks<T::Error>,
+{
+    type Error = T::Error;
+
+    fn post(&mut self, url: &str, body: String) -> Result<String, Self::Error> {
+        let mut attempt = 1;
+
+        loop {
+            let result = self.transport.post(url, body.clone());
+
+            let failure = match &result {
+                Err(err) if self.policy.retry_transport_errors => Failure::Transport(err),
+                Err(_) => return result,
+                Ok(response) => match TransientFailure::of(response) {
+                    Some(TransientFailure::RateLimited) => Failure::RateLimited,
+                    Some(TransientFailure::EmptyResponse) => Failure::EmptyResponse,
+                    None => {
+                        self.hooks.on_success(url, attempt);
+
+                        return result;
+                    },
+                },
+            };
+
+            if attempt >= self.policy.max_attempts {
+                self.hooks.on_give_up(url, attempt, failure);
+
+                return result;
+            }
+
+            let delay = self.policy.jittered_backoff(attempt - 1);
+
+            self.hooks.on_retry(url, attempt, failure, delay);
+
+            std::thread::sleep(delay);
+
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::retry::RetryPolicy;
+    use std::time::Duration;
+
+    #[test]
+    fn jittered_backoff_near_max_duration() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::MAX,
+            max_backoff: Duration::MAX,
+            jitter: 0.5,
+            ..RetryPolicy::default()
+        };
+
+        for retry in [0, 1, u32::MAX] {
+            assert!(policy.jittered_backoff(retry) >= Duration::MAX / 2);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Module containing helpers for offline analysis of large dumps of server responses
+//!
+//! A [`Corpus`] loads a file of newline-separated raw responses into memory once. Since dash-rs'
+//! models borrow from their input, the values parsed from a corpus point directly into that
+//! buffer: nothing is copied per response. Responses are only parsed when iterated over, so only a
+//! single parsed level has to be held in memory at a time, even for dumps containing millions of
+//! levels.
+//!
+//! dash-rs does not memory-map corpora itself: a mapped file can be truncated or rewritten by
+//! another process while dash-rs hands out `&str`s pointing into it, which is undefined behavior
+//! that safe code could not guard against. Callers who can guarantee that the file is not modified
+//! while in use can still map it themselves (e.g. using `memmap2`) and wrap the mapping in a
+//! [`Corpus`] via [`Corpus::from_bytes`], to avoid reading dumps larger than the available memory.
+
+use core::str::Utf8Error;
+use std::{fs, io, path::Path};
+
+use thiserror::Error;
+
+use crate::{
+    model::level::Level,
+    response::{self, ResponseError},
+};
+
+/// Errors that can occur while iterating over the responses in a [`Corpus`]
+#[derive(Debug, Error)]
+pub enum CorpusError<'a> {
+    /// The given line of the corpus is not valid UTF-8
+    #[error("line {line}: {error}")]
+    Utf8 {
+        /// The 1-based number of the offending line
+        line: usize,
+        error: Utf8Error,
+    },
+
+    /// The response on the given line of the corpus could not be parsed
+    #[error("line {line}: {error}")]
+    Parse {
+        /// The 1-based number of the offending line
+        line: usize,
+        error: ResponseError<'a>,
+    },
+}
+
+/// A buffer of newline-separated raw server responses
+///
+/// By default, the buffer is a file loaded into memory (see [`Corpus::open`]), but any byte buffer
+/// can be used, such as a memory-mapped file (see [`Corpus::from_bytes`]).
+#[derive(Debug, Clone)]
+pub struct Corpus<B = Vec<u8>> {
+    data: B,
+}
+
+impl Corpus {
+    /// Reads the file at the given path into memory
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Corpus> {
+        Ok(Corpus { data: fs::read(path)? })
+    }
+}
+
+impl<B: AsRef<[u8]>> Corpus<B> {
+    /// Constructs a corpus from a buffer that already holds its contents
+    ///
+    /// This allows parsing responses that are already in memory, or memory-mapping large corpora,
+    /// see the module-level documentation.
+    pub fn from_bytes(data: B) -> Corpus<B> {
+        Corpus { data }
+    }
+
+    /// The raw contents of this corpus
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    /// Unwraps this corpus, returning the underlying buffer
+    pub fn into_inner(self) -> B {
+        self.data
+    }
+
+    /// Iterates over the non-empty lines of this corpus, together with their 1-based line numbers
+    ///
+    /// Both `\n` and `\r\n` line endings are supported.
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            remaining: self.as_bytes(),
+            line: 0,
+        }
+    }
+
+    /// Lazily parses each line of this corpus using the given response parser
+    ///
+    /// Lines that fail to parse result in an error being yielded for that line, iteration then
+    /// continues with the next line.
+    pub fn responses<'a, T, F>(&'a self, mut parse: F) -> impl Iterator<Item = Result<T, CorpusError<'a>>> + 'a
+    where
+        F: FnMut(&'a str) -> Result<T, ResponseError<'a>> + 'a,
+    {
+        self.lines().map(move |(line, raw)| {
+            let raw = core::str::from_utf8(raw).map_err(|error| CorpusError::Utf8 { line, error })?;
+
+            parse(raw).map_err(|error| CorpusError::Parse { line, error })
+        })
+    }
+
+    /// Lazily parses each line of this corpus as the response to a `downloadGJLevel` request
+    pub fn levels(&self) -> impl Iterator<Item = Result<Level<'_>, CorpusError<'_>>> + '_ {
+        self.responses(response::parse_download_gj_level_response)
+    }
+}
+
+/// Iterator over the non-empty lines of a [`Corpus`], see [`Corpus::lines`]
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    remaining: &'a [u8],
+    line: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = (usize, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.remaining.is_empty() {
+            let (line, rest) = match crate::split::find_byte(b'\n', self.remaining) {
+                Some(end) => (&self.remaining[..end], &self.remaining[end + 1..]),
+                None => (self.remaining, &[][..]),
+            };
+
+            self.remaining = rest;
+            self.line += 1;
+
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+            if !line.is_empty() {
+                return Some((self.line, line));
+            }
+        }
+
+        None
+    }
+}
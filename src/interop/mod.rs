@@ -0,0 +1,7 @@
+//! Module containing adapters for feeding dash-rs' models into third party software
+//!
+//! Each submodule targets a specific piece of software and is gated behind a feature of the same
+//! name. The module itself is only available if at least one of these features is enabled.
+
+#[cfg(feature = "pointercrate")]
+pub mod pointercrate;
@@ -0,0 +1,104 @@
+//! Module containing conversions into the JSON shapes used by [pointercrate](https://pointercrate.com)
+//! and other demon list software modelled after its API
+//!
+//! The structs in this module only implement [`Serialize`] and [`Deserialize`]. Use any serde
+//! compatible JSON library to turn them into request bodies.
+//!
+//! Demon lists track information the boomlings servers do not know about, such as a demon's
+//! position on the list or who verified it. These fields are left as `None` by the conversions
+//! from dash-rs' models and have to be filled in by the caller.
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{creator::Creator, level::Level};
+
+/// A player, as embedded in pointercrate's demon objects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Player<'a> {
+    /// The player's name. For players converted from a [`Creator`], this is their in-game name
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+
+    /// Whether the player is banned from the list. Always `false` for converted [`Creator`]s
+    pub banned: bool,
+}
+
+impl<'a> From<&'a Creator<'_>> for Player<'a> {
+    fn from(creator: &'a Creator<'_>) -> Self {
+        Player {
+            name: Cow::Borrowed(&creator.name),
+            banned: false,
+        }
+    }
+}
+
+/// A demon, in the shape pointercrate expects when adding a demon to the list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemonSubmission<'a> {
+    /// The demon's name
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+
+    /// The demon's position on the list. `None` if not yet determined
+    pub position: Option<u16>,
+
+    /// The minimal progress (in percent) a record needs to be accepted. `None` if not yet
+    /// determined
+    pub requirement: Option<u8>,
+
+    /// The name of the player who verified the demon. `None` if not yet determined, as the
+    /// boomlings servers do not provide this information
+    #[serde(borrow)]
+    pub verifier: Option<Cow<'a, str>>,
+
+    /// The name of the player who published the demon
+    #[serde(borrow)]
+    pub publisher: Option<Cow<'a, str>>,
+
+    /// The names of the players who created the demon
+    ///
+    /// For converted [`Level`]s, this contains only the publisher, as the boomlings servers do not
+    /// provide a list of all creators
+    #[serde(borrow)]
+    pub creators: Vec<Cow<'a, str>>,
+
+    /// A link to the verification video. `None` if not yet determined
+    #[serde(borrow)]
+    pub video: Option<Cow<'a, str>>,
+
+    /// The demon's in-game level id
+    pub level_id: Option<u64>,
+}
+
+impl<'a> DemonSubmission<'a> {
+    /// Constructs a submission from the given [`Level`], without any publisher information
+    ///
+    /// For levels whose creator is available as a [`Creator`], use the [`From`] implementation
+    /// instead, which also fills in the publisher.
+    pub fn from_level<Data, Song, User>(level: &'a Level<'_, Data, Song, User>) -> Self {
+        DemonSubmission {
+            name: Cow::Borrowed(&level.name),
+            position: None,
+            requirement: None,
+            verifier: None,
+            publisher: None,
+            creators: Vec::new(),
+            video: None,
+            level_id: Some(level.level_id),
+        }
+    }
+}
+
+impl<'a, Data, Song> From<&'a Level<'_, Data, Song, Option<Creator<'_>>>> for DemonSubmission<'a> {
+    fn from(level: &'a Level<'_, Data, Song, Option<Creator<'_>>>) -> Self {
+        let publisher = level.creator.as_ref().map(|creator| Cow::Borrowed(&*creator.name));
+
+        DemonSubmission {
+            creators: publisher.iter().cloned().collect(),
+            publisher,
+            ..DemonSubmission::from_level(level)
+        }
+    }
+}
@@ -1,9 +1,41 @@
 #![forbid(unsafe_code)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+#[cfg(feature = "archive")]
+pub mod archive;
+#[cfg(feature = "parallel")]
+pub mod batch;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "corpus")]
+pub mod corpus;
+#[cfg(feature = "pointercrate")]
+pub mod interop;
 pub mod model;
 pub mod request;
 pub mod response;
+pub mod schema;
 pub(crate) mod serde;
+mod split;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "arena")]
+pub use crate::serde::ArenaProcessor;
+#[cfg(feature = "std")]
+pub use crate::serde::ReaderDeserializer;
+pub use crate::serde::{
+    from_indexed_str, to_indexed_string, ByteDelimiter, Dash, DeError, Delimiter, DuplicateIndexPolicy, DynamicDelimiter, GJBytes,
+    GJFormat, IndexedDeserializer, IndexedSerializer, LegacySet, PercentDecoder, PercentEncodeSet, ProcessError, RawFields, Rfc3986Set,
+    RobtopSet, SerError, StrProxy, ThreeByteDelimiter, Thunk, ThunkProcessor, ThunkProxy, Utf8Policy, Write, LEGACY_SET, RFC3986_SET,
+    ROBTOP_SET,
+};
 
-pub use crate::serde::{Dash, DeError, GJFormat, IndexedDeserializer, IndexedSerializer, ProcessError, SerError, Thunk, ThunkProcessor};
+/// Re-export of the arena allocator used by [`Thunk::process_in`]
+#[cfg(feature = "arena")]
+pub use bumpalo;
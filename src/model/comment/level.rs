@@ -1,17 +1,19 @@
-use std::borrow::Cow;
-// use std::borrow::Cow;
+use alloc::{borrow::Cow, format};
+// use alloc::borrow::Cow;
 
-use dash_rs_derive::Dash;
+use dash_rs_derive::{Dash, DashPartialEq};
 use serde::{Deserialize, Serialize};
-use variant_partial_eq::VariantPartialEq;
 
 use crate::{
-    model::user::{Color, IconType, ModLevel},
+    model::{
+        user::{Color, IconType, ModLevel},
+        RelativeAge,
+    },
     serde::{Base64Decoder, Thunk},
     GJFormat, ProcessError, ThunkProcessor,
 };
 
-#[derive(Debug, Eq, VariantPartialEq, Clone, Deserialize, Serialize, Dash)]
+#[derive(Debug, Eq, DashPartialEq, Clone, Deserialize, Serialize, Dash)]
 pub struct LevelComment<'a> {
     /// Information about the user that made this [`LevelComment`]. Is generally a [`CommentUser`]
     /// object
@@ -21,7 +23,7 @@ pub struct LevelComment<'a> {
     /// The actual content of the [`LevelComment`] made.
     #[dash(index = 2)]
     #[serde(borrow)]
-    #[variant_compare = "crate::util::option_variant_eq"]
+    #[dash(compare_with = "crate::util::option_variant_eq")]
     pub content: Option<Thunk<'a, Base64Decoder>>,
 
     /// The unique user id of the player who made this [`LevelComment`]
@@ -39,19 +41,36 @@ pub struct LevelComment<'a> {
 
     /// Whether this [`LevelComment`] has been flagged as spam (because of having received too many
     /// dislikes or for other reasons)
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `7` (called `isSpam` in the game's code), as `0` or `1`
     #[dash(index = 7)]
     pub is_flagged_spam: bool,
 
     /// Robtop's completely braindead way of keeping track of when this [`LevelComment`] was posted
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `9`, as a string of the form `"5 days"`
+    #[serde(borrow)]
     #[dash(index = 9)]
-    pub time_since_post: Cow<'a, str>,
+    pub time_since_post: Thunk<'a, RelativeAge>,
 
-    /// If enabled by the user making this [`LevelComment`], the progress they have done on the
-    /// level this comment is on.
+    /// If enabled by the user making this [`LevelComment`], the progress (in percent) they have
+    /// done on the level this comment is on.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `10`. A value of `0` means that the progress is not
+    /// displayed, and is mapped to [`None`].
     #[dash(index = 10)]
+    #[dash(default)]
+    #[dash(with = "crate::util::serde_adapters::default_to_none")]
     pub progress: Option<u8>,
 
     /// The level of moderator the player that made this [`LevelComment`] is
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `11`, with `0` meaning no moderator, `1` a normal and `2` an
+    /// elder moderator
     #[dash(index = 11)]
     pub mod_level: ModLevel,
 
@@ -60,7 +79,7 @@ pub struct LevelComment<'a> {
     ///
     /// Note that the yellow color of comments made by the creator is not reported here.
     #[dash(index = 12)]
-    #[variant_compare = "crate::util::option_variant_eq"]
+    #[dash(compare_with = "crate::util::option_variant_eq")]
     pub special_color: Option<Thunk<'a, Color>>,
 }
 
@@ -69,11 +88,24 @@ impl<'de> GJFormat<'de> for LevelComment<'de> {
     const MAP_LIKE: bool = true;
 }
 
+impl LevelComment<'_> {
+    /// Whether the text of this [`LevelComment`] is displayed in a moderator color, i.e. whether it
+    /// was made by an elder moderator (or RobTop)
+    ///
+    /// ## GD Internals:
+    /// The servers send the color to use in [`LevelComment::special_color`], but only elder
+    /// moderators' comments are colored in-game, even if no color was sent. Comments of normal
+    /// moderators carry a white color (`255,255,255`), which is not displayed.
+    pub fn is_moderator_colored(&self) -> bool {
+        self.mod_level == ModLevel::Elder
+    }
+}
+
 impl ThunkProcessor for Color {
     type Error = ProcessError;
     type Output<'a> = Color;
 
-    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error> {
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
         let mut split = unprocessed.split(',');
 
         let r = split.next();
@@ -102,7 +134,7 @@ impl ThunkProcessor for Color {
     }
 }
 
-#[derive(Debug, Eq, VariantPartialEq, Clone, Deserialize, Serialize, Dash)]
+#[derive(Debug, Eq, DashPartialEq, Clone, Deserialize, Serialize, Dash)]
 pub struct CommentUser<'a> {
     /// This [`CommentUser`]'s name
     #[dash(index = 1)]
@@ -132,7 +164,7 @@ pub struct CommentUser<'a> {
 
     /// Values indicating whether this [`CommentUser`] has glow activated or not.
     #[dash(index = 15)]
-    #[dash(serialize_with = "crate::util::true_to_two")]
+    #[dash(serialize_with = "crate::util::serde_adapters::true_to_two")]
     pub has_glow: bool,
 
     /// The [`CommentUser`]'s unique account ID
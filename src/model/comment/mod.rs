@@ -1,2 +1,66 @@
+use alloc::borrow::Cow;
+use core::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+use serde::{Deserialize, Serialize};
+
 pub mod level;
 pub mod profile;
+
+/// Information about a ban from posting comments, as returned by the servers when trying to upload
+/// a comment while banned
+///
+/// ## GD Internals:
+/// Temporary bans are reported as `temp_<seconds>_<reason>`, where `<seconds>` is the remaining
+/// duration of the ban. Permanent bans are reported as `-10`, without any reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentBan<'a> {
+    /// The remaining duration of the ban. [`None`] if the ban is permanent
+    pub duration: Option<Duration>,
+
+    /// The reason given for the ban, if any
+    #[serde(borrow)]
+    pub reason: Option<Cow<'a, str>>,
+}
+
+impl<'a> CommentBan<'a> {
+    /// Attempts to parse a comment ban from a response body. Returns [`None`] if the response does
+    /// not describe a ban
+    pub fn from_response(response: &'a str) -> Option<CommentBan<'a>> {
+        if response == "-10" {
+            return Some(CommentBan {
+                duration: None,
+                reason: None,
+            });
+        }
+
+        let mut parts = response.strip_prefix("temp_")?.splitn(2, '_');
+        let seconds = parts.next()?.parse().ok()?;
+        let reason = parts.next().filter(|reason| !reason.is_empty()).map(Cow::Borrowed);
+
+        Some(CommentBan {
+            duration: Some(Duration::from_secs(seconds)),
+            reason,
+        })
+    }
+
+    /// Returns `true` iff this ban is permanent
+    pub fn is_permanent(&self) -> bool {
+        self.duration.is_none()
+    }
+}
+
+impl Display for CommentBan<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.duration {
+            Some(duration) => write!(f, "banned from commenting for {} seconds", duration.as_secs())?,
+            None => write!(f, "permanently banned from commenting")?,
+        }
+
+        match self.reason {
+            Some(ref reason) => write!(f, " (reason: {})", reason),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,17 +1,19 @@
 use crate::{
+    model::RelativeAge,
     serde::{Base64Decoder, Thunk},
     GJFormat,
 };
-use dash_rs_derive::Dash;
+use dash_rs_derive::{Dash, DashPartialEq};
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
-use variant_partial_eq::VariantPartialEq;
 
-#[derive(Debug, Serialize, Deserialize, Eq, VariantPartialEq, Clone, Dash)]
+#[derive(Debug, Serialize, Deserialize, Eq, DashPartialEq, Clone, Dash)]
 pub struct ProfileComment<'a> {
     /// The actual content of the [`ProfileComment`] made.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `2`, and is urlsafe base64 encoded
     #[serde(borrow)]
-    #[variant_compare = "crate::util::option_variant_eq"]
+    #[dash(compare_with = "crate::util::option_variant_eq")]
     #[dash(index = 2)]
     pub content: Option<Thunk<'a, Base64Decoder>>,
 
@@ -26,8 +28,12 @@ pub struct ProfileComment<'a> {
 
     /// Robtop's completely braindead way of keeping track of when this [`ProfileComment`] was
     /// posted
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `9`, as a string of the form `"6 days"`
+    #[serde(borrow)]
     #[dash(index = 9)]
-    pub time_since_post: Cow<'a, str>,
+    pub time_since_post: Thunk<'a, RelativeAge>,
 }
 
 impl<'de> GJFormat<'de> for ProfileComment<'de> {
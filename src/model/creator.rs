@@ -1,7 +1,6 @@
-use dash_rs_derive::Dash;
+use alloc::borrow::Cow;
+use dash_rs_derive::{Dash, DashPartialEq};
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
-use variant_partial_eq::VariantPartialEq;
 
 use crate::GJFormat;
 
@@ -13,7 +12,7 @@ use crate::GJFormat;
 ///
 /// Creators do not use the map-like representation, meaning the order of fields in the raw data
 /// must correspond to the order of fields in this struct.
-#[derive(Debug, Deserialize, Serialize, VariantPartialEq, Eq, Clone, Dash)]
+#[derive(Debug, Deserialize, Serialize, DashPartialEq, Eq, Clone, Dash)]
 pub struct Creator<'a> {
     /// The [`Creator`]'s unique user ID
     #[dash(index = 1)]
@@ -28,7 +27,7 @@ pub struct Creator<'a> {
     ///
     /// This field is [`None`] if the creator hasn't registered for an account.
     #[dash(index = 3)]
-    #[dash(with = "crate::util::default_to_none")]
+    #[dash(with = "crate::util::serde_adapters::default_to_none")]
     pub account_id: Option<u64>,
 }
 
@@ -38,6 +37,8 @@ impl<'de> GJFormat<'de> for Creator<'de> {
 }
 
 impl<'a> Creator<'a> {
+    /// Converts this [`Creator`] into one that owns all its data, so that it no longer borrows
+    /// from the response it was parsed from
     pub fn into_owned(self) -> Creator<'static> {
         Creator {
             user_id: self.user_id,
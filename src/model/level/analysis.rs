@@ -0,0 +1,188 @@
+//! Module containing functions for extracting information from a level's parsed [`Objects`]
+//!
+//! These allow cross-checking the values a level's metadata claims (such as its coin count)
+//! against what is actually placed in the level.
+
+use alloc::vec::Vec;
+
+use crate::model::level::{object::ids, Objects};
+
+/// A coin placed in a level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoinInfo {
+    /// The x position of the coin
+    pub x: f32,
+
+    /// The y position of the coin
+    pub y: f32,
+
+    /// Whether this is a secret coin, as used in official levels, as opposed to a user coin
+    ///
+    /// ## GD Internals:
+    /// Secret coins have object id `142`, user coins have object id `1329`
+    pub secret: bool,
+}
+
+/// Returns all coins placed in the level, in the order they appear in the object list
+pub fn coins(objects: &Objects) -> Vec<CoinInfo> {
+    objects
+        .objects
+        .iter()
+        .filter_map(|object| match object.id {
+            ids::SECRET_COIN | ids::USER_COIN => Some(CoinInfo {
+                x: object.x,
+                y: object.y,
+                secret: object.id == ids::SECRET_COIN,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns `true` iff the number of user coins placed in the level matches `coin_amount`, which
+/// usually is a [`Level`](super::Level)'s `coin_amount`
+pub fn coin_amount_matches(objects: &Objects, coin_amount: u8) -> bool {
+    coins(objects).iter().filter(|coin| !coin.secret).count() == coin_amount as usize
+}
+
+/// The smallest axis-aligned rectangle containing the positions of a set of objects
+///
+/// Since dash-rs does not know the sizes of objects, this only takes the objects' positions (their
+/// centers) into account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    /// The smallest x position of any object
+    pub min_x: f32,
+
+    /// The smallest y position of any object
+    pub min_y: f32,
+
+    /// The largest x position of any object
+    pub max_x: f32,
+
+    /// The largest y position of any object
+    pub max_y: f32,
+}
+
+impl BoundingBox {
+    /// The width of this [`BoundingBox`]
+    pub fn width(&self) -> f32 {
+        self.max_x - self.min_x
+    }
+
+    /// The height of this [`BoundingBox`]
+    pub fn height(&self) -> f32 {
+        self.max_y - self.min_y
+    }
+
+    /// The center of this [`BoundingBox`], as an `(x, y)` pair
+    pub fn center(&self) -> (f32, f32) {
+        ((self.min_x + self.max_x) / 2.0, (self.min_y + self.max_y) / 2.0)
+    }
+}
+
+/// Computes the [`BoundingBox`] of all objects in the level, or [`None`] if the level contains no
+/// objects
+pub fn bounding_box(objects: &Objects) -> Option<BoundingBox> {
+    let mut iter = objects.objects.iter();
+    let first = iter.next()?;
+    let initial = BoundingBox {
+        min_x: first.x,
+        min_y: first.y,
+        max_x: first.x,
+        max_y: first.y,
+    };
+
+    Some(iter.fold(initial, |bounds, object| BoundingBox {
+        min_x: bounds.min_x.min(object.x),
+        min_y: bounds.min_y.min(object.y),
+        max_x: bounds.max_x.max(object.x),
+        max_y: bounds.max_y.max(object.y),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::model::level::{
+        analysis::{bounding_box, coin_amount_matches, coins, BoundingBox, CoinInfo},
+        metadata::LevelMetadata,
+        object::{ids, LevelObject, ObjectData},
+        Objects,
+    };
+
+    fn object(id: u16, x: f32, y: f32) -> LevelObject {
+        LevelObject {
+            id,
+            x,
+            y,
+            flipped_x: false,
+            flipped_y: false,
+            rotation: 0.0,
+            metadata: ObjectData::Unknown,
+        }
+    }
+
+    #[test]
+    fn find_coins() {
+        let objects = Objects {
+            meta: LevelMetadata::default(),
+            objects: vec![
+                object(1, 15.0, 15.0),
+                object(ids::USER_COIN, 105.0, 45.0),
+                object(ids::SECRET_COIN, 225.0, 75.0),
+                object(ids::USER_COIN, 315.0, 15.0),
+            ],
+        };
+
+        assert_eq!(
+            coins(&objects),
+            vec![
+                CoinInfo {
+                    x: 105.0,
+                    y: 45.0,
+                    secret: false
+                },
+                CoinInfo {
+                    x: 225.0,
+                    y: 75.0,
+                    secret: true
+                },
+                CoinInfo {
+                    x: 315.0,
+                    y: 15.0,
+                    secret: false
+                },
+            ]
+        );
+        assert!(coin_amount_matches(&objects, 2));
+        assert!(!coin_amount_matches(&objects, 3));
+    }
+
+    #[test]
+    fn compute_bounding_box() {
+        let mut objects = Objects {
+            meta: LevelMetadata::default(),
+            objects: vec![object(1, 15.0, 45.0), object(1, -105.0, 15.0), object(1, 225.0, 75.0)],
+        };
+
+        let bounds = bounding_box(&objects).unwrap();
+
+        assert_eq!(
+            bounds,
+            BoundingBox {
+                min_x: -105.0,
+                min_y: 15.0,
+                max_x: 225.0,
+                max_y: 75.0
+            }
+        );
+        assert_eq!(bounds.width(), 330.0);
+        assert_eq!(bounds.center(), (60.0, 45.0));
+
+        objects.objects.clear();
+
+        assert_eq!(bounding_box(&objects), None);
+    }
+}
@@ -1,37 +1,53 @@
 use crate::{
     model::{
-        level::{DemonRating, Level, LevelData, LevelLength, LevelRating},
+        level::{DemonRating, DescriptionDecoder, ExtraString, Level, LevelData, LevelLength, LevelRating, Objects, Password},
         song::MainSong,
+        RelativeAge,
     },
-    serde::InternalProxy,
-    Dash,
+    schema::{self, FieldSchema},
+    serde::{InternalProxy, StrProxy, Thunk, ThunkProcessor, ThunkProxy},
+    util, Dash,
 };
 use serde::{de::Error, Deserialize, Serialize};
-use std::borrow::Borrow;
 
+/// Turns a [`ThunkProxy`] obtained from deserialization back into a [`Thunk`]
+///
+/// [`InternalLevel`] is used for both serialization and deserialization, so its thunk fields can
+/// in principle hold processed values. Deserialization never produces those, but rather than
+/// relying on that, they are reported as an error.
+fn from_thunk_proxy<C: ThunkProcessor, E: Error>(proxy: ThunkProxy<'_, C>) -> Result<Thunk<'_, C>, E> {
+    match proxy {
+        ThunkProxy::Unprocessed(unprocessed) => Ok(Thunk::Unprocessed(unprocessed)),
+        ThunkProxy::Processed(_) => Err(E::custom("deserialization produced an already processed value")),
+    }
+}
+
+// Fields are declared in ascending order of their indices, which is the order in which they are
+// serialized. Indices that are only present in some responses are skipped when not set.
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename = "Level")]
+#[cfg_attr(feature = "strict-format", serde(deny_unknown_fields))]
 struct InternalLevel<'src> {
     #[serde(rename = "1")]
     index_1: u64,
     #[serde(rename = "2")]
-    index_2: &'src str,
+    index_2: StrProxy<'src>,
+    #[serde(borrow)]
     #[serde(rename = "3")]
-    index_3: Option<&'src str>,
+    index_3: Option<ThunkProxy<'src, DescriptionDecoder>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    #[serde(rename = "4")]
+    index_4: Option<ThunkProxy<'src, Objects>>,
     #[serde(rename = "5")]
     index_5: u32,
     #[serde(rename = "6")]
     index_6: u64,
-    #[serde(serialize_with = "crate::util::false_to_empty_string")]
-    #[serde(rename = "25")]
-    index_25: bool,
-    #[serde(serialize_with = "crate::util::true_to_ten")]
+    #[serde(serialize_with = "crate::util::serde_adapters::true_to_ten")]
     #[serde(rename = "8")]
     index_8: bool,
     #[serde(rename = "9")]
     index_9: i32,
-    #[serde(serialize_with = "crate::util::false_to_empty_string")]
-    #[serde(rename = "17")]
-    index_17: bool,
     #[serde(rename = "10")]
     index_10: u32,
     #[serde(rename = "12")]
@@ -42,75 +58,163 @@ struct InternalLevel<'src> {
     index_14: i32,
     #[serde(rename = "15")]
     index_15: i32,
+    #[serde(serialize_with = "crate::util::serde_adapters::false_to_empty_string")]
+    #[serde(rename = "17")]
+    index_17: bool,
     #[serde(rename = "18")]
     index_18: u8,
     #[serde(rename = "19")]
     index_19: i32,
-    #[serde(with = "crate::util::default_to_none")]
+    #[serde(serialize_with = "crate::util::serde_adapters::false_to_empty_string")]
+    #[serde(rename = "25")]
+    index_25: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    #[serde(rename = "27")]
+    index_27: Option<ThunkProxy<'src, Password>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    #[serde(rename = "28")]
+    index_28: Option<ThunkProxy<'src, RelativeAge>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
+    #[serde(rename = "29")]
+    index_29: Option<ThunkProxy<'src, RelativeAge>>,
+    #[serde(with = "crate::util::serde_adapters::default_to_none")]
     #[serde(rename = "30")]
     index_30: Option<u64>,
     #[serde(rename = "31")]
     index_31: bool,
-    #[serde(with = "crate::util::default_to_none")]
+    #[serde(with = "crate::util::serde_adapters::default_to_none")]
     #[serde(rename = "35")]
     index_35: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "36")]
+    index_36: Option<StrProxy<'src>>,
     #[serde(rename = "37")]
     index_37: u8,
     #[serde(rename = "38")]
     index_38: bool,
-    #[serde(with = "crate::util::default_to_none")]
+    #[serde(with = "crate::util::serde_adapters::default_to_none")]
     #[serde(rename = "39")]
     index_39: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "40")]
+    index_40: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "41")]
+    index_41: Option<u32>,
     #[serde(rename = "42")]
-    index_42: bool,
+    index_42: u8,
     #[serde(rename = "43")]
     index_43: u8,
-    #[serde(with = "crate::util::default_to_none")]
+    #[serde(with = "crate::util::serde_adapters::default_to_none")]
     #[serde(rename = "45")]
     index_45: Option<u32>,
     #[serde(rename = "46")]
-    index_46: Option<&'src str>,
+    index_46: Option<u32>,
     #[serde(rename = "47")]
-    index_47: Option<&'src str>,
-
-    // Only present sometimes
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "4")]
-    index_4: Option<&'src str>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "27")]
-    index_27: Option<&'src str>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "28")]
-    index_28: Option<&'src str>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "29")]
-    index_29: Option<&'src str>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "36")]
-    index_36: Option<&'src str>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "40")]
-    index_40: Option<&'src str>,
+    index_47: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "52")]
-    index_52: Option<&'src str>,
+    index_52: Option<StrProxy<'src>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "53")]
-    index_53: Option<&'src str>,
+    index_53: Option<StrProxy<'src>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "57")]
-    index_57: Option<&'src str>,
+    index_57: Option<u32>,
 }
 
+/// Descriptions of the indices provided for levels in responses to level searches, see
+/// [`InternalLevel`]
+const LISTED_LEVEL_FIELDS: &[FieldSchema] = &[
+    FieldSchema::new("1", "level_id", "u64"),
+    FieldSchema::new("2", "name", "Cow<'a, str>"),
+    FieldSchema::new("3", "description", "Option<Thunk<'a, DescriptionDecoder>>")
+        .optional()
+        .processed_by("DescriptionDecoder"),
+    FieldSchema::new("5", "version", "u32"),
+    FieldSchema::new("6", "creator", "u64"),
+    FieldSchema::new("8", "difficulty", "LevelRating"),
+    FieldSchema::new("9", "difficulty", "LevelRating"),
+    FieldSchema::new("10", "downloads", "u32"),
+    FieldSchema::new("12", "main_song", "Option<MainSong>").optional(),
+    FieldSchema::new("13", "gd_version", "GameVersion"),
+    FieldSchema::new("14", "likes", "i32"),
+    FieldSchema::new("15", "length", "LevelLength"),
+    FieldSchema::new("17", "difficulty", "LevelRating"),
+    FieldSchema::new("18", "stars", "u8"),
+    FieldSchema::new("19", "featured", "Featured"),
+    FieldSchema::new("25", "difficulty", "LevelRating"),
+    FieldSchema::new("30", "copy_of", "Option<u64>").optional(),
+    FieldSchema::new("31", "two_player", "bool"),
+    FieldSchema::new("35", "custom_song", "Option<u64>").optional(),
+    FieldSchema::new("37", "coin_amount", "u8"),
+    FieldSchema::new("38", "coins_verified", "bool"),
+    FieldSchema::new("39", "stars_requested", "Option<u8>").optional(),
+    FieldSchema::new("42", "feature_tier", "FeatureTier"),
+    FieldSchema::new("43", "difficulty", "LevelRating"),
+    FieldSchema::new("45", "object_amount", "Option<u32>").optional(),
+    FieldSchema::new("46", "editor_seconds", "Option<u32>").optional(),
+    FieldSchema::new("47", "editor_seconds_copies", "Option<u32>").optional(),
+];
+
+/// Descriptions of the indices provided for levels in responses to level downloads, see
+/// [`InternalLevel`]
+const LEVEL_FIELDS: &[FieldSchema] = &[
+    FieldSchema::new("1", "level_id", "u64"),
+    FieldSchema::new("2", "name", "Cow<'a, str>"),
+    FieldSchema::new("3", "description", "Option<Thunk<'a, DescriptionDecoder>>")
+        .optional()
+        .processed_by("DescriptionDecoder"),
+    FieldSchema::new("4", "level_data.level_data", "Thunk<'a, Objects>").processed_by("Objects"),
+    FieldSchema::new("5", "version", "u32"),
+    FieldSchema::new("6", "creator", "u64"),
+    FieldSchema::new("8", "difficulty", "LevelRating"),
+    FieldSchema::new("9", "difficulty", "LevelRating"),
+    FieldSchema::new("10", "downloads", "u32"),
+    FieldSchema::new("12", "main_song", "Option<MainSong>").optional(),
+    FieldSchema::new("13", "gd_version", "GameVersion"),
+    FieldSchema::new("14", "likes", "i32"),
+    FieldSchema::new("15", "length", "LevelLength"),
+    FieldSchema::new("17", "difficulty", "LevelRating"),
+    FieldSchema::new("18", "stars", "u8"),
+    FieldSchema::new("19", "featured", "Featured"),
+    FieldSchema::new("25", "difficulty", "LevelRating"),
+    FieldSchema::new("27", "level_data.password", "Thunk<'a, Password>").processed_by("Password"),
+    FieldSchema::new("28", "level_data.time_since_upload", "Thunk<'a, RelativeAge>").processed_by("RelativeAge"),
+    FieldSchema::new("29", "level_data.time_since_update", "Thunk<'a, RelativeAge>").processed_by("RelativeAge"),
+    FieldSchema::new("30", "copy_of", "Option<u64>").optional(),
+    FieldSchema::new("31", "two_player", "bool"),
+    FieldSchema::new("35", "custom_song", "Option<u64>").optional(),
+    FieldSchema::new("36", "level_data.extra_string", "ExtraString<'a>"),
+    FieldSchema::new("37", "coin_amount", "u8"),
+    FieldSchema::new("38", "coins_verified", "bool"),
+    FieldSchema::new("39", "stars_requested", "Option<u8>").optional(),
+    FieldSchema::new("40", "level_data.has_low_detail_mode", "bool"),
+    FieldSchema::new("41", "level_data.daily_index", "Option<u32>").optional(),
+    FieldSchema::new("42", "feature_tier", "FeatureTier"),
+    FieldSchema::new("43", "difficulty", "LevelRating"),
+    FieldSchema::new("45", "object_amount", "Option<u32>").optional(),
+    FieldSchema::new("46", "editor_seconds", "Option<u32>").optional(),
+    FieldSchema::new("47", "editor_seconds_copies", "Option<u32>").optional(),
+    FieldSchema::new("52", "level_data.songs_used", "Vec<u64>"),
+    FieldSchema::new("53", "level_data.sfx_used", "Vec<u64>"),
+    FieldSchema::new("57", "level_data.verification_frames", "Option<u32>").optional(),
+];
+
 impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
+    const INDICES: &'static [&'static str] = &schema::indices::<{ LISTED_LEVEL_FIELDS.len() }>(LISTED_LEVEL_FIELDS);
+    const FIELDS: &'static [FieldSchema] = LISTED_LEVEL_FIELDS;
+
     fn dash_deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let internal = InternalLevel::deserialize(deserializer)?;
 
         Ok(Self {
             level_id: InternalProxy::from_deserialize_proxy(internal.index_1),
             name: InternalProxy::from_deserialize_proxy(internal.index_2),
-            description: InternalProxy::from_deserialize_proxy(internal.index_3),
+            description: internal.index_3.map(from_thunk_proxy).transpose()?,
             version: InternalProxy::from_deserialize_proxy(internal.index_5),
             creator: InternalProxy::from_deserialize_proxy(internal.index_6),
             downloads: InternalProxy::from_deserialize_proxy(internal.index_10),
@@ -125,10 +229,10 @@ impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
             coin_amount: InternalProxy::from_deserialize_proxy(internal.index_37),
             coins_verified: InternalProxy::from_deserialize_proxy(internal.index_38),
             stars_requested: InternalProxy::from_deserialize_proxy(internal.index_39),
-            is_epic: InternalProxy::from_deserialize_proxy(internal.index_42),
+            feature_tier: InternalProxy::from_deserialize_proxy(internal.index_42),
             object_amount: InternalProxy::from_deserialize_proxy(internal.index_45),
-            index_46: InternalProxy::from_deserialize_proxy(internal.index_46),
-            index_47: InternalProxy::from_deserialize_proxy(internal.index_47),
+            editor_seconds: internal.index_46,
+            editor_seconds_copies: internal.index_47,
 
             main_song: if internal.index_35.is_some() {
                 None
@@ -149,13 +253,10 @@ impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
     }
 
     fn dash_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        // We are in a manual impl, so we can do the .as_deref() trick to avoid needing two separate structs
-        let index_3 = self.description.to_serialize_proxy();
-
         let internal = InternalLevel {
             index_1: self.level_id.to_serialize_proxy(),
-            index_2: self.name.to_serialize_proxy(),
-            index_3: index_3.as_deref(),
+            index_2: self.name.to_serialize_proxy().into(),
+            index_3: self.description.to_serialize_proxy(),
             index_5: self.version.to_serialize_proxy(),
             index_6: self.creator.to_serialize_proxy(),
             index_10: self.downloads.to_serialize_proxy(),
@@ -170,10 +271,10 @@ impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
             index_37: self.coin_amount.to_serialize_proxy(),
             index_38: self.coins_verified.to_serialize_proxy(),
             index_39: self.stars_requested.to_serialize_proxy(),
-            index_42: self.is_epic.to_serialize_proxy(),
+            index_42: self.feature_tier.to_serialize_proxy(),
             index_45: self.object_amount.to_serialize_proxy(),
-            index_46: self.index_46.to_serialize_proxy(),
-            index_47: self.index_47.to_serialize_proxy(),
+            index_46: self.editor_seconds,
+            index_47: self.editor_seconds_copies,
 
             index_12: self.main_song.map(|song| song.main_song_id).unwrap_or(0),
             index_25: self.difficulty == LevelRating::Auto,
@@ -194,6 +295,7 @@ impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
             index_29: None,
             index_36: None,
             index_40: None,
+            index_41: None,
             index_52: None,
             index_53: None,
             index_57: None,
@@ -203,20 +305,24 @@ impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
 }
 
 impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
+    const INDICES: &'static [&'static str] = &schema::indices::<{ LEVEL_FIELDS.len() }>(LEVEL_FIELDS);
+    const FIELDS: &'static [FieldSchema] = LEVEL_FIELDS;
+
     fn dash_deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let internal = InternalLevel::deserialize(deserializer)?;
 
         let level_data = match (internal.index_4, internal.index_27, internal.index_28, internal.index_29) {
             (Some(idx4), Some(idx27), Some(idx28), Some(idx29)) => LevelData {
-                level_data: InternalProxy::from_deserialize_proxy(idx4),
-                password: InternalProxy::from_deserialize_proxy(idx27),
-                time_since_upload: InternalProxy::from_deserialize_proxy(idx28),
-                time_since_update: InternalProxy::from_deserialize_proxy(idx29),
-                index_36: InternalProxy::from_deserialize_proxy(internal.index_36.unwrap_or_default()),
-                index_40: InternalProxy::from_deserialize_proxy(internal.index_40.unwrap_or_default()),
-                index_52: InternalProxy::from_deserialize_proxy(internal.index_52.unwrap_or_default()),
-                index_53: InternalProxy::from_deserialize_proxy(internal.index_53.unwrap_or_default()),
-                index_57: InternalProxy::from_deserialize_proxy(internal.index_57.unwrap_or_default()),
+                level_data: from_thunk_proxy(idx4)?,
+                password: from_thunk_proxy(idx27)?,
+                time_since_upload: from_thunk_proxy(idx28)?,
+                time_since_update: from_thunk_proxy(idx29)?,
+                extra_string: ExtraString(InternalProxy::from_deserialize_proxy(internal.index_36.unwrap_or_default())),
+                has_low_detail_mode: internal.index_40.unwrap_or_default(),
+                daily_index: internal.index_41,
+                songs_used: util::parse_id_list(&internal.index_52.unwrap_or_default(), ',').map_err(D::Error::custom)?,
+                sfx_used: util::parse_id_list(&internal.index_53.unwrap_or_default(), ',').map_err(D::Error::custom)?,
+                verification_frames: internal.index_57.filter(|&frames| frames != 0),
             },
             _ => return Err(D::Error::custom("Missing indices for level data!")),
         };
@@ -224,7 +330,7 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
         Ok(Self {
             level_id: InternalProxy::from_deserialize_proxy(internal.index_1),
             name: InternalProxy::from_deserialize_proxy(internal.index_2),
-            description: InternalProxy::from_deserialize_proxy(internal.index_3),
+            description: internal.index_3.map(from_thunk_proxy).transpose()?,
             version: InternalProxy::from_deserialize_proxy(internal.index_5),
             creator: InternalProxy::from_deserialize_proxy(internal.index_6),
             downloads: InternalProxy::from_deserialize_proxy(internal.index_10),
@@ -239,10 +345,10 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
             coin_amount: InternalProxy::from_deserialize_proxy(internal.index_37),
             coins_verified: InternalProxy::from_deserialize_proxy(internal.index_38),
             stars_requested: InternalProxy::from_deserialize_proxy(internal.index_39),
-            is_epic: InternalProxy::from_deserialize_proxy(internal.index_42),
+            feature_tier: InternalProxy::from_deserialize_proxy(internal.index_42),
             object_amount: InternalProxy::from_deserialize_proxy(internal.index_45),
-            index_46: InternalProxy::from_deserialize_proxy(internal.index_46),
-            index_47: InternalProxy::from_deserialize_proxy(internal.index_47),
+            editor_seconds: internal.index_46,
+            editor_seconds_copies: internal.index_47,
 
             main_song: if internal.index_35.is_some() {
                 None
@@ -264,15 +370,13 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
     }
 
     fn dash_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        // We are in a manual impl, so we can do the .as_deref() trick to avoid needing two separate structs
-        let index_3 = self.description.to_serialize_proxy();
-        let index_4 = self.level_data.level_data.to_serialize_proxy();
-        let index_27 = self.level_data.password.to_serialize_proxy();
+        let index_52 = util::format_id_list(&self.level_data.songs_used, ',');
+        let index_53 = util::format_id_list(&self.level_data.sfx_used, ',');
 
         let internal = InternalLevel {
             index_1: self.level_id.to_serialize_proxy(),
-            index_2: self.name.to_serialize_proxy(),
-            index_3: index_3.as_deref(),
+            index_2: self.name.to_serialize_proxy().into(),
+            index_3: self.description.to_serialize_proxy(),
             index_5: self.version.to_serialize_proxy(),
             index_6: self.creator.to_serialize_proxy(),
             index_10: self.downloads.to_serialize_proxy(),
@@ -287,10 +391,10 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
             index_37: self.coin_amount.to_serialize_proxy(),
             index_38: self.coins_verified.to_serialize_proxy(),
             index_39: self.stars_requested.to_serialize_proxy(),
-            index_42: self.is_epic.to_serialize_proxy(),
+            index_42: self.feature_tier.to_serialize_proxy(),
             index_45: self.object_amount.to_serialize_proxy(),
-            index_46: self.index_46.to_serialize_proxy(),
-            index_47: self.index_47.to_serialize_proxy(),
+            index_46: self.editor_seconds,
+            index_47: self.editor_seconds_copies,
 
             index_12: self.main_song.map(|song| song.main_song_id).unwrap_or(0),
             index_25: self.difficulty == LevelRating::Auto,
@@ -306,15 +410,16 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
                 _ => 5,
             },
 
-            index_4: Some(index_4.borrow()),
-            index_27: Some(index_27.borrow()),
+            index_4: Some(self.level_data.level_data.to_serialize_proxy()),
+            index_27: Some(self.level_data.password.to_serialize_proxy()),
             index_28: Some(self.level_data.time_since_upload.to_serialize_proxy()),
             index_29: Some(self.level_data.time_since_update.to_serialize_proxy()),
-            index_36: Some(self.level_data.index_36.to_serialize_proxy()),
-            index_40: Some(self.level_data.index_40.to_serialize_proxy()),
-            index_52: Some(self.level_data.index_52.to_serialize_proxy()),
-            index_53: Some(self.level_data.index_53.to_serialize_proxy()),
-            index_57: Some(self.level_data.index_57.to_serialize_proxy()),
+            index_36: Some(self.level_data.extra_string.0.to_serialize_proxy().into()),
+            index_40: Some(self.level_data.has_low_detail_mode),
+            index_41: self.level_data.daily_index,
+            index_52: Some(index_52.as_str().into()),
+            index_53: Some(index_53.as_str().into()),
+            index_57: Some(self.level_data.verification_frames.unwrap_or_default()),
         };
         internal.serialize(serializer)
     }
@@ -364,17 +469,21 @@ impl LevelRating {
         }
     }
 
+    /// Converts this [`LevelRating`] into the value provided at index `9` in server responses
+    ///
+    /// Auto levels are identified by index `25`, and the value at index `9` is ignored for them.
+    /// The servers set it to `50` in that case, so we do the same.
     fn into_response_value(self) -> i32 {
         match self {
             LevelRating::Unknown(value) => value,
             LevelRating::NotAvailable => 0,
+            LevelRating::Auto => 50,
             LevelRating::Easy => 10,
             LevelRating::Normal => 20,
             LevelRating::Hard => 30,
             LevelRating::Harder => 40,
             LevelRating::Insane => 50,
             LevelRating::Demon(demon_rating) => demon_rating.into_response_value(),
-            _ => panic!("got {:?}, please handle before calling this function", self),
         }
     }
 }
@@ -2,7 +2,9 @@ use crate::{model::level::object::speed::Speed, GJFormat};
 use dash_rs_derive::Dash;
 use serde::{Deserialize, Serialize};
 
+// The metadata section contains many settings dash-rs does not model (e.g. color channels)
 #[derive(Debug, PartialEq, Clone, Default, Copy, Serialize, Deserialize, Dash)]
+#[dash(allow_unknown_indices)]
 pub struct LevelMetadata {
     #[dash(index = "kA4")]
     #[dash(default = "one")]
@@ -1,19 +1,30 @@
 //! Module containing structs modelling Geometry Dash levels as they are returned from the boomlings
 //! servers
 
-use itoa::Buffer;
-use std::{
+use alloc::{
     borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
     fmt::{Display, Formatter},
-    io::Read,
+    ops::{BitOr, BitOrAssign, RangeInclusive},
 };
+use dash_rs_derive::DashPartialEq;
+use itoa::Buffer;
+#[cfg(feature = "std")]
+use std::io::Read;
 use thiserror::Error;
-use variant_partial_eq::VariantPartialEq;
 
-use base64::{engine::general_purpose::URL_SAFE, Engine};
+#[cfg(feature = "std")]
+use base64::engine::general_purpose::URL_SAFE;
+#[cfg(feature = "std")]
 use flate2::read::{GzDecoder, GzEncoder, ZlibDecoder};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "arena")]
+use crate::serde::ArenaProcessor;
 use crate::{
     model::{
         creator::Creator,
@@ -22,19 +33,26 @@ use crate::{
             object::{speed::Speed, LevelObject, ObjectData},
         },
         song::{MainSong, NewgroundsSong},
-        GameVersion,
+        GameVersion, RelativeAge,
     },
+    request::{level::LevelUploadRequest, Authentication, GD_22},
     serde::{Base64Decoder, ProcessError, Thunk, ThunkProcessor},
     util, Dash, GJFormat, SerError,
 };
+#[cfg(feature = "arena")]
+use bumpalo::Bump;
+#[cfg(feature = "std")]
 use flate2::Compression;
 
 // use flate2::read::GzDecoder;
 // use std::io::Read;
 
+pub mod analysis;
 mod internal;
 pub mod metadata;
 pub mod object;
+pub mod save;
+mod transform;
 
 /// Enum representing the possible level lengths known to dash-rs
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
@@ -157,6 +175,127 @@ impl LevelRating {
     pub fn is_demon(&self) -> bool {
         matches!(self, LevelRating::Demon(_))
     }
+
+    /// Returns the range of star (or moon) values a level with this [`LevelRating`] can be
+    /// rated with, or [`None`] if the rating does not correspond to a star value
+    pub fn star_range(&self) -> Option<RangeInclusive<u8>> {
+        match self {
+            LevelRating::Auto => Some(1..=1),
+            LevelRating::Easy => Some(2..=2),
+            LevelRating::Normal => Some(3..=3),
+            LevelRating::Hard => Some(4..=5),
+            LevelRating::Harder => Some(6..=7),
+            LevelRating::Insane => Some(8..=9),
+            LevelRating::Demon(_) => Some(10..=10),
+            LevelRating::NotAvailable | LevelRating::Unknown(_) => None,
+        }
+    }
+
+    /// Returns the [`DifficultyFace`] the game displays for a level with this [`LevelRating`] and
+    /// the given featured state and [`FeatureTier`]
+    ///
+    /// Unknown ratings are displayed like [`LevelRating::NotAvailable`], and unknown demon ratings
+    /// like [`DemonRating::Hard`]. An unknown [`FeatureTier`] is ignored, meaning the glow is
+    /// determined by the featured state alone.
+    pub fn difficulty_face(&self, featured: Featured, feature_tier: FeatureTier) -> DifficultyFace {
+        let rating = match *self {
+            LevelRating::Unknown(_) => LevelRating::NotAvailable,
+            LevelRating::Demon(DemonRating::Unknown(_)) => LevelRating::Demon(DemonRating::Hard),
+            rating => rating,
+        };
+        let glow = match (feature_tier, featured) {
+            (FeatureTier::Epic, _) => FaceGlow::Epic,
+            (FeatureTier::Legendary, _) => FaceGlow::Legendary,
+            (FeatureTier::Mythic, _) => FaceGlow::Mythic,
+            (_, Featured::Featured(_)) => FaceGlow::Featured,
+            _ => FaceGlow::None,
+        };
+
+        DifficultyFace { rating, glow }
+    }
+}
+
+/// The glow the game draws around the [`DifficultyFace`] of rated levels
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FaceGlow {
+    /// No glow, used for levels that are neither featured nor have a [`FeatureTier`]
+    None,
+
+    /// The glow of featured levels
+    Featured,
+
+    /// The glow of levels with [`FeatureTier::Epic`]
+    Epic,
+
+    /// The glow of levels with [`FeatureTier::Legendary`]
+    Legendary,
+
+    /// The glow of levels with [`FeatureTier::Mythic`]
+    Mythic,
+}
+
+/// Identifies the difficulty icon the game displays for a level, see
+/// [`LevelRating::difficulty_face`]
+///
+/// Two levels have the same [`DifficultyFace`] iff the game displays the same icon for them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DifficultyFace {
+    /// The rating whose face is displayed. Never [`LevelRating::Unknown`] or
+    /// [`DemonRating::Unknown`]
+    pub rating: LevelRating,
+
+    /// The glow drawn around the face
+    pub glow: FaceGlow,
+}
+
+impl DifficultyFace {
+    /// The name of the sprite the game uses for the face itself
+    ///
+    /// ## GD Internals:
+    /// These sprites are contained in the `GJ_GameSheet03` sprite sheet.
+    pub fn sprite_name(&self) -> &'static str {
+        match self.rating {
+            LevelRating::Auto => "difficulty_auto_btn_001.png",
+            LevelRating::Easy => "difficulty_01_btn_001.png",
+            LevelRating::Normal => "difficulty_02_btn_001.png",
+            LevelRating::Hard => "difficulty_03_btn_001.png",
+            LevelRating::Harder => "difficulty_04_btn_001.png",
+            LevelRating::Insane => "difficulty_05_btn_001.png",
+            LevelRating::Demon(DemonRating::Easy) => "difficulty_07_btn_001.png",
+            LevelRating::Demon(DemonRating::Medium) => "difficulty_08_btn_001.png",
+            LevelRating::Demon(DemonRating::Insane) => "difficulty_09_btn_001.png",
+            LevelRating::Demon(DemonRating::Extreme) => "difficulty_10_btn_001.png",
+            LevelRating::Demon(_) => "difficulty_06_btn_001.png",
+            LevelRating::NotAvailable | LevelRating::Unknown(_) => "difficulty_00_btn_001.png",
+        }
+    }
+
+    /// The name of the sprite the game draws behind the face, or [`None`] if there is no glow
+    pub fn glow_sprite_name(&self) -> Option<&'static str> {
+        match self.glow {
+            FaceGlow::None => None,
+            FaceGlow::Featured => Some("GJ_featuredCoin_001.png"),
+            FaceGlow::Epic => Some("GJ_epicCoin_001.png"),
+            FaceGlow::Legendary => Some("GJ_epicCoin2_001.png"),
+            FaceGlow::Mythic => Some("GJ_epicCoin3_001.png"),
+        }
+    }
+}
+
+impl Display for LevelRating {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            LevelRating::Unknown(value) => write!(f, "Unknown ({})", value),
+            LevelRating::NotAvailable => f.write_str("N/A"),
+            LevelRating::Auto => f.write_str("Auto"),
+            LevelRating::Easy => f.write_str("Easy"),
+            LevelRating::Normal => f.write_str("Normal"),
+            LevelRating::Hard => f.write_str("Hard"),
+            LevelRating::Harder => f.write_str("Harder"),
+            LevelRating::Insane => f.write_str("Insane"),
+            LevelRating::Demon(demon) => demon.fmt(f),
+        }
+    }
 }
 
 /// Enum representing the possible demon difficulties
@@ -202,6 +341,19 @@ pub enum DemonRating {
     Extreme,
 }
 
+impl Display for DemonRating {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            DemonRating::Unknown(value) => write!(f, "Unknown Demon ({})", value),
+            DemonRating::Easy => f.write_str("Easy Demon"),
+            DemonRating::Medium => f.write_str("Medium Demon"),
+            DemonRating::Hard => f.write_str("Hard Demon"),
+            DemonRating::Insane => f.write_str("Insane Demon"),
+            DemonRating::Extreme => f.write_str("Extreme Demon"),
+        }
+    }
+}
+
 /// Enum representing a levels featured state
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(from = "i32", into = "i32")]
@@ -252,9 +404,68 @@ impl From<Featured> for i32 {
 
 crate::into_conversion!(Featured, i32);
 
+/// Enum representing the additional rating tiers a level can receive on top of being featured
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum FeatureTier {
+    /// The level has no additional rating
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `"0"`
+    None,
+
+    /// The level is epic
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `"1"`
+    Epic,
+
+    /// The level is legendary. Added in update 2.2
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `"2"`
+    Legendary,
+
+    /// The level is mythic. Added in update 2.2
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `"3"`
+    Mythic,
+
+    /// Some unknown tier. This variant will be constructed if robtop ever adds more tiers and while
+    /// dash-rs hasn't updated yet
+    Unknown(u8),
+}
+
+impl From<u8> for FeatureTier {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => FeatureTier::None,
+            1 => FeatureTier::Epic,
+            2 => FeatureTier::Legendary,
+            3 => FeatureTier::Mythic,
+            _ => FeatureTier::Unknown(value),
+        }
+    }
+}
+
+impl From<FeatureTier> for u8 {
+    fn from(tier: FeatureTier) -> Self {
+        match tier {
+            FeatureTier::None => 0,
+            FeatureTier::Epic => 1,
+            FeatureTier::Legendary => 2,
+            FeatureTier::Mythic => 3,
+            FeatureTier::Unknown(value) => value,
+        }
+    }
+}
+
+crate::into_conversion!(FeatureTier, u8);
+
 /// Enum representing a level's copyability status
 // FIXME: Find a sane implementation for (de)serialize here
-#[derive(Debug, Clone, Eq, PartialEq, Copy)]
+#[derive(Debug, Clone, Eq, PartialEq, Copy, Hash)]
 pub enum Password {
     /// The level isn't copyable through the official Geometry Dash client
     ///
@@ -284,6 +495,7 @@ pub enum Password {
     /// * XOR the resulting string with the key `"26364"` (note that the XOR operation is performed
     ///   using the ASCII value of the characters in that string)
     /// * base64 encode the result of that
+    ///
     /// In-Game, passwords are sometimes left-padded with zeros. However, this is not a requirement
     /// for the game to be able to correctly process passwords, and merely an implementation detail
     /// that changed at some point after 1.7
@@ -319,7 +531,7 @@ impl<'de> Deserialize<'de> for Password {
 }
 
 /// The XOR key the game uses to encode level passwords
-pub const LEVEL_PASSWORD_XOR_KEY: &str = "26364";
+pub const LEVEL_PASSWORD_XOR_KEY: &str = util::xor_keys::LEVEL_PASSWORD;
 
 /// Encodes the given numerical password by padding it with zeros and applies the XOR-encoding with
 /// [`LEVEL_PASSWORD_XOR_KEY`]
@@ -350,7 +562,7 @@ impl Password {
     ///
     /// ## Arguments
     /// + `raw_password_data`: The raw data returned from the servers. Assumed to be follow the
-    /// encoding described in [`Password`]'s documentation
+    ///   encoding described in [`Password`]'s documentation
     fn from_robtop(raw_password_data: &str) -> Result<Self, ProcessError> {
         Ok(match raw_password_data {
             "0" => Password::NoCopy,
@@ -358,7 +570,7 @@ impl Password {
             _ => {
                 // More than enough for storing the decoded password even if in future the format grows
                 let mut decoded_buffer = [0; 32];
-                let password_len = URL_SAFE.decode_slice(raw_password_data, &mut decoded_buffer)?;
+                let password_len = util::base64_decode_slice(raw_password_data, &mut decoded_buffer)?;
 
                 // This xor pass is applied after we base64 decoded the input, it's how the game tries to protect
                 // data
@@ -389,11 +601,14 @@ impl ThunkProcessor for Password {
         match *processed {
             Password::FreeCopy => Ok(Cow::Borrowed("Aw==")),
             Password::NoCopy => Ok(Cow::Borrowed("0")),
-            Password::PasswordCopy(pw) => {
-                // FIXME: its possible to avoid an allocation here by base64-encoding to a stack-buffer,
-                // and passing that stack buffer directly to a Serializer's serialize_bytes method.
-                Ok(Cow::Owned(URL_SAFE.encode(robtop_encode_level_password(pw))))
-            },
+            Password::PasswordCopy(pw) => Ok(Cow::Owned(util::base64_encode(&robtop_encode_level_password(pw)))),
+        }
+    }
+
+    fn serialize_unprocessed<S: Serializer>(processed: &Self::Output<'_>, serializer: S) -> Result<S::Ok, S::Error> {
+        match *processed {
+            Password::PasswordCopy(pw) => serializer.serialize_bytes(&robtop_encode_level_password(pw)),
+            _ => serializer.serialize_str(&Self::as_unprocessed(processed).map_err(serde::ser::Error::custom)?),
         }
     }
 
@@ -403,7 +618,7 @@ impl ThunkProcessor for Password {
 }
 
 impl Display for Password {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Password::NoCopy => write!(f, "No Copy"),
             Password::FreeCopy => write!(f, "Free Copy"),
@@ -427,23 +642,23 @@ pub type ListedLevel<'a> = Level<'a, (), Option<NewgroundsSong<'a>>, Option<Crea
 ///
 /// ### Unmapped values:
 /// + Index `8`: Index 8 is a boolean value indicating whether the level has a
-/// difficulty rating that isn't N/A. This is equivalent to checking if
-/// [`Level::difficulty`] is unequal to
-/// [`LevelRating::NotAvailable`]
+///   difficulty rating that isn't N/A. This is equivalent to checking if
+///   [`Level::difficulty`] is unequal to
+///   [`LevelRating::NotAvailable`]
 /// + Index `17`: Index 17 is a boolean value indicating whether
-/// the level is a demon level. This is equivalent to checking if
-/// [`Level::difficulty`] is the [`LevelRating::Demon`] variant.
+///   the level is a demon level. This is equivalent to checking if
+///   [`Level::difficulty`] is the [`LevelRating::Demon`] variant.
 /// + Index `25`: Index 25 is a boolean value indicating
-/// whether the level is an auto level. This is equivalent to checking if
-/// [`Level::difficulty`] is equal to
-/// [`LevelRating::Auto`]
+///   whether the level is an auto level. This is equivalent to checking if
+///   [`Level::difficulty`] is equal to
+///   [`LevelRating::Auto`]
 /// + Index `43`: This index is an indicator of demon difficulty as follows:
-///  3 = easy demon,
-///  4 = medium demon,
-///  5 = insane demon,
-///  6 = extreme demon.
-/// In other cases it's hard demon (thanks Ryder!). However, since we extract this information from
-/// index 9, dash-rs ignores this value.
+///   3 = easy demon,
+///   4 = medium demon,
+///   5 = insane demon,
+///   6 = extreme demon.
+///   In other cases it's hard demon (thanks Ryder!). However, since we extract this information
+///   from index 9, dash-rs ignores this value.
 ///
 /// ### Value only provided via `downloadGJLevels`
 /// These values are not provided for by the `getGJLevels` endpoint and are
@@ -454,7 +669,7 @@ pub type ListedLevel<'a> = Level<'a, (), Option<NewgroundsSong<'a>>, Option<Crea
 /// The following indices aren't used by the Geometry Dash servers: `11`, `16`,
 /// `17`, `20`, `21`, `22`, `23`, `24`, `26`, `31`, `32`, `33`, `34`, `40`,
 /// `41`, `44`
-#[derive(Debug, VariantPartialEq, Serialize, Deserialize)]
+#[derive(Debug, DashPartialEq, Serialize, Deserialize)]
 pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// The level's unique level id
     ///
@@ -473,9 +688,10 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// description.
     ///
     /// ## GD Internals:
-    /// This value is provided at index `3` and encoded using urlsafe base 64.
-    #[variant_compare = "crate::util::option_variant_eq"]
-    pub description: Option<Thunk<'a, Base64Decoder>>,
+    /// This value is provided at index `3` and encoded using urlsafe base 64. See [`Description`]
+    /// for the restrictions the game places on descriptions.
+    #[dash(compare_with = "crate::util::option_variant_eq")]
+    pub description: Option<Thunk<'a, DescriptionDecoder>>,
 
     /// The [`Level`]'s version. The version get incremented every time
     /// the level is updated, and the initial version is always version 1.
@@ -560,7 +776,10 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// Value indicating whether this level is played in two-player mode
     ///
     /// ## GD Internals:
-    /// This value is provided at index `31` and actually sanely encoded
+    /// This value is provided at index `31` and actually sanely encoded. Older JSON
+    /// representations of levels stored the raw value in an `index_31` field, which is still
+    /// accepted when deserializing.
+    #[serde(alias = "index_31", default, deserialize_with = "crate::util::serde_adapters::lenient_bool")]
     pub two_player: bool,
 
     /// The id of the newgrounds song this [`Level`] uses, or [`None`]
@@ -592,11 +811,12 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// were requested
     pub stars_requested: Option<u8>,
 
-    /// Value indicating whether this [`Level`] is epic
+    /// The additional rating tier (epic, legendary or mythic) of this [`Level`]
     ///
     /// ## GD Internals:
-    /// This value is provided at index `42`, as an integer
-    pub is_epic: bool,
+    /// This value is provided at index `42`, as an integer. Before update 2.2, the only possible
+    /// values were `0` and `1`.
+    pub feature_tier: FeatureTier,
 
     /// The amount of objects in this [`Level`]. Note that a value of `None` _does not_ mean
     /// that there are no objects in the level, but rather that the server's didn't provide an
@@ -607,19 +827,25 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// in version 2.1 or later. For all older levels this is always `None`
     pub object_amount: Option<u32>,
 
-    /// According to the GDPS source this is always `1`, although that is
-    /// evidently wrong
+    /// The time in seconds the creator spent in the editor working on this [`Level`]. `None` if
+    /// the server did not provide this value.
+    ///
+    /// Note that this value is only tracked since update 2.2. For older levels, it is some
+    /// placeholder value (the GDPS source always returns `1`).
     ///
     /// ## GD Internals:
-    /// This value is provided at index `46` and seems to be an integer
-    pub index_46: Option<Cow<'a, str>>,
+    /// This value is provided at index `46`
+    pub editor_seconds: Option<u32>,
 
-    /// According to the GDPS source, this is always `2`, although that is
-    /// evidently wrong
+    /// The time in seconds the creator spent in the editor working on the levels this [`Level`]
+    /// is a copy of, as described for [`Level::editor_seconds`]
+    ///
+    /// Note that this value is only tracked since update 2.2. For older levels, it is some
+    /// placeholder value (the GDPS source always returns `2`).
     ///
     /// ## GD Internals:
-    /// This value is provided at index `47` and seems to be an integer
-    pub index_47: Option<Cow<'a, str>>,
+    /// This value is provided at index `47`
+    pub editor_seconds_copies: Option<u32>,
 
     /// Additional data about this level that can be retrieved by downloading the level.
     ///
@@ -632,6 +858,279 @@ impl<'a, Data, Song, User> Level<'a, Data, Song, User> {
     pub fn is_platformer(&self) -> bool {
         matches!(self.length, LevelLength::Platformer)
     }
+
+    /// Returns `true` iff this level has received any [`FeatureTier`], e.g. is epic or better
+    #[deprecated(note = "use the `feature_tier` field instead")]
+    pub fn is_epic(&self) -> bool {
+        self.feature_tier != FeatureTier::None
+    }
+
+    /// Returns `true` iff this level is rated, e.g. completing it awards stars (or moons)
+    ///
+    /// Featured levels and levels with a [`FeatureTier`] are always rated, even if
+    /// [`Level::stars`] does not reflect this (which happens for some levels rated before update
+    /// 1.9).
+    pub fn is_rated(&self) -> bool {
+        self.stars > 0 || matches!(self.featured, Featured::Featured(_)) || self.feature_tier != FeatureTier::None
+    }
+
+    /// Returns the amount of stars (or moons) completing this level awards, or is likely to
+    /// award once it gets rated
+    ///
+    /// For levels with a star value, this is simply [`Level::stars`]. Otherwise, the estimate is
+    /// based on the level's [`LevelRating`]: if the creator requested a star value consistent with
+    /// the rating, that value is used, otherwise the lowest value the rating allows (see
+    /// [`LevelRating::star_range`]). If the level has no meaningful rating, the requested star
+    /// value is used, if any.
+    pub fn estimated_star_reward(&self) -> Option<u8> {
+        if self.stars > 0 {
+            return Some(self.stars);
+        }
+
+        match self.difficulty.star_range() {
+            Some(range) => match self.stars_requested {
+                Some(requested) if range.contains(&requested) => Some(requested),
+                _ => Some(*range.start()),
+            },
+            None => self.stars_requested.filter(|requested| (1..=10).contains(requested)),
+        }
+    }
+
+    /// Returns the [`DifficultyFace`] the game displays for this level, see
+    /// [`LevelRating::difficulty_face`]
+    pub fn difficulty_face(&self) -> DifficultyFace {
+        self.difficulty.difficulty_face(self.featured, self.feature_tier)
+    }
+
+    /// Returns `true` iff this level has user coins that have not (yet) been verified
+    ///
+    /// Unverified coins can still be collected, but do not count towards the player's user coin
+    /// total.
+    pub fn has_unverified_coins(&self) -> bool {
+        self.coin_amount > 0 && !self.coins_verified
+    }
+
+    /// Checks whether the [`Level::object_amount`] reported by the servers matches the amount of
+    /// objects actually contained in the given [`Objects`], which should be this level's parsed
+    /// level data
+    ///
+    /// If the servers did not report an object count, there is nothing to verify and `Ok(())` is
+    /// returned.
+    ///
+    /// ## GD Internals:
+    /// The object count is computed by the client when uploading a level. A mismatch between it
+    /// and the actual level data is a known indicator of a level having been uploaded using a
+    /// modified client.
+    pub fn verify_object_count(&self, objects: &Objects) -> Result<(), ObjectCountDiscrepancy> {
+        match self.object_amount {
+            Some(reported) if reported as usize != objects.objects.len() => Err(ObjectCountDiscrepancy {
+                reported,
+                actual: objects.objects.len(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Transforms this level's custom song and creator using the given functions, keeping all
+    /// other fields as they are
+    ///
+    /// This allows re-associating levels with songs and creators obtained from some other source,
+    /// e.g. a local database, without having to copy every field by hand.
+    pub fn map_parts<S, U>(self, song_fn: impl FnOnce(Song) -> S, creator_fn: impl FnOnce(User) -> U) -> Level<'a, Data, S, U> {
+        Level {
+            level_id: self.level_id,
+            name: self.name,
+            description: self.description,
+            version: self.version,
+            creator: creator_fn(self.creator),
+            difficulty: self.difficulty,
+            downloads: self.downloads,
+            main_song: self.main_song,
+            gd_version: self.gd_version,
+            likes: self.likes,
+            length: self.length,
+            stars: self.stars,
+            featured: self.featured,
+            copy_of: self.copy_of,
+            two_player: self.two_player,
+            custom_song: song_fn(self.custom_song),
+            coin_amount: self.coin_amount,
+            coins_verified: self.coins_verified,
+            stars_requested: self.stars_requested,
+            feature_tier: self.feature_tier,
+            object_amount: self.object_amount,
+            editor_seconds: self.editor_seconds,
+            editor_seconds_copies: self.editor_seconds_copies,
+            level_data: self.level_data,
+        }
+    }
+}
+
+/// A set of [`Level`] fields whose values change without the level's creator doing anything, see
+/// [`Level::eq_ignoring`]
+///
+/// Sets can be combined using `|`, e.g. `VolatileFields::DOWNLOADS | VolatileFields::LIKES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VolatileFields(u8);
+
+impl VolatileFields {
+    /// The empty set
+    pub const NONE: VolatileFields = VolatileFields(0);
+    /// [`Level::downloads`]
+    pub const DOWNLOADS: VolatileFields = VolatileFields(1 << 0);
+    /// [`Level::likes`]
+    pub const LIKES: VolatileFields = VolatileFields(1 << 1);
+    /// The weight of [`Featured::Featured`]. Whether a level is featured at all is still compared.
+    pub const FEATURED_WEIGHT: VolatileFields = VolatileFields(1 << 2);
+    /// All volatile fields
+    pub const ALL: VolatileFields = VolatileFields(Self::DOWNLOADS.0 | Self::LIKES.0 | Self::FEATURED_WEIGHT.0);
+
+    /// Returns `true` iff all fields in `other` are also contained in this set
+    pub const fn contains(self, other: VolatileFields) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the set of fields contained in this set, but not in `other`
+    pub const fn difference(self, other: VolatileFields) -> VolatileFields {
+        VolatileFields(self.0 & !other.0)
+    }
+}
+
+impl BitOr for VolatileFields {
+    type Output = VolatileFields;
+
+    fn bitor(self, rhs: VolatileFields) -> VolatileFields {
+        VolatileFields(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for VolatileFields {
+    fn bitor_assign(&mut self, rhs: VolatileFields) {
+        self.0 |= rhs.0
+    }
+}
+
+impl<'a, Data: PartialEq, Song: PartialEq, User: PartialEq> Level<'a, Data, Song, User> {
+    /// Compares two versions of the same level, ignoring all [`VolatileFields`]
+    ///
+    /// Volatile fields, such as the download count, change all the time without the level itself
+    /// changing. This comparison thus only returns `false` if the level was actually updated (or
+    /// e.g. got rated).
+    pub fn eq_stable(&self, other: &Self) -> bool {
+        self.eq_ignoring(other, VolatileFields::ALL)
+    }
+
+    /// Compares two versions of the same level, ignoring the given fields
+    pub fn eq_ignoring(&self, other: &Self, ignored: VolatileFields) -> bool {
+        // Destructured without `..`, so that adding a field to `Level` fails to compile until it is
+        // taken into account here
+        let Level {
+            level_id,
+            name,
+            description,
+            version,
+            creator,
+            difficulty,
+            downloads,
+            main_song,
+            gd_version,
+            likes,
+            length,
+            stars,
+            featured,
+            copy_of,
+            two_player,
+            custom_song,
+            coin_amount,
+            coins_verified,
+            stars_requested,
+            feature_tier,
+            object_amount,
+            editor_seconds,
+            editor_seconds_copies,
+            level_data,
+        } = self;
+
+        let featured_eq = match (*featured, other.featured) {
+            (Featured::Featured(_), Featured::Featured(_)) if ignored.contains(VolatileFields::FEATURED_WEIGHT) => true,
+            (featured, other_featured) => featured == other_featured,
+        };
+
+        featured_eq
+            && (ignored.contains(VolatileFields::DOWNLOADS) || *downloads == other.downloads)
+            && (ignored.contains(VolatileFields::LIKES) || *likes == other.likes)
+            && *level_id == other.level_id
+            && *name == other.name
+            && util::option_variant_eq(description, &other.description)
+            && *version == other.version
+            && *creator == other.creator
+            && *difficulty == other.difficulty
+            && *main_song == other.main_song
+            && *gd_version == other.gd_version
+            && *length == other.length
+            && *stars == other.stars
+            && *copy_of == other.copy_of
+            && *two_player == other.two_player
+            && *custom_song == other.custom_song
+            && *coin_amount == other.coin_amount
+            && *coins_verified == other.coins_verified
+            && *stars_requested == other.stars_requested
+            && *feature_tier == other.feature_tier
+            && *object_amount == other.object_amount
+            && *editor_seconds == other.editor_seconds
+            && *editor_seconds_copies == other.editor_seconds_copies
+            && *level_data == other.level_data
+    }
+}
+
+impl<'a, Data> Level<'a, Data, Option<NewgroundsSong<'a>>, Option<Creator<'a>>> {
+    /// Replaces this level's resolved custom song and creator with their IDs
+    ///
+    /// This undoes the association performed when parsing responses to level searches (see
+    /// [`ListedLevel`]), and allows writing such levels back out in RobTop's format.
+    ///
+    /// The IDs of songs and creators that could not be resolved in the first place are not
+    /// recoverable. An unresolved creator is turned into the user ID `0`, and an unresolved custom
+    /// song into no custom song at all.
+    pub fn into_unresolved(self) -> Level<'a, Data, Option<u64>, u64> {
+        self.map_parts(
+            |song| song.map(|song| song.song_id),
+            |creator| creator.map(|creator| creator.user_id).unwrap_or(0),
+        )
+    }
+
+    /// Returns a compact, human-readable one-line summary of this level, e.g. for use in chat
+    /// messages
+    ///
+    /// The summary contains the level's name, ID, creator, difficulty, star (or moon) value and
+    /// song, for example `Dark Realm (11774780) by Bianox | Insane Demon, 10 stars | At the Speed
+    /// of Light by Dimrain47`. Parts that are unknown or do not apply (such as the star value of
+    /// unrated levels) are left out.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} ({})", self.name, self.level_id);
+
+        if let Some(ref creator) = self.creator {
+            summary.push_str(" by ");
+            summary.push_str(&creator.name);
+        }
+
+        summary.push_str(" | ");
+        summary.push_str(&self.difficulty.to_string());
+
+        if self.stars > 0 {
+            let unit = if self.is_platformer() { "moons" } else { "stars" };
+
+            summary.push_str(&format!(", {} {}", self.stars, unit));
+        }
+
+        if let Some(ref song) = self.custom_song {
+            summary.push_str(&format!(" | {} by {}", song.name, song.artist));
+        } else if let Some(song) = self.main_song {
+            summary.push_str(&format!(" | {} by {}", song.name, song.artist));
+        }
+
+        summary
+    }
 }
 
 impl<'de, Data, Song, User> GJFormat<'de> for Level<'de, Data, Song, User>
@@ -643,7 +1142,7 @@ where
 }
 
 /// Struct encapsulating the additional level data returned when actually downloading a level
-#[derive(Debug, VariantPartialEq, Serialize, Deserialize)]
+#[derive(Debug, DashPartialEq, Serialize, Deserialize)]
 pub struct LevelData<'a> {
     /// The level's actual data.
     ///
@@ -659,35 +1158,353 @@ pub struct LevelData<'a> {
     /// [`Password`] variants
     pub password: Thunk<'a, Password>,
 
-    /// The time passed since the `Level` was uploaded, as a string. Note that these strings are
-    /// very imprecise, as they are only of the form "x months ago", or similar.
+    /// The time passed since the `Level` was uploaded. Note that these values are very imprecise,
+    /// as they are only of the form "x months ago", or similar.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `28`. For format details, see [`RelativeAge`]
+    pub time_since_upload: Thunk<'a, RelativeAge>,
+
+    /// The time passed since the `Level` was last updated. Note that these values are very
+    /// imprecise, as they are only of the form "x months ago", or similar.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `29`. For format details, see [`RelativeAge`]
+    pub time_since_update: Thunk<'a, RelativeAge>,
+
+    /// Additional statistics about the level computed by the editor. See [`ExtraString`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `36`. The GDPS source calls this value `extraString`
+    #[serde(borrow)]
+    pub extra_string: ExtraString<'a>,
+
+    /// Whether this level offers a low detail mode
     ///
-    /// TODO: Parse these into an enum
+    /// ## GD Internals:
+    /// This value is provided at index `40`. Some levels have the empty string here, which is
+    /// treated as `false`
+    pub has_low_detail_mode: bool,
+
+    /// If this level was downloaded as the current daily or weekly level, its number in the
+    /// sequence of all daily (or weekly) levels
     ///
     /// ## GD Internals:
-    /// This value is provided at index `28`
-    pub time_since_upload: Cow<'a, str>,
+    /// This value is provided at index `41`, and only present if the level was requested via
+    /// [`LevelRequest::daily`](crate::request::level::LevelRequest::daily) or
+    /// [`LevelRequest::weekly`](crate::request::level::LevelRequest::weekly). Weekly levels have
+    /// `100000` added to their number.
+    pub daily_index: Option<u32>,
 
-    /// The time passed since the `Level` was last updated, as a string. Note that these strings are
-    /// very imprecise, as they are only of the form "x months ago", or similar.
+    /// The ids of the songs from the 2.2 music library used by this level
     ///
     /// ## GD Internals:
-    /// This value is provided at index `29`
-    pub time_since_update: Cow<'a, str>,
+    /// This value is provided at index `52`, as a comma separated list
+    pub songs_used: Vec<u64>,
 
-    /// According to the GDPS source, this is a value called `extraString`
+    /// The ids of the sound effects from the 2.2 SFX library used by this level
     ///
     /// ## GD Internals:
-    /// This value is provided at index `36`
-    pub index_36: Cow<'a, str>,
+    /// This value is provided at index `53`, as a comma separated list
+    pub sfx_used: Vec<u64>,
 
-    pub index_40: Cow<'a, str>,
+    /// The number of frames the verification of this level took. `None` for levels verified before
+    /// update 2.2, which did not track this.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `57`, with `0` or the empty string meaning the value is
+    /// unknown. Frames are counted at 240 frames per second.
+    ///
+    /// Both representations of an unknown value are mapped to `None`, which is always serialized as
+    /// `0`. Levels providing the empty string thus deliberately do not round-trip byte for byte.
+    pub verification_frames: Option<u32>,
+}
 
-    pub index_52: Cow<'a, str>,
+impl<'a> Level<'a, LevelData<'a>> {
+    /// Prepares a [`LevelUploadRequest`] that uploads this level as a new level of the account
+    /// described by `authentication` and `user_name`, e.g. when migrating levels to a private
+    /// server.
+    ///
+    /// All metadata is copied over, with the following exceptions:
+    /// * The level id is set to `0`, so that the upload creates a new level
+    /// * The version is reset to `1`
+    /// * The reference to the level this level was copied from is dropped, as level ids do not
+    ///   carry over between servers
+    ///
+    /// Level data that has already been processed is re-encoded. Processed descriptions are
+    /// validated before being re-encoded, see [`Description::validate`].
+    pub fn prepare_reupload<'b>(
+        &'b self, authentication: Authentication<'b>, user_name: &'b str,
+    ) -> Result<LevelUploadRequest<'b>, ReuploadError> {
+        let description = match self.description {
+            Some(Thunk::Processed(ref description)) => {
+                description.validate()?;
+
+                DescriptionDecoder::as_unprocessed(description)?
+            },
+            Some(ref description) => description.as_unprocessed()?,
+            None => Cow::Borrowed(""),
+        };
 
-    pub index_53: Cow<'a, str>,
+        Ok(LevelUploadRequest {
+            base: GD_22,
+            authentication,
+            user_name,
+            level_id: 0,
+            name: Cow::Borrowed(&self.name),
+            description,
+            version: 1,
+            length: self.length,
+            main_song: self.main_song.map(|song| song.main_song_id).unwrap_or_default(),
+            custom_song: self.custom_song.unwrap_or_default(),
+            auto: self.difficulty == LevelRating::Auto,
+            password: *self.level_data.password.as_processed()?,
+            original: 0,
+            two_player: self.two_player,
+            objects: self.object_amount.unwrap_or_default(),
+            coins: self.coin_amount,
+            requested_stars: self.stars_requested.unwrap_or_default(),
+            unlisted: false,
+            ldm: self.level_data.has_low_detail_mode,
+            editor_seconds: self.editor_seconds.unwrap_or_default(),
+            editor_seconds_copies: self.editor_seconds_copies.unwrap_or_default(),
+            songs_used: self.level_data.songs_used.clone(),
+            sfx_used: self.level_data.sfx_used.clone(),
+            level_string: self.level_data.level_data.as_unprocessed()?,
+            extra_string: Cow::Borrowed(self.level_data.extra_string.raw()),
+        })
+    }
+}
+
+/// Errors that can occur while preparing the reupload of a level via [`Level::prepare_reupload`]
+#[derive(Debug, Error)]
+pub enum ReuploadError {
+    /// The level's description could not be re-encoded
+    #[error("{0}")]
+    Description(#[from] DescriptionError),
+
+    /// The level's password could not be re-encoded
+    #[error("{0}")]
+    Process(#[from] ProcessError),
+
+    /// The level's data could not be re-encoded
+    #[error("{0}")]
+    LevelData(#[from] LevelProcessError),
+}
+
+impl LevelData<'_> {
+    /// How long the verification of this level took, as calculated from
+    /// [`LevelData::verification_frames`]
+    pub fn verification_time(&self) -> Option<core::time::Duration> {
+        self.verification_frames
+            .map(|frames| core::time::Duration::from_secs_f64(frames as f64 / 240.0))
+    }
+
+    /// The size, in bytes, of this level's compressed level data
+    ///
+    /// This is computed from the length of the base64 encoded data, without decoding it.
+    pub fn compressed_size(&self) -> Result<usize, LevelProcessError> {
+        let encoded = self.level_data.as_unprocessed()?;
+        let padding = encoded.bytes().rev().take_while(|&byte| byte == b'=').count();
+
+        Ok((encoded.len() - padding) * 3 / 4)
+    }
 
-    pub index_57: Cow<'a, str>,
+    /// The [`CompressionScheme`] used for this level's level data
+    ///
+    /// Only the first few bytes of the level data are decoded to determine this.
+    pub fn compression_scheme(&self) -> Result<CompressionScheme, LevelProcessError> {
+        let encoded = self.level_data.as_unprocessed()?;
+        let prefix = encoded.get(..4).unwrap_or(&encoded);
+
+        Ok(CompressionScheme::detect(&util::base64_decode(prefix)?))
+    }
+
+    /// The size, in bytes, of this level's level data after decompression
+    ///
+    /// For gzip compressed level data, this is read from the gzip trailer without decompressing
+    /// anything. Zlib compressed level data has to be decompressed to determine its size, although
+    /// neither is the decompressed data retained, nor are any objects parsed.
+    pub fn decompressed_size(&self) -> Result<usize, LevelProcessError> {
+        let encoded = self.level_data.as_unprocessed()?;
+
+        if self.compression_scheme()? == CompressionScheme::Gzip && encoded.len() % 4 == 0 && encoded.len() >= 8 {
+            // The last four bytes of a gzip stream store the size of the uncompressed data (modulo
+            // 2^32) in little endian. The last eight base64 characters decode to at least four bytes.
+            let trailer = util::base64_decode(&encoded[encoded.len() - 8..])?;
+
+            if let [.., a, b, c, d] = trailer[..] {
+                return Ok(u32::from_le_bytes([a, b, c, d]) as usize);
+            }
+        }
+
+        decompressed_size(&util::base64_decode(&*encoded)?)
+    }
+}
+
+/// The `extraString` of a level, an underscore separated list of integers computed by the editor
+/// when uploading a level.
+///
+/// The meaning of the individual values is not fully known, which is why the raw string is
+/// retained as is. [`ExtraString::values`] splits it into its components.
+///
+/// ## GD Internals:
+/// An example value is `29_566_73_98_29_29_54_424_29_29_237_132_177_29_29_29`. The number of
+/// components differs between levels.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtraString<'a>(#[serde(borrow)] pub Cow<'a, str>);
+
+impl<'a> ExtraString<'a> {
+    /// The unprocessed `extraString`, exactly as provided by the servers
+    pub fn raw(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns an iterator over the components of this `extraString`. Components that are not
+    /// integers are reported as errors. An empty `extraString` has no components
+    pub fn values(&self) -> impl Iterator<Item = Result<u32, core::num::ParseIntError>> + '_ {
+        let raw: &str = &self.0;
+
+        raw.split('_').filter(move |_| !raw.is_empty()).map(str::parse)
+    }
+
+    /// Parses this `extraString` into its [`ObjectCounts`]
+    pub fn object_counts(&self) -> Result<ObjectCounts, ProcessError> {
+        ObjectCounts::from_unprocessed(Cow::Borrowed(self.raw()))
+    }
+
+    /// Converts this `extraString` into one that owns its data
+    pub fn into_owned(self) -> ExtraString<'static> {
+        ExtraString(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+/// The structured contents of an [`ExtraString`]
+///
+/// ## GD Internals:
+/// The editor writes one count per object category when saving a level, which is needed to
+/// faithfully reconstruct the editor state of a downloaded level. Unlike what the GDPS source
+/// suggests, the segments are plain decimal integers, not base64 encoded data.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectCounts {
+    /// The individual counts, in the order in which they appear in the `extraString`
+    pub counts: Vec<u32>,
+}
+
+impl ThunkProcessor for ObjectCounts {
+    type Error = ProcessError;
+    type Output<'a> = ObjectCounts;
+
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
+        Ok(ObjectCounts {
+            counts: util::parse_id_list(&unprocessed, '_')?,
+        })
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        Ok(Cow::Owned(util::format_id_list(&processed.counts, '_')))
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+/// The maximum number of characters the game allows in a level description
+pub const MAX_DESCRIPTION_LENGTH: usize = 180;
+
+/// A level description
+///
+/// Descriptions are processed by the [`DescriptionDecoder`], which takes care of base64 en- and
+/// decoding them.
+///
+/// ## GD Internals:
+/// Descriptions are urlsafe base64 encoded. The game only allows entering printable ASCII
+/// characters (which excludes line breaks and emoji), and at most [`MAX_DESCRIPTION_LENGTH`] of
+/// them. The servers contain descriptions from times when different restrictions applied, so these
+/// restrictions are only checked when uploading descriptions (see [`Description::validate`]), and
+/// every description that can be decoded can also be re-encoded.
+#[derive(Debug, Clone, Default, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Description<'a>(pub Cow<'a, str>);
+
+// Not derived, as the `PartialEq` implementation for `Thunk` requires comparisons across lifetimes
+impl<'b> PartialEq<Description<'b>> for Description<'_> {
+    fn eq(&self, other: &Description<'b>) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<'a> Description<'a> {
+    /// Constructs a new description, checking that the game would accept it
+    pub fn new(description: impl Into<Cow<'a, str>>) -> Result<Self, DescriptionError> {
+        let description = Description(description.into());
+
+        description.validate()?;
+
+        Ok(description)
+    }
+
+    /// Checks that the game would accept this description, see the type-level documentation
+    pub fn validate(&self) -> Result<(), DescriptionError> {
+        if let Some(character) = self.0.chars().find(|c| !matches!(c, ' '..='~')) {
+            return Err(DescriptionError::InvalidCharacter(character));
+        }
+
+        // Since only ASCII is allowed, the length in bytes is the number of characters
+        if self.0.len() > MAX_DESCRIPTION_LENGTH {
+            return Err(DescriptionError::TooLong { length: self.0.len() });
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Description<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Errors that can occur while processing a [`Description`]
+#[derive(Debug, Error)]
+pub enum DescriptionError {
+    /// The description could not be decoded
+    #[error("{0}")]
+    Process(#[from] ProcessError),
+
+    /// The description contains a character that cannot be entered in-game
+    #[error("description contains invalid character {0:?}")]
+    InvalidCharacter(char),
+
+    /// The description is longer than [`MAX_DESCRIPTION_LENGTH`]
+    #[error("description has {length} characters, but at most {} are allowed", MAX_DESCRIPTION_LENGTH)]
+    TooLong { length: usize },
+}
+
+/// [`ThunkProcessor`] for level descriptions, see [`Description`]
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct DescriptionDecoder;
+
+impl ThunkProcessor for DescriptionDecoder {
+    type Error = DescriptionError;
+    type Output<'a> = Description<'a>;
+
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
+        Ok(Description(Base64Decoder::from_unprocessed(unprocessed)?))
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        Ok(Base64Decoder::as_unprocessed(&processed.0)?)
+    }
+
+    fn serialize_unprocessed<S: Serializer>(processed: &Self::Output<'_>, serializer: S) -> Result<S::Ok, S::Error> {
+        Base64Decoder::serialize_unprocessed(&processed.0, serializer)
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -696,6 +1513,40 @@ pub struct Objects {
     pub objects: Vec<LevelObject>,
 }
 
+/// A mismatch between the object count a [`Level`] reports and the amount of objects in its level
+/// data, see [`Level::verify_object_count`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+#[error("Level reports {reported} objects, but its level data contains {actual}")]
+pub struct ObjectCountDiscrepancy {
+    /// The object count reported by the servers, see [`Level::object_amount`]
+    pub reported: u32,
+
+    /// The amount of objects actually contained in the level data
+    pub actual: usize,
+}
+
+impl ObjectCountDiscrepancy {
+    /// The amount of objects the level data contains in excess of the reported count. Negative if
+    /// the level data contains fewer objects than reported.
+    pub fn difference(&self) -> i64 {
+        self.actual as i64 - self.reported as i64
+    }
+}
+
+/// An object from some level string that could not be parsed, see
+/// [`Objects::from_unprocessed_lossy`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedObject {
+    /// The position of the object in the level string, not counting the metadata section
+    pub index: usize,
+
+    /// The raw data of the object
+    pub raw: String,
+
+    /// Description of the error that occurred while parsing the object
+    pub error: String,
+}
+
 #[derive(Debug, Error)]
 pub enum LevelProcessError {
     #[error("{0}")]
@@ -712,9 +1563,15 @@ pub enum LevelProcessError {
     UnknownCompression,
 
     /// Error during (de)compression
+    #[cfg(feature = "std")]
     #[error("{0}")]
     Compression(#[from] std::io::Error),
 
+    /// Level data cannot be (de)compressed because the `std` feature is disabled
+    #[cfg(not(feature = "std"))]
+    #[error("(De)compression of level data requires the `std` feature")]
+    CompressionUnavailable,
+
     /// The given level string did not contain a metadata section
     #[error("Missing metadata section in level string")]
     MissingMetadata,
@@ -725,88 +1582,409 @@ impl ThunkProcessor for Objects {
     type Output<'a> = Objects;
 
     fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self, LevelProcessError> {
-        // Doing the entire base64 in one go is actually faster than using base64::read::DecoderReader and
-        // having the two readers go back and forth.
-        let decoded = URL_SAFE.decode(&*unprocessed)?;
+        Objects::parse(&unprocessed, None)
+    }
 
-        // Here's the deal: Robtop decompresses all levels by calling the zlib function 'inflateInit2_' with
-        // the second argument set to 47. This basically tells zlib "this data might be compressed using
-        // zlib or gzip format, with window size at most 15, but you gotta figure it out yourself".
-        // However, flate2 doesnt expose this option, so we have to manually determine whether we
-        // have gzip or zlib compression.
+    fn as_unprocessed(processed: &Objects) -> Result<Cow<'_, str>, LevelProcessError> {
+        Ok(Cow::Owned(util::base64_encode(&processed.to_compressed_bytes()?)))
+    }
 
-        let mut decompressed = String::new();
+    fn serialize_unprocessed<S: Serializer>(processed: &Objects, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&processed.to_compressed_bytes().map_err(serde::ser::Error::custom)?)
+    }
 
-        match &decoded[..2] {
-            // gz magic bytes
-            [0x1f, 0x8b] => {
-                let mut decoder = GzDecoder::new(&decoded[..]);
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
 
-                decoder.read_to_string(&mut decompressed)?;
-            },
-            // There's no such thing as "zlib magic bytes", but the first byte stores some information about how the data is compressed.
-            // '0x78' is the first byte for the compression method robtop used (note: this is only used for very old levels, as he switched
-            // to gz for newer levels)
-            [0x78, _] => {
-                let mut decoder = ZlibDecoder::new(&decoded[..]);
+#[cfg(feature = "arena")]
+impl ArenaProcessor for DescriptionDecoder {
+    fn from_unprocessed_in<'a>(unprocessed: &'a str, arena: &'a Bump) -> Result<Description<'a>, DescriptionError> {
+        Ok(Description(Base64Decoder::from_unprocessed_in(unprocessed, arena)?))
+    }
+}
 
-                decoder.read_to_string(&mut decompressed)?;
-            },
-            _ => return Err(LevelProcessError::UnknownCompression),
+/// Only the decoded and decompressed level string is allocated in the arena, the parsed objects
+/// are not
+#[cfg(feature = "arena")]
+impl ArenaProcessor for Objects {
+    fn from_unprocessed_in<'a>(unprocessed: &'a str, arena: &'a Bump) -> Result<Objects, LevelProcessError> {
+        #[cfg(feature = "std")]
+        if unprocessed.len() > STREAMING_THRESHOLD {
+            return Objects::read(unprocessed.as_bytes(), None);
         }
 
-        let mut iter = decompressed.split_terminator(';');
+        let decoded = crate::serde::decode_base64_in(unprocessed, arena)?;
+        let decompressed = decompress_in(decoded, arena)?;
 
-        let metadata_string = match iter.next() {
-            Some(meta) => meta,
-            None => return Err(LevelProcessError::MissingMetadata),
+        Objects::parse_decompressed(decompressed, None)
+    }
+}
+
+/// Length of (base64 encoded) level data above which [`Objects`] are processed via
+/// [`Objects::from_reader`], to avoid holding multiple copies of huge levels in memory at once
+#[cfg(feature = "std")]
+pub const STREAMING_THRESHOLD: usize = 1024 * 1024;
+
+/// Size of the chunks in which [`Objects::from_reader`] reads decompressed level data
+#[cfg(feature = "std")]
+const STREAMING_CHUNK_SIZE: usize = 64 * 1024;
+
+#[cfg(feature = "std")]
+impl Objects {
+    /// Processes base64 encoded and compressed level data, read from `reader`, into [`Objects`]
+    ///
+    /// Unlike [`ThunkProcessor::from_unprocessed`], this never holds the entire decoded or
+    /// decompressed level string in memory. Instead, the data is base64 decoded, decompressed and
+    /// split into objects chunk by chunk, so peak memory usage is roughly that of the parsed objects.
+    /// This is slower for small levels, but meant for processing huge (10MB+) level strings on
+    /// memory-constrained machines.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Objects, LevelProcessError> {
+        Objects::read(reader, None)
+    }
+
+    fn read<R: Read>(reader: R, malformed: Option<&mut Vec<MalformedObject>>) -> Result<Objects, LevelProcessError> {
+        use flate2::bufread;
+        use std::io::BufRead;
+
+        let mut decoded = std::io::BufReader::new(base64::read::DecoderReader::new(reader, &URL_SAFE));
+
+        // See `decompress` for why we need to detect the compression scheme ourselves
+        match decoded.fill_buf()? {
+            [0x1f, 0x8b, ..] => parse_objects_streaming(bufread::GzDecoder::new(decoded), malformed),
+            [0x78, ..] => parse_objects_streaming(bufread::ZlibDecoder::new(decoded), malformed),
+            _ => Err(LevelProcessError::UnknownCompression),
+        }
+    }
+}
+
+/// Parses a decompressed level string read from `reader` chunk by chunk, only ever buffering the
+/// object currently being read
+#[cfg(feature = "std")]
+fn parse_objects_streaming<R: Read>(mut reader: R, mut malformed: Option<&mut Vec<MalformedObject>>) -> Result<Objects, LevelProcessError> {
+    let mut buffer = Vec::with_capacity(STREAMING_CHUNK_SIZE);
+    let mut meta = None;
+    let mut objects = Vec::new();
+
+    let mut parse_section = |section: &[u8]| -> Result<(), LevelProcessError> {
+        let section = core::str::from_utf8(section).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?;
+
+        match meta {
+            None => meta = Some(LevelMetadata::from_gj_str(section).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?),
+            Some(_) => push_object(&mut objects, section, malformed.as_deref_mut())?,
+        }
+
+        Ok(())
+    };
+
+    loop {
+        let filled = buffer.len();
+        buffer.resize(filled + STREAMING_CHUNK_SIZE, 0);
+
+        let read = loop {
+            match reader.read(&mut buffer[filled..]) {
+                Ok(read) => break read,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
         };
+        buffer.truncate(filled + read);
 
-        let meta = LevelMetadata::from_gj_str(metadata_string).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?;
+        if read == 0 {
+            break;
+        }
 
-        iter.map(LevelObject::from_gj_str)
-            .collect::<Result<_, _>>()
-            .map(|objects| Objects { meta, objects })
-            .map_err(|err| LevelProcessError::Deserialize(err.to_string()))
+        let mut consumed = 0;
+
+        while let Some(end) = crate::split::find_byte(b';', &buffer[consumed..]) {
+            parse_section(&buffer[consumed..consumed + end])?;
+            consumed += end + 1;
+        }
+
+        buffer.drain(..consumed);
     }
 
-    fn as_unprocessed(processed: &Objects) -> Result<Cow<str>, LevelProcessError> {
-        let mut bytes = Vec::new();
+    // Same as `split_terminator`, the final section does not need to be terminated by a ';'
+    if !buffer.is_empty() {
+        parse_section(&buffer)?;
+    }
 
-        processed.meta.write_gj(&mut bytes)?;
+    match meta {
+        Some(meta) => Ok(Objects { meta, objects }),
+        None => Err(LevelProcessError::MissingMetadata),
+    }
+}
 
-        bytes.push(b';');
+/// Parses `raw` and appends the resulting object to `objects`
+///
+/// If parsing fails and `malformed` is given, the object is recorded there instead of failing.
+fn push_object(objects: &mut Vec<LevelObject>, raw: &str, malformed: Option<&mut Vec<MalformedObject>>) -> Result<(), LevelProcessError> {
+    match (LevelObject::from_gj_str(raw), malformed) {
+        (Ok(object), _) => objects.push(object),
+        (Err(err), Some(malformed)) => malformed.push(MalformedObject {
+            index: objects.len() + malformed.len(),
+            raw: raw.to_string(),
+            error: err.to_string(),
+        }),
+        (Err(err), None) => return Err(LevelProcessError::Deserialize(err.to_string())),
+    }
 
-        for object in &processed.objects {
-            object.write_gj(&mut bytes)?;
-            bytes.push(b';');
+    Ok(())
+}
+
+/// The compression schemes level data can be compressed with
+///
+/// ## GD Internals:
+/// The game detects the compression scheme used for level data on its own, meaning both are
+/// accepted everywhere. Only very old levels use zlib compression, newer levels are always gzip
+/// compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressionScheme {
+    /// Gzip compression, recognized by the magic bytes `1f 8b`
+    Gzip,
+
+    /// Zlib compression, recognized by a first byte of `78`
+    Zlib,
+
+    /// Neither gzip nor zlib compression. Level data using an unknown compression scheme cannot be
+    /// processed.
+    Unknown,
+}
+
+impl CompressionScheme {
+    /// Determines the compression scheme of the given (base64 decoded) level data from its first
+    /// bytes
+    pub fn detect(decoded: &[u8]) -> CompressionScheme {
+        match decoded {
+            // gz magic bytes
+            [0x1f, 0x8b, ..] => CompressionScheme::Gzip,
+            // There's no such thing as "zlib magic bytes", but the first byte stores some information about how the data is compressed.
+            // '0x78' is the first byte for the compression method robtop used (note: this is only used for very old levels, as he switched
+            // to gz for newer levels)
+            [0x78, ..] => CompressionScheme::Zlib,
+            _ => CompressionScheme::Unknown,
         }
+    }
+}
 
-        // FIXME(game specific): Should we remember the compression scheme (zlib or gz) from above, or just
-        // always re-compress using gz? Since the game dyncamially detects the compression method, we're
-        // compatible either way.
+#[cfg(feature = "std")]
+pub(crate) fn decompress(decoded: &[u8]) -> Result<String, LevelProcessError> {
+    // Here's the deal: Robtop decompresses all levels by calling the zlib function 'inflateInit2_' with
+    // the second argument set to 47. This basically tells zlib "this data might be compressed using
+    // zlib or gzip format, with window size at most 15, but you gotta figure it out yourself".
+    // However, flate2 doesnt expose this option, so we have to manually determine whether we
+    // have gzip or zlib compression.
 
-        let mut encoder = GzEncoder::new(&bytes[..], Compression::new(9)); // TODO: idk what these values mean
-        let mut compressed = Vec::new();
+    let mut decompressed = String::new();
 
-        encoder.read_to_end(&mut compressed)?;
+    match CompressionScheme::detect(decoded) {
+        CompressionScheme::Gzip => {
+            let mut decoder = GzDecoder::new(decoded);
 
-        Ok(Cow::Owned(URL_SAFE.encode(compressed)))
+            decoder.read_to_string(&mut decompressed)?;
+        },
+        CompressionScheme::Zlib => {
+            let mut decoder = ZlibDecoder::new(decoded);
+
+            decoder.read_to_string(&mut decompressed)?;
+        },
+        CompressionScheme::Unknown => return Err(LevelProcessError::UnknownCompression),
     }
 
-    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
-        output
+    Ok(decompressed)
+}
+
+/// Decompresses level data like [`decompress`], but into a buffer allocated in `arena`
+#[cfg(all(feature = "std", feature = "arena"))]
+fn decompress_in<'a>(decoded: &[u8], arena: &'a Bump) -> Result<&'a str, LevelProcessError> {
+    fn read_into<R: Read>(mut reader: R, buffer: &mut bumpalo::collections::Vec<u8>) -> std::io::Result<()> {
+        loop {
+            let filled = buffer.len();
+            buffer.resize(filled + STREAMING_CHUNK_SIZE, 0);
+
+            match reader.read(&mut buffer[filled..]) {
+                Ok(0) => {
+                    buffer.truncate(filled);
+
+                    return Ok(());
+                },
+                Ok(read) => buffer.truncate(filled + read),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => buffer.truncate(filled),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    let mut decompressed = bumpalo::collections::Vec::new_in(arena);
+
+    match CompressionScheme::detect(decoded) {
+        CompressionScheme::Gzip => read_into(GzDecoder::new(decoded), &mut decompressed)?,
+        CompressionScheme::Zlib => read_into(ZlibDecoder::new(decoded), &mut decompressed)?,
+        CompressionScheme::Unknown => return Err(LevelProcessError::UnknownCompression),
     }
+
+    core::str::from_utf8(decompressed.into_bump_slice()).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err).into())
+}
+
+#[cfg(all(not(feature = "std"), feature = "arena"))]
+fn decompress_in<'a>(_decoded: &[u8], _arena: &'a Bump) -> Result<&'a str, LevelProcessError> {
+    Err(LevelProcessError::CompressionUnavailable)
+}
+
+/// Determines the size of the given level data after decompression, without retaining the
+/// decompressed data
+#[cfg(feature = "std")]
+fn decompressed_size(decoded: &[u8]) -> Result<usize, LevelProcessError> {
+    let size = match CompressionScheme::detect(decoded) {
+        CompressionScheme::Gzip => std::io::copy(&mut GzDecoder::new(decoded), &mut std::io::sink())?,
+        CompressionScheme::Zlib => std::io::copy(&mut ZlibDecoder::new(decoded), &mut std::io::sink())?,
+        CompressionScheme::Unknown => return Err(LevelProcessError::UnknownCompression),
+    };
+
+    Ok(size as usize)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn compress(bytes: &[u8]) -> Result<Vec<u8>, LevelProcessError> {
+    // FIXME(game specific): Should we remember the compression scheme (zlib or gz) from above, or just
+    // always re-compress using gz? Since the game dyncamially detects the compression method, we're
+    // compatible either way.
+
+    let mut encoder = GzEncoder::new(bytes, Compression::new(9)); // TODO: idk what these values mean
+    let mut compressed = Vec::new();
+
+    encoder.read_to_end(&mut compressed)?;
+
+    Ok(compressed)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn decompress(_decoded: &[u8]) -> Result<String, LevelProcessError> {
+    Err(LevelProcessError::CompressionUnavailable)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn compress(_bytes: &[u8]) -> Result<Vec<u8>, LevelProcessError> {
+    Err(LevelProcessError::CompressionUnavailable)
+}
+
+#[cfg(not(feature = "std"))]
+fn decompressed_size(_decoded: &[u8]) -> Result<usize, LevelProcessError> {
+    Err(LevelProcessError::CompressionUnavailable)
 }
 
 impl Objects {
+    /// Processes level data like [`ThunkProcessor::from_unprocessed`], but skips objects that fail
+    /// to parse instead of aborting
+    ///
+    /// The skipped objects are returned alongside the successfully parsed ones. Failure to decode,
+    /// decompress or parse the metadata section still causes an error.
+    pub fn from_unprocessed_lossy(unprocessed: &str) -> Result<(Objects, Vec<MalformedObject>), LevelProcessError> {
+        let mut malformed = Vec::new();
+        let objects = Objects::parse(unprocessed, Some(&mut malformed))?;
+
+        Ok((objects, malformed))
+    }
+
+    /// Parses a "custom object" string, i.e. a group of objects copied in the editor
+    ///
+    /// Unlike level strings, these consist only of objects, without a metadata section. Both the
+    /// plain object string and its compressed, urlsafe base64 encoded form are accepted.
+    ///
+    /// ## GD Internals:
+    /// Custom objects are stored in the `customObjectDict` of `CCGameManager.dat` in their encoded
+    /// form. When shared online, they usually appear in their plain form (e.g. `1,1,2,15,3,15;`).
+    pub fn parse_custom_objects(data: &str) -> Result<Vec<LevelObject>, LevelProcessError> {
+        // Non-empty object strings always contain commas, which are not part of the base64 alphabet
+        let decompressed = if data.is_empty() || data.contains(',') {
+            Cow::Borrowed(data)
+        } else {
+            Cow::Owned(decompress(&util::base64_decode(data)?)?)
+        };
+
+        let mut objects = Vec::new();
+
+        for raw in decompressed.split_terminator(';').filter(|raw| !raw.is_empty()) {
+            push_object(&mut objects, raw, None)?;
+        }
+
+        Ok(objects)
+    }
+
+    fn parse(unprocessed: &str, malformed: Option<&mut Vec<MalformedObject>>) -> Result<Objects, LevelProcessError> {
+        #[cfg(feature = "std")]
+        if unprocessed.len() > STREAMING_THRESHOLD {
+            return Objects::read(unprocessed.as_bytes(), malformed);
+        }
+
+        // Doing the entire base64 in one go is actually faster than using base64::read::DecoderReader and
+        // having the two readers go back and forth.
+        let decoded = util::base64_decode(unprocessed)?;
+
+        let decompressed = decompress(&decoded)?;
+
+        Objects::parse_decompressed(&decompressed, malformed)
+    }
+
+    fn parse_decompressed(decompressed: &str, mut malformed: Option<&mut Vec<MalformedObject>>) -> Result<Objects, LevelProcessError> {
+        let mut iter = decompressed.split_terminator(';');
+
+        let metadata_string = match iter.next() {
+            Some(meta) => meta,
+            None => return Err(LevelProcessError::MissingMetadata),
+        };
+
+        let meta = LevelMetadata::from_gj_str(metadata_string).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?;
+        let mut objects = Vec::new();
+
+        for raw in iter {
+            push_object(&mut objects, raw, malformed.as_deref_mut())?;
+        }
+
+        Ok(Objects { meta, objects })
+    }
+
+    /// Estimates the time it takes to play through this level, taking into account all activated
+    /// speed and reverse portals
     pub fn length_in_seconds(&self) -> f32 {
+        self.seconds_from(0.0, self.meta.starting_speed)
+    }
+
+    /// Returns all start positions placed in this level, in the order they appear in the object
+    /// list
+    ///
+    /// Each object returned has [`ObjectData::StartPosition`] as its metadata.
+    pub fn start_positions(&self) -> impl Iterator<Item = &LevelObject> + '_ {
+        self.objects
+            .iter()
+            .filter(|object| matches!(object.metadata, ObjectData::StartPosition(_)))
+    }
+
+    /// Estimates the time it takes to play through this level when starting from the given start
+    /// position, like [`Objects::length_in_seconds`] does for the level's actual start
+    ///
+    /// Only portals to the right of the start position are taken into account. If the given object
+    /// is not a start position, the level's starting speed is used.
+    pub fn length_in_seconds_from(&self, start_position: &LevelObject) -> f32 {
+        let speed = match start_position.metadata {
+            ObjectData::StartPosition(settings) => settings.speed,
+            _ => self.meta.starting_speed,
+        };
+
+        self.seconds_from(start_position.x, speed)
+    }
+
+    fn seconds_from(&self, start_x: f32, start_speed: Speed) -> f32 {
         let mut portals = Vec::new();
-        let mut furthest_x = 0.0;
+        let mut furthest_x = start_x;
 
         for object in &self.objects {
-            if let ObjectData::SpeedPortal { checked: true, speed } = object.metadata {
-                portals.push((object.x, speed))
+            match object.metadata {
+                ObjectData::SpeedPortal { checked: true, .. } | ObjectData::ReversePortal { checked: true } if object.x >= start_x => {
+                    portals.push((object.x - start_x, object.metadata))
+                },
+                _ => (),
             }
 
             furthest_x = f32::max(furthest_x, object.x);
@@ -814,35 +1992,64 @@ impl Objects {
 
         portals.sort_by(|(x1, _), (x2, _)| x1.partial_cmp(x2).unwrap());
 
-        get_seconds_from_x_pos(furthest_x, self.meta.starting_speed, &portals)
+        get_seconds_from_x_pos(furthest_x - start_x, start_speed, &portals)
     }
-}
 
-fn get_seconds_from_x_pos(pos: f32, start_speed: Speed, portals: &[(f32, Speed)]) -> f32 {
-    let mut speed: f32 = start_speed.into();
+    /// Serializes these objects into the level string format and compresses the result, yielding
+    /// the level data as it is stored by the game, before base64 encoding
+    fn to_compressed_bytes(&self) -> Result<Vec<u8>, LevelProcessError> {
+        let mut bytes = Vec::new();
+
+        self.meta.write_gj(&mut bytes)?;
+
+        bytes.push(b';');
+
+        for object in &self.objects {
+            object.write_gj(&mut bytes)?;
+            bytes.push(b';');
+        }
 
-    if portals.is_empty() {
-        return pos / speed;
+        compress(&bytes)
     }
+}
 
+/// Computes the time it takes the player to reach the given x position
+///
+/// `portals` must be sorted by their x position. Since we cannot know which reverse portals the
+/// player actually touches, they are assumed to come in pairs: The right portal of each pair
+/// reverses the player, who then moves back to the left portal, turns around again, and passes the
+/// entire section a second time. An unpaired final reverse portal is ignored.
+fn get_seconds_from_x_pos(pos: f32, start_speed: Speed, portals: &[(f32, ObjectData)]) -> f32 {
+    let mut speed: f32 = start_speed.into();
     let mut last_obj_pos = 0.0;
     let mut total_time = 0.0;
+    let mut last_reverse_portal = None;
 
-    for (x, portal_speed) in portals {
-        // distance between last portal and this one
-        let current_segment = x - last_obj_pos;
-
-        // break if we're past the position we want to calculate the position to
-        if pos <= current_segment {
+    for &(x, portal) in portals {
+        // break if we're past the position we want to calculate the time to
+        if pos <= x {
             break;
         }
 
-        // Calculate time spent in this segment and add to total time
-        total_time += current_segment / speed;
+        match portal {
+            ObjectData::SpeedPortal { speed: portal_speed, .. } => {
+                // Calculate time spent in this segment and add to total time
+                total_time += (x - last_obj_pos) / speed;
 
-        speed = (*portal_speed).into();
-
-        last_obj_pos = *x;
+                speed = portal_speed.into();
+                last_obj_pos = x;
+            },
+            ObjectData::ReversePortal { .. } => match last_reverse_portal.take() {
+                None => last_reverse_portal = Some(x),
+                Some(turning_point) => {
+                    // The reversed section is passed backwards, and then forwards again
+                    total_time += (x - last_obj_pos) / speed + 2.0 * (x - turning_point) / speed;
+
+                    last_obj_pos = x;
+                },
+            },
+            _ => (),
+        }
     }
 
     // add the time spent between end and last portal to total time and return
@@ -853,7 +2060,23 @@ fn get_seconds_from_x_pos(pos: f32, start_speed: Speed, portals: &[(f32, Speed)]
 mod tests {
     use base64::{engine::general_purpose::URL_SAFE, Engine};
 
-    use crate::model::level::{robtop_encode_level_password, Password};
+    #[cfg(feature = "std")]
+    use crate::model::level::{
+        object::{game_mode::GameMode, StartPosition},
+        LevelProcessError, Objects,
+    };
+    use crate::{
+        model::level::{
+            get_seconds_from_x_pos,
+            object::{speed::Speed, ObjectData},
+            robtop_encode_level_password, DemonRating, Description, DescriptionDecoder, DescriptionError, FaceGlow, FeatureTier, Featured,
+            LevelRating, ObjectCounts, Password, MAX_DESCRIPTION_LENGTH,
+        },
+        ThunkProcessor,
+    };
+    use alloc::borrow::Cow;
+    #[cfg(feature = "std")]
+    use alloc::vec::Vec;
 
     #[test]
     fn deserialize_password() {
@@ -864,10 +2087,92 @@ mod tests {
         assert_eq!(Password::from_robtop("0").unwrap(), Password::NoCopy);
     }
 
+    #[test]
+    fn difficulty_faces() {
+        let face = LevelRating::Demon(DemonRating::Unknown(60)).difficulty_face(Featured::Featured(12), FeatureTier::None);
+
+        assert_eq!(face.rating, LevelRating::Demon(DemonRating::Hard));
+        assert_eq!(face.sprite_name(), "difficulty_06_btn_001.png");
+        assert_eq!(face.glow_sprite_name(), Some("GJ_featuredCoin_001.png"));
+
+        let face = LevelRating::Insane.difficulty_face(Featured::NotFeatured, FeatureTier::Legendary);
+
+        assert_eq!(face.glow, FaceGlow::Legendary);
+        assert_eq!(face.sprite_name(), "difficulty_05_btn_001.png");
+        assert_eq!(
+            LevelRating::Unknown(7).difficulty_face(Featured::Unfeatured, FeatureTier::Unknown(9)),
+            LevelRating::NotAvailable.difficulty_face(Featured::NotFeatured, FeatureTier::None)
+        );
+    }
+
+    #[test]
+    fn length_with_speed_and_reverse_portals() {
+        let normal: f32 = Speed::Normal.into();
+        let fast: f32 = Speed::Fast.into();
+        let portal = |speed| ObjectData::SpeedPortal { checked: true, speed };
+        let reverse = ObjectData::ReversePortal { checked: true };
+        let assert_close = |left: f32, right: f32| assert!((left - right).abs() < 1e-4, "{} != {}", left, right);
+
+        assert_close(get_seconds_from_x_pos(1000.0, Speed::Normal, &[]), 1000.0 / normal);
+        assert_close(
+            get_seconds_from_x_pos(1000.0, Speed::Normal, &[(400.0, portal(Speed::Fast))]),
+            400.0 / normal + 600.0 / fast,
+        );
+
+        // Reversed at 600, turned around again at 200, then continuing until 1000
+        let portals = [(200.0, reverse), (600.0, reverse)];
+        assert_close(get_seconds_from_x_pos(1000.0, Speed::Normal, &portals), 1800.0 / normal);
+
+        // Portals at or past the end do not matter, and unpaired reverse portals are ignored
+        let portals = [(500.0, reverse), (1000.0, portal(Speed::Fast))];
+        assert_close(get_seconds_from_x_pos(1000.0, Speed::Normal, &portals), 1000.0 / normal);
+    }
+
+    #[test]
+    fn process_object_counts() {
+        let raw = "29_566_73_98_29";
+        let counts = ObjectCounts::from_unprocessed(Cow::Borrowed(raw)).unwrap();
+
+        assert_eq!(counts.counts, vec![29, 566, 73, 98, 29]);
+        assert_eq!(ObjectCounts::as_unprocessed(&counts).unwrap(), raw);
+        assert_eq!(ObjectCounts::from_unprocessed(Cow::Borrowed("")).unwrap(), ObjectCounts::default());
+        assert!(ObjectCounts::from_unprocessed(Cow::Borrowed("29__73")).is_err());
+    }
+
+    #[test]
+    fn process_description() {
+        let description = DescriptionDecoder::from_unprocessed(Cow::Borrowed("aGFwcHkgbmV3IHllYXIhIQ==")).unwrap();
+
+        assert_eq!(description, Description(Cow::Borrowed("happy new year!!")));
+        assert_eq!(
+            DescriptionDecoder::as_unprocessed(&description).unwrap(),
+            "aGFwcHkgbmV3IHllYXIhIQ=="
+        );
+
+        assert!(Description::new("x".repeat(MAX_DESCRIPTION_LENGTH)).is_ok());
+        assert!(matches!(
+            Description::new("x".repeat(MAX_DESCRIPTION_LENGTH + 1)),
+            Err(DescriptionError::TooLong { length: 181 })
+        ));
+        assert!(matches!(
+            Description::new("two\nlines"),
+            Err(DescriptionError::InvalidCharacter('\n'))
+        ));
+
+        // Descriptions the game would reject still exist on the servers, and need to roundtrip
+        let emoji = DescriptionDecoder::as_unprocessed(&Description(Cow::Borrowed("🎉"))).unwrap();
+
+        assert_eq!(
+            DescriptionDecoder::from_unprocessed(emoji).unwrap(),
+            Description(Cow::Borrowed("🎉"))
+        );
+        assert!(DescriptionDecoder::as_unprocessed(&Description("x".repeat(MAX_DESCRIPTION_LENGTH + 1).into())).is_ok());
+    }
+
     #[test]
     fn serialize_password() {
         let encoded = robtop_encode_level_password(123456);
-        let result = URL_SAFE.encode(&encoded);
+        let result = URL_SAFE.encode(encoded);
 
         assert_eq!(result, "AwcBBQAHAA==")
     }
@@ -878,8 +2183,87 @@ mod tests {
         // in-game code for padding is inconsistent, see above test cases
 
         // password of 'Time Pressure' by AeonAir
-        assert_eq!(URL_SAFE.encode(&robtop_encode_level_password(3101)), "AwYDBQUCBw==");
+        assert_eq!(URL_SAFE.encode(robtop_encode_level_password(3101)), "AwYDBQUCBw==");
         // password of 'Breakthrough' by Hinds1324
-        assert_eq!(URL_SAFE.encode(&robtop_encode_level_password(0)), "AwYDBgQCBg==")
+        assert_eq!(URL_SAFE.encode(robtop_encode_level_password(0)), "AwYDBgQCBg==")
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn process_truncated_level_data() {
+        // Decodes to a single byte, which is too short to determine the compression scheme
+        assert!(matches!(
+            Objects::from_unprocessed(Cow::Borrowed("AA==")),
+            Err(LevelProcessError::UnknownCompression)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn process_level_data_with_malformed_objects() {
+        let level_string = "kA4,0;1,1,2,15,3,15;1,1,2,abc,3,15;1,1,2,45,3,15;";
+        let unprocessed = URL_SAFE.encode(super::compress(level_string.as_bytes()).unwrap());
+
+        assert!(matches!(
+            Objects::from_unprocessed(Cow::Borrowed(&unprocessed)),
+            Err(LevelProcessError::Deserialize(_))
+        ));
+
+        let (objects, malformed) = Objects::from_unprocessed_lossy(&unprocessed).unwrap();
+
+        assert_eq!(objects.objects.len(), 2);
+        assert_eq!(malformed.len(), 1);
+        assert_eq!(malformed[0].index, 1);
+        assert_eq!(malformed[0].raw, "1,1,2,abc,3,15");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn start_positions() {
+        let level_string = "kA4,1;1,1,2,0,3,15;1,31,2,500,3,15,kA2,1,kA4,3,kA11,1;1,203,2,600,3,15,13,1;1,1,2,1000,3,15;";
+        let unprocessed = URL_SAFE.encode(super::compress(level_string.as_bytes()).unwrap());
+        let objects = Objects::from_unprocessed(Cow::Borrowed(&unprocessed)).unwrap();
+        let normal: f32 = Speed::Normal.into();
+        let fast: f32 = Speed::Fast.into();
+
+        let start_positions: Vec<_> = objects.start_positions().collect();
+
+        assert_eq!(start_positions.len(), 1);
+        assert_eq!(start_positions[0].x, 500.0);
+        assert_eq!(
+            start_positions[0].metadata,
+            ObjectData::StartPosition(StartPosition {
+                speed: Speed::Fast,
+                game_mode: GameMode::Ship,
+                mini: false,
+                dual: false,
+                gravity_inverted: true,
+            })
+        );
+
+        assert!((objects.length_in_seconds() - (600.0 / normal + 400.0 / fast)).abs() < 1e-4);
+        assert!((objects.length_in_seconds_from(start_positions[0]) - 500.0 / fast).abs() < 1e-4);
+
+        // Start positions survive a roundtrip through the level string format
+        let reprocessed = Objects::as_unprocessed(&objects).unwrap();
+
+        assert_eq!(Objects::from_unprocessed(reprocessed).unwrap(), objects);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn custom_objects() {
+        let plain = "1,1,2,15,3,15;1,203,2,45,3,15,13,1;";
+        let objects = Objects::parse_custom_objects(plain).unwrap();
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[1].x, 45.0);
+        assert!(matches!(objects[1].metadata, ObjectData::SpeedPortal { checked: true, .. }));
+
+        let encoded = URL_SAFE.encode(super::compress(plain.as_bytes()).unwrap());
+
+        assert_eq!(Objects::parse_custom_objects(&encoded).unwrap(), objects);
+        assert_eq!(Objects::parse_custom_objects("").unwrap(), Vec::new());
+        assert!(Objects::parse_custom_objects("1,1,2,abc,3,15;").is_err());
     }
 }
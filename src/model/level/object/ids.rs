@@ -3,3 +3,11 @@ pub const NORMAL_PORTAL: u16 = 201;
 pub const MEDIUM_PORTAL: u16 = 202;
 pub const FAST_PORTAL: u16 = 203;
 pub const VERY_FAST_PORTAL: u16 = 1334;
+/// The "reverse gameplay" object, which flips the direction the player moves in
+pub const REVERSE_PORTAL: u16 = 1917;
+
+/// A start position, see [`StartPosition`](super::StartPosition)
+pub const START_POSITION: u16 = 31;
+
+pub const SECRET_COIN: u16 = 142;
+pub const USER_COIN: u16 = 1329;
@@ -1,5 +1,6 @@
 use crate::{
-    model::level::object::{ids, speed::Speed, LevelObject, ObjectData},
+    model::level::object::{game_mode::GameMode, ids, speed::Speed, LevelObject, ObjectData, StartPosition},
+    schema::{self, FieldSchema},
     Dash, GJFormat,
 };
 use serde::{Deserialize, Serialize};
@@ -29,9 +30,41 @@ pub struct InternalLevelObject {
     // portal related fields
     #[serde(rename = "13", default)]
     checked: bool,
+
+    // start position related fields
+    #[serde(rename = "kA2", default, skip_serializing_if = "Option::is_none")]
+    game_mode: Option<u8>,
+
+    #[serde(rename = "kA3", default, skip_serializing_if = "Option::is_none")]
+    mini: Option<bool>,
+
+    #[serde(rename = "kA4", default, skip_serializing_if = "Option::is_none")]
+    speed: Option<u8>,
+
+    #[serde(rename = "kA8", default, skip_serializing_if = "Option::is_none")]
+    dual: Option<bool>,
+
+    #[serde(rename = "kA11", default, skip_serializing_if = "Option::is_none")]
+    gravity_inverted: Option<bool>,
 }
 
 impl<'de> Dash<'de> for LevelObject {
+    const INDICES: &'static [&'static str] = &schema::indices::<12>(Self::FIELDS);
+    const FIELDS: &'static [FieldSchema] = &[
+        FieldSchema::new("1", "id", "u16"),
+        FieldSchema::new("2", "x", "f32"),
+        FieldSchema::new("3", "y", "f32"),
+        FieldSchema::new("4", "flipped_x", "bool"),
+        FieldSchema::new("5", "flipped_y", "bool"),
+        FieldSchema::new("6", "rotation", "f32"),
+        FieldSchema::new("13", "metadata", "ObjectData"),
+        FieldSchema::new("kA2", "metadata", "ObjectData"),
+        FieldSchema::new("kA3", "metadata", "ObjectData"),
+        FieldSchema::new("kA4", "metadata", "ObjectData"),
+        FieldSchema::new("kA8", "metadata", "ObjectData"),
+        FieldSchema::new("kA11", "metadata", "ObjectData"),
+    ];
+
     fn dash_deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let internal = InternalLevelObject::deserialize(deserializer)?;
 
@@ -44,6 +77,10 @@ impl<'de> Dash<'de> for LevelObject {
                 checked: internal.checked,
                 speed: Speed::Normal,
             },
+            ids::MEDIUM_PORTAL => ObjectData::SpeedPortal {
+                checked: internal.checked,
+                speed: Speed::Medium,
+            },
             ids::FAST_PORTAL => ObjectData::SpeedPortal {
                 checked: internal.checked,
                 speed: Speed::Fast,
@@ -52,6 +89,14 @@ impl<'de> Dash<'de> for LevelObject {
                 checked: internal.checked,
                 speed: Speed::VeryFast,
             },
+            ids::REVERSE_PORTAL => ObjectData::ReversePortal { checked: internal.checked },
+            ids::START_POSITION => ObjectData::StartPosition(StartPosition {
+                speed: internal.speed.map(Speed::from).unwrap_or_default(),
+                game_mode: internal.game_mode.map(GameMode::from).unwrap_or_default(),
+                mini: internal.mini.unwrap_or_default(),
+                dual: internal.dual.unwrap_or_default(),
+                gravity_inverted: internal.gravity_inverted.unwrap_or_default(),
+            }),
             _ => ObjectData::Unknown,
         };
 
@@ -79,9 +124,16 @@ impl<'de> Dash<'de> for LevelObject {
 
         match self.metadata {
             ObjectData::None | ObjectData::Unknown => {},
-            ObjectData::SpeedPortal { checked, .. } => {
+            ObjectData::SpeedPortal { checked, .. } | ObjectData::ReversePortal { checked } => {
                 internal.checked = checked;
             },
+            ObjectData::StartPosition(start_position) => {
+                internal.game_mode = Some(start_position.game_mode.into());
+                internal.mini = Some(start_position.mini);
+                internal.speed = Some(start_position.speed.into());
+                internal.dual = Some(start_position.dual);
+                internal.gravity_inverted = Some(start_position.gravity_inverted);
+            },
         };
 
         internal.serialize(serializer)
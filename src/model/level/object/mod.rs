@@ -1,6 +1,7 @@
-use crate::model::level::object::speed::Speed;
+use crate::model::level::object::{game_mode::GameMode, speed::Speed};
 use serde::{Deserialize, Serialize};
 
+pub mod game_mode;
 pub mod ids;
 mod internal;
 pub mod speed;
@@ -21,5 +22,53 @@ pub struct LevelObject {
 pub enum ObjectData {
     None,
     Unknown,
-    SpeedPortal { checked: bool, speed: Speed },
+    SpeedPortal {
+        checked: bool,
+        speed: Speed,
+    },
+    /// A portal reversing the direction the player moves in, without changing their speed
+    ReversePortal {
+        checked: bool,
+    },
+    /// A start position, from which the level can be played in practice mode
+    StartPosition(StartPosition),
+}
+
+/// The settings the player starts with when playing from a start position
+///
+/// ## GD Internals:
+/// Start position objects embed these settings using the same keys as the level's metadata
+/// section (see [`LevelMetadata`](crate::model::level::metadata::LevelMetadata)), e.g. `kA4` for
+/// the speed.
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Default, Serialize, Deserialize)]
+pub struct StartPosition {
+    /// The speed the player starts with
+    ///
+    /// ## GD Internals:
+    /// This value is provided at key `kA4`
+    pub speed: Speed,
+
+    /// The game mode the player starts in
+    ///
+    /// ## GD Internals:
+    /// This value is provided at key `kA2`
+    pub game_mode: GameMode,
+
+    /// Whether the player starts in mini mode
+    ///
+    /// ## GD Internals:
+    /// This value is provided at key `kA3`
+    pub mini: bool,
+
+    /// Whether the player starts in dual mode
+    ///
+    /// ## GD Internals:
+    /// This value is provided at key `kA8`
+    pub dual: bool,
+
+    /// Whether the player starts with inverted gravity
+    ///
+    /// ## GD Internals:
+    /// This value is provided at key `kA11`
+    pub gravity_inverted: bool,
 }
@@ -1,4 +1,3 @@
-// TODO: Speed portals and stuff
 use serde::{Deserialize, Serialize};
 
 /// Enum modelling the different speeds a player can have during gameplay
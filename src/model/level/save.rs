@@ -0,0 +1,349 @@
+//! Module containing conversions between the server's indexed level format and the key based
+//! format used in save files and `.gmd` exports
+//!
+//! ## GD Internals:
+//! Locally, the game stores levels as property list dictionaries. Instead of the numeric indices
+//! used by the servers, fields are identified by keys of the form `k<n>`, with the special key
+//! `kCEK` identifying the type of object stored in the dictionary (`4` for levels). Values are
+//! tagged with their type: `<i>` for integers, `<s>` for strings, `<r>` for reals, and `<t />` for
+//! `true`. Fields holding their default value (zero, `false`, or the empty string) are generally
+//! omitted.
+//!
+//! Both formats are converted into one another via [`LEVEL_KEYS`], which describes how each
+//! index corresponds to a key. Loading a level from the save format therefore goes through the
+//! indexed format, meaning the same [`Level`] model can be used for both.
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+use core::fmt::Write as _;
+
+use itoa::Buffer;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    model::level::{Level, LevelData, Password},
+    serde::{IndexedDeserializer, ProcessError, SerError, ThunkProcessor},
+    GJFormat,
+};
+
+/// The value of the `kCEK` key identifying a dictionary as a level
+pub const LEVEL_KCEK: &str = "4";
+
+/// How the value of some field is represented in the save format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// Stored as `<i>`, identically to the indexed format
+    Integer,
+
+    /// Stored as `<s>`, identically to the indexed format
+    String,
+
+    /// Stored as `<t />` if true, and omitted otherwise. In the indexed format, false is `0` or the
+    /// empty string
+    Bool,
+
+    /// Stored as `<i>` holding the plain password, with `1` meaning free copy and `0` meaning no
+    /// copy. In the indexed format, this is the encoded form described in [`Password`]
+    Password,
+}
+
+/// Describes how a field of the indexed format corresponds to a key of the save format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveKey {
+    /// The field's index in the server's format
+    pub index: &'static str,
+
+    /// The field's key in the save format
+    pub key: &'static str,
+
+    /// How the field's value is represented in the save format
+    pub kind: ValueKind,
+
+    /// Whether the field is required by [`Level`]. Since the game omits fields holding default
+    /// values from save files, these are filled in when converting to the indexed format
+    pub required: bool,
+}
+
+const fn key(index: &'static str, key: &'static str, kind: ValueKind, required: bool) -> SaveKey {
+    SaveKey {
+        index,
+        key,
+        kind,
+        required,
+    }
+}
+
+/// Table describing the correspondence between indices of the server format and keys of the save
+/// format, in ascending order of the indices
+///
+/// Indices without a corresponding key are dropped when converting to the save format. In
+/// particular, save files do not contain a level's upload and update times (indices `28` and
+/// `29`), which are set to [`UNKNOWN_AGE`] when converting back.
+pub const LEVEL_KEYS: &[SaveKey] = &[
+    key("1", "k1", ValueKind::Integer, true),
+    key("2", "k2", ValueKind::String, true),
+    key("3", "k3", ValueKind::String, false),
+    key("4", "k4", ValueKind::String, false),
+    key("5", "k16", ValueKind::Integer, true),
+    key("6", "k6", ValueKind::Integer, true),
+    key("8", "k9", ValueKind::Integer, true),
+    key("9", "k10", ValueKind::Integer, true),
+    key("10", "k11", ValueKind::Integer, true),
+    key("12", "k8", ValueKind::Integer, true),
+    key("13", "k17", ValueKind::Integer, true),
+    key("14", "k22", ValueKind::Integer, true),
+    key("15", "k23", ValueKind::Integer, true),
+    key("17", "k25", ValueKind::Bool, true),
+    key("18", "k26", ValueKind::Integer, true),
+    key("19", "k27", ValueKind::Integer, true),
+    key("25", "k33", ValueKind::Bool, true),
+    key("27", "k41", ValueKind::Password, false),
+    key("30", "k42", ValueKind::Integer, true),
+    key("31", "k43", ValueKind::Bool, true),
+    key("35", "k45", ValueKind::Integer, true),
+    key("36", "k67", ValueKind::String, false),
+    key("37", "k37", ValueKind::Integer, true),
+    key("38", "k65", ValueKind::Bool, true),
+    key("39", "k66", ValueKind::Integer, true),
+    key("40", "k72", ValueKind::Bool, false),
+    key("41", "k74", ValueKind::Integer, false),
+    key("42", "k75", ValueKind::Integer, true),
+    key("43", "k76", ValueKind::Integer, true),
+    key("45", "k48", ValueKind::Integer, true),
+    key("46", "k80", ValueKind::Integer, false),
+    key("47", "k81", ValueKind::Integer, false),
+];
+
+/// Indices required by [`LevelData`] that have no counterpart in the save format
+const UNMAPPED_LEVEL_DATA_INDICES: &[&str] = &["28", "29"];
+
+/// Placeholder used for a level's upload and update times when converting from the save format,
+/// which does not store them
+pub const UNKNOWN_AGE: &str = "0 seconds";
+
+/// Errors that can occur when converting between the indexed and the save format
+#[derive(Debug, Error)]
+pub enum SaveFormatError {
+    /// The input was not a well-formed dictionary in the save format
+    #[error("malformed save data: {0}")]
+    Malformed(&'static str),
+
+    /// The dictionary's `kCEK` key did not identify it as a level
+    #[error("dictionary is not a level (kCEK {0})")]
+    NotALevel(String),
+
+    /// The input was not a well-formed level in the indexed format
+    #[error("malformed level data: {0}")]
+    Indexed(String),
+
+    /// The level's password could not be converted
+    #[error("{0}")]
+    Password(#[from] ProcessError),
+
+    /// The level could not be serialized into the indexed format
+    #[error("{0}")]
+    Serialize(#[from] SerError),
+}
+
+/// Looks up the save format key corresponding to the given index
+pub fn key_for_index(index: &str) -> Option<&'static SaveKey> {
+    LEVEL_KEYS.iter().find(|save_key| save_key.index == index)
+}
+
+/// Looks up the index corresponding to the given save format key
+pub fn index_for_key(key: &str) -> Option<&'static SaveKey> {
+    LEVEL_KEYS.iter().find(|save_key| save_key.key == key)
+}
+
+/// Converts a level in the server's indexed format into a dictionary in the save format
+///
+/// The returned string contains the dictionary's entries wrapped in `<d>` tags, as they appear in
+/// save files.
+pub fn indexed_to_save(indexed: &str) -> Result<String, SaveFormatError> {
+    let mut deserializer = IndexedDeserializer::new(indexed, ":", true);
+    let fields = BTreeMap::<&str, &str>::deserialize(&mut deserializer).map_err(|err| SaveFormatError::Indexed(err.to_string()))?;
+
+    let mut save = String::from("<d><k>kCEK</k><i>");
+    save.push_str(LEVEL_KCEK);
+    save.push_str("</i>");
+
+    for save_key in LEVEL_KEYS {
+        let value = match fields.get(save_key.index) {
+            Some(value) => *value,
+            None => continue,
+        };
+
+        match save_key.kind {
+            ValueKind::Integer => write_entry(&mut save, save_key.key, "i", value),
+            ValueKind::String => write_entry(&mut save, save_key.key, "s", &escape(value)),
+            ValueKind::Bool => {
+                if !matches!(value, "" | "0") {
+                    let _ = write!(save, "<k>{}</k><t />", save_key.key);
+                }
+            },
+            ValueKind::Password => {
+                let password = match Password::from_robtop(value)? {
+                    Password::NoCopy => 0,
+                    Password::FreeCopy => 1,
+                    Password::PasswordCopy(password) => password,
+                };
+
+                write_entry(&mut save, save_key.key, "i", Buffer::new().format(password))
+            },
+        }
+    }
+
+    save.push_str("</d>");
+
+    Ok(save)
+}
+
+/// Converts a level dictionary in the save format into the server's indexed format
+///
+/// The input may either be the dictionary's entries, or the entries wrapped in `<d>`/`<dict>` tags.
+/// Keys without a corresponding index are ignored. The result can be loaded using
+/// [`GJFormat::from_gj_str`].
+pub fn save_to_indexed(save: &str) -> Result<String, SaveFormatError> {
+    let entries = parse_dictionary(save)?;
+
+    match entries.get("kCEK") {
+        Some(Cow::Borrowed(LEVEL_KCEK)) => (),
+        Some(kcek) => return Err(SaveFormatError::NotALevel(kcek.to_string())),
+        None => return Err(SaveFormatError::Malformed("missing kCEK key")),
+    }
+
+    let mut indexed = String::new();
+    // Only levels including their level data need the level data's indices to be present
+    let has_level_data = entries.contains_key("k4");
+
+    for save_key in LEVEL_KEYS {
+        let value = match (entries.get(save_key.key), save_key.kind) {
+            (Some(value), ValueKind::Password) => {
+                let password = match value.parse().map_err(ProcessError::from)? {
+                    0 => Password::NoCopy,
+                    1 => Password::FreeCopy,
+                    password => Password::PasswordCopy(password),
+                };
+
+                Password::as_unprocessed(&password)?.into_owned()
+            },
+            (Some(value), _) => value.to_string(),
+            // The game omits the password of levels that cannot be copied
+            (None, ValueKind::Password) if has_level_data => "0".to_string(),
+            (None, ValueKind::Bool | ValueKind::Integer | ValueKind::Password) if save_key.required => "0".to_string(),
+            (None, _) if save_key.required => String::new(),
+            (None, _) => continue,
+        };
+
+        push_field(&mut indexed, save_key.index, &value);
+    }
+
+    if has_level_data {
+        for index in UNMAPPED_LEVEL_DATA_INDICES {
+            push_field(&mut indexed, index, UNKNOWN_AGE);
+        }
+    }
+
+    Ok(indexed)
+}
+
+impl<'a> Level<'a, LevelData<'a>> {
+    /// Converts this level into a dictionary in the save format, see [`indexed_to_save`]
+    pub fn to_save_string(&self) -> Result<String, SaveFormatError> {
+        indexed_to_save(&self.to_gj_string()?)
+    }
+}
+
+fn push_field(indexed: &mut String, index: &str, value: &str) {
+    if !indexed.is_empty() {
+        indexed.push(':');
+    }
+
+    indexed.push_str(index);
+    indexed.push(':');
+    indexed.push_str(value);
+}
+
+fn write_entry(save: &mut String, key: &str, tag: &str, value: &str) {
+    let _ = write!(save, "<k>{}</k><{}>{}</{}>", key, tag, value, tag);
+}
+
+fn escape(value: &str) -> Cow<'_, str> {
+    if !value.contains(['&', '<', '>']) {
+        return Cow::Borrowed(value);
+    }
+
+    Cow::Owned(value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"))
+}
+
+fn unescape(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+
+    Cow::Owned(
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// Parses the entries of a (non-nested) property list dictionary, mapping keys to their values
+/// (`"1"` for `<t />`)
+fn parse_dictionary(input: &str) -> Result<BTreeMap<&str, Cow<'_, str>>, SaveFormatError> {
+    let mut entries = BTreeMap::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("<k>") {
+        rest = &rest[start + 3..];
+
+        let end = rest.find("</k>").ok_or(SaveFormatError::Malformed("unterminated key"))?;
+        let key = &rest[..end];
+
+        rest = rest[end + 4..].trim_start();
+
+        let tag_end = rest.find('>').ok_or(SaveFormatError::Malformed("missing value for key"))?;
+        let tag = &rest[..=tag_end];
+
+        let value = match tag {
+            "<t/>" | "<t />" => {
+                rest = &rest[tag_end + 1..];
+
+                Cow::Borrowed("1")
+            },
+            "<s/>" | "<s />" => {
+                rest = &rest[tag_end + 1..];
+
+                Cow::Borrowed("")
+            },
+            "<i>" | "<s>" | "<r>" => {
+                let closing = match tag {
+                    "<i>" => "</i>",
+                    "<s>" => "</s>",
+                    _ => "</r>",
+                };
+
+                rest = &rest[tag_end + 1..];
+
+                let value_end = rest.find(closing).ok_or(SaveFormatError::Malformed("unterminated value"))?;
+                let value = unescape(&rest[..value_end]);
+
+                rest = &rest[value_end + closing.len()..];
+
+                value
+            },
+            _ => return Err(SaveFormatError::Malformed("unsupported value type")),
+        };
+
+        entries.insert(key, value);
+    }
+
+    Ok(entries)
+}
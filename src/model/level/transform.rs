@@ -0,0 +1,211 @@
+//! Module containing geometric transformations of level objects, e.g. for merging multiple levels
+//! into one
+//!
+//! All transformations keep the objects' [`flipped_x`](LevelObject::flipped_x),
+//! [`flipped_y`](LevelObject::flipped_y) and [`rotation`](LevelObject::rotation) consistent with
+//! their new positions, so that the transformed objects look the same as the original ones, just
+//! moved, mirrored or rotated as a whole.
+//!
+//! ## GD Internals:
+//! Object rotations are given in degrees, with positive values rotating clockwise. The y axis
+//! points upwards.
+
+use crate::model::level::{analysis, object::LevelObject, Objects};
+
+/// Brings a rotation into the range `(-180, 180]`
+fn normalize_rotation(rotation: f32) -> f32 {
+    let rotation = rotation % 360.0;
+
+    if rotation > 180.0 {
+        rotation - 360.0
+    } else if rotation <= -180.0 {
+        rotation + 360.0
+    } else {
+        rotation
+    }
+}
+
+impl LevelObject {
+    /// Moves this object by the given offsets
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        self.x += dx;
+        self.y += dy;
+    }
+
+    /// Mirrors this object along the vertical line at the given x position
+    pub fn mirror_x(&mut self, axis: f32) {
+        self.x = 2.0 * axis - self.x;
+        self.flipped_x = !self.flipped_x;
+        self.rotation = normalize_rotation(-self.rotation);
+    }
+
+    /// Mirrors this object along the horizontal line at the given y position
+    pub fn mirror_y(&mut self, axis: f32) {
+        self.y = 2.0 * axis - self.y;
+        self.flipped_y = !self.flipped_y;
+        self.rotation = normalize_rotation(-self.rotation);
+    }
+
+    /// Rotates this object clockwise by the given number of quarter turns around the given point
+    ///
+    /// Unlike [`LevelObject::rotate_around`], this does not introduce any rounding errors.
+    pub fn rotate_quarter_turns_around(&mut self, center_x: f32, center_y: f32, turns: i32) {
+        let (dx, dy) = (self.x - center_x, self.y - center_y);
+        let (dx, dy) = match turns.rem_euclid(4) {
+            0 => (dx, dy),
+            1 => (dy, -dx),
+            2 => (-dx, -dy),
+            _ => (-dy, dx),
+        };
+
+        self.x = center_x + dx;
+        self.y = center_y + dy;
+        self.rotation = normalize_rotation(self.rotation + 90.0 * turns.rem_euclid(4) as f32);
+    }
+
+    /// Rotates this object clockwise by the given angle (in degrees) around the given point
+    #[cfg(feature = "std")]
+    pub fn rotate_around(&mut self, center_x: f32, center_y: f32, degrees: f32) {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (dx, dy) = (self.x - center_x, self.y - center_y);
+
+        self.x = center_x + dx * cos + dy * sin;
+        self.y = center_y - dx * sin + dy * cos;
+        self.rotation = normalize_rotation(self.rotation + degrees);
+    }
+}
+
+impl Objects {
+    /// Moves all objects by the given offsets
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        for object in &mut self.objects {
+            object.translate(dx, dy);
+        }
+    }
+
+    /// Mirrors the level horizontally, along the vertical line through the center of its
+    /// [`bounding_box`](analysis::bounding_box)
+    pub fn mirror_x(&mut self) {
+        if let Some(bounds) = analysis::bounding_box(self) {
+            let (axis, _) = bounds.center();
+
+            for object in &mut self.objects {
+                object.mirror_x(axis);
+            }
+        }
+    }
+
+    /// Mirrors the level vertically, along the horizontal line through the center of its
+    /// [`bounding_box`](analysis::bounding_box)
+    pub fn mirror_y(&mut self) {
+        if let Some(bounds) = analysis::bounding_box(self) {
+            let (_, axis) = bounds.center();
+
+            for object in &mut self.objects {
+                object.mirror_y(axis);
+            }
+        }
+    }
+
+    /// Rotates the level clockwise by the given number of quarter turns around the center of its
+    /// [`bounding_box`](analysis::bounding_box)
+    pub fn rotate_quarter_turns(&mut self, turns: i32) {
+        if let Some(bounds) = analysis::bounding_box(self) {
+            let (center_x, center_y) = bounds.center();
+
+            for object in &mut self.objects {
+                object.rotate_quarter_turns_around(center_x, center_y, turns);
+            }
+        }
+    }
+
+    /// Rotates the level clockwise by the given angle (in degrees) around the center of its
+    /// [`bounding_box`](analysis::bounding_box)
+    #[cfg(feature = "std")]
+    pub fn rotate(&mut self, degrees: f32) {
+        if let Some(bounds) = analysis::bounding_box(self) {
+            let (center_x, center_y) = bounds.center();
+
+            for object in &mut self.objects {
+                object.rotate_around(center_x, center_y, degrees);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::model::level::{
+        metadata::LevelMetadata,
+        object::{LevelObject, ObjectData},
+        transform::normalize_rotation,
+        Objects,
+    };
+
+    fn object(x: f32, y: f32, rotation: f32) -> LevelObject {
+        LevelObject {
+            id: 1,
+            x,
+            y,
+            flipped_x: false,
+            flipped_y: false,
+            rotation,
+            metadata: ObjectData::Unknown,
+        }
+    }
+
+    fn objects() -> Objects {
+        Objects {
+            meta: LevelMetadata::default(),
+            objects: vec![object(15.0, 15.0, 0.0), object(75.0, 45.0, 90.0)],
+        }
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(normalize_rotation(270.0), -90.0);
+        assert_eq!(normalize_rotation(-180.0), 180.0);
+        assert_eq!(normalize_rotation(-450.0), -90.0);
+        assert_eq!(normalize_rotation(45.0), 45.0);
+    }
+
+    #[test]
+    fn translate_and_mirror() {
+        let mut objects = objects();
+
+        objects.translate(30.0, -15.0);
+        objects.mirror_x();
+
+        let first = objects.objects[0];
+        let second = objects.objects[1];
+
+        assert_eq!((first.x, first.y), (105.0, 0.0));
+        assert_eq!((second.x, second.y), (45.0, 30.0));
+        assert!(second.flipped_x && !second.flipped_y);
+        assert_eq!(second.rotation, -90.0);
+
+        objects.mirror_y();
+
+        assert_eq!(objects.objects[0].y, 30.0);
+        assert!(objects.objects[0].flipped_y);
+        assert_eq!(objects.objects[1].rotation, 90.0);
+    }
+
+    #[test]
+    fn rotate_quarter_turns() {
+        let mut objects = objects();
+
+        // The center of the bounding box is (45, 30)
+        objects.rotate_quarter_turns(1);
+
+        assert_eq!((objects.objects[0].x, objects.objects[0].y), (30.0, 60.0));
+        assert_eq!((objects.objects[1].x, objects.objects[1].y), (60.0, 0.0));
+        assert_eq!(objects.objects[1].rotation, 180.0);
+
+        objects.rotate_quarter_turns(-1);
+
+        assert_eq!(objects, self::objects());
+    }
+}
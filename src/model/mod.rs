@@ -14,8 +14,17 @@
 //!
 //! These versions can be converted to and from each other, simply by borrowing.
 
+use alloc::{borrow::Cow, string::ToString};
+use core::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    str::FromStr,
+    time::Duration,
+};
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
+
+use crate::serde::{ProcessError, ThunkProcessor};
 
 pub mod comment;
 pub mod creator;
@@ -24,7 +33,14 @@ pub mod song;
 pub mod user;
 
 /// Enum modelling the version of a Geometry Dash client
-#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+///
+/// Versions are ordered chronologically, with [`GameVersion::Unknown`] (meaning "before 1.6")
+/// ordered before all other versions.
+///
+/// Some versions can be written in two ways, for example 1.6 as either `Version { major: 0, minor:
+/// 7 }` or `Version { major: 1, minor: 6 }`. Comparisons, hashing and ordering all operate on the
+/// [canonical](GameVersion::canonical) form, so both spellings are equal.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(into = "u8", from = "u8")]
 pub enum GameVersion {
     /// Variant representing an unknown version. This variant is only used for
@@ -38,6 +54,90 @@ pub enum GameVersion {
     Version { minor: u8, major: u8 },
 }
 
+impl GameVersion {
+    /// The most recent version of Geometry Dash known to dash-rs
+    pub const CURRENT: GameVersion = GameVersion::Version { major: 2, minor: 2 };
+
+    /// The canonical representation of this version, which is the one [`Display`] and [`FromStr`]
+    /// agree on and which survives a roundtrip through the numeric representation
+    ///
+    /// ## GD Internals:
+    /// Version 1.6 is encoded as `7` instead of `16`, and `10` means "before 1.6" (as the game only
+    /// started tracking versions with update 1.6). Thus, `Version { major: 1, minor: 6 }`
+    /// canonicalizes to `Version { major: 0, minor: 7 }`, and `Version { major: 1, minor: 0 }`
+    /// canonicalizes to [`GameVersion::Unknown`].
+    pub const fn canonical(self) -> GameVersion {
+        match self {
+            GameVersion::Version { minor: 6, major: 1 } => GameVersion::Version { minor: 7, major: 0 },
+            GameVersion::Version { minor: 0, major: 1 } => GameVersion::Unknown,
+            version => version,
+        }
+    }
+
+    /// Key ordering versions chronologically. [`GameVersion::Unknown`] is ordered before all
+    /// versions, and 1.6 is ordered between 1.5 and 1.7.
+    fn chronological_key(self) -> (bool, u8, u8) {
+        match self.canonical() {
+            GameVersion::Unknown => (false, 0, 0),
+            GameVersion::Version { minor: 7, major: 0 } => (true, 1, 6),
+            GameVersion::Version { minor, major } => (true, major, minor),
+        }
+    }
+}
+
+impl PartialEq for GameVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.chronological_key() == other.chronological_key()
+    }
+}
+
+impl Eq for GameVersion {}
+
+impl Hash for GameVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.chronological_key().hash(state)
+    }
+}
+
+impl Ord for GameVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.chronological_key().cmp(&other.chronological_key())
+    }
+}
+
+impl PartialOrd for GameVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parses the representation produced by [`GameVersion`]'s [`Display`] implementation, e.g. `"2.2"`
+/// or `"Pre 1.6"`
+///
+/// The result is always [canonical](GameVersion::canonical), meaning `"1.0"` parses to
+/// [`GameVersion::Unknown`].
+impl FromStr for GameVersion {
+    type Err = ProcessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s.eq_ignore_ascii_case("Pre 1.6") {
+            return Ok(GameVersion::Unknown);
+        }
+
+        let (major, minor) = s.split_once('.').ok_or(ProcessError::InvalidFormat)?;
+        let (major, minor): (u8, u8) = (major.parse()?, minor.parse()?);
+
+        // The numeric representation has only one digit for the minor version
+        if minor >= 10 || major.checked_mul(10).and_then(|major| major.checked_add(minor)).is_none() {
+            return Err(ProcessError::InvalidFormat);
+        }
+
+        Ok(GameVersion::Version { minor, major }.canonical())
+    }
+}
+
 impl From<u8> for GameVersion {
     fn from(version: u8) -> Self {
         if version == 10 {
@@ -61,7 +161,7 @@ impl From<GameVersion> for u8 {
 }
 
 impl Display for GameVersion {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         match self {
             GameVersion::Unknown => write!(f, "Pre 1.6"),
             GameVersion::Version { minor: 7, major: 0 } => write!(f, "1.6"),
@@ -71,3 +171,121 @@ impl Display for GameVersion {
 }
 
 crate::into_conversion!(GameVersion, u8);
+
+/// Struct modelling the imprecise, relative timestamps Geometry Dash uses in place of actual
+/// dates, e.g. for the time since a level was uploaded
+///
+/// ## GD Internals:
+/// RobTop's servers provide these values as strings of the form `"8 years"` or `"1 month"`, the
+/// `"ago"` is appended by the client. Some private servers include the `"ago"` in the response,
+/// which is accepted when parsing but not retained. The [`Display`] implementation produces
+/// RobTop's phrasing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RelativeAge {
+    /// The amount of [`RelativeAge::unit`]s passed
+    pub amount: u32,
+
+    /// The unit in which the age is given
+    pub unit: TimeUnit,
+}
+
+/// Enum modelling the units of time a [`RelativeAge`] can be given in
+///
+/// Variants are ordered from shortest to longest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeUnit {
+    /// The singular name of this unit, as used by Geometry Dash
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeUnit::Second => "second",
+            TimeUnit::Minute => "minute",
+            TimeUnit::Hour => "hour",
+            TimeUnit::Day => "day",
+            TimeUnit::Week => "week",
+            TimeUnit::Month => "month",
+            TimeUnit::Year => "year",
+        }
+    }
+
+    /// The approximate length of this unit, assuming 30 day months and 365 day years
+    pub fn approximate_duration(self) -> Duration {
+        Duration::from_secs(match self {
+            TimeUnit::Second => 1,
+            TimeUnit::Minute => 60,
+            TimeUnit::Hour => 60 * 60,
+            TimeUnit::Day => 24 * 60 * 60,
+            TimeUnit::Week => 7 * 24 * 60 * 60,
+            TimeUnit::Month => 30 * 24 * 60 * 60,
+            TimeUnit::Year => 365 * 24 * 60 * 60,
+        })
+    }
+
+    fn from_name(name: &str) -> Option<TimeUnit> {
+        let singular = name.strip_suffix('s').unwrap_or(name);
+
+        match singular.to_ascii_lowercase().as_str() {
+            "second" => Some(TimeUnit::Second),
+            "minute" => Some(TimeUnit::Minute),
+            "hour" => Some(TimeUnit::Hour),
+            "day" => Some(TimeUnit::Day),
+            "week" => Some(TimeUnit::Week),
+            "month" => Some(TimeUnit::Month),
+            "year" => Some(TimeUnit::Year),
+            _ => None,
+        }
+    }
+}
+
+impl RelativeAge {
+    /// The approximate duration represented by this [`RelativeAge`]. See
+    /// [`TimeUnit::approximate_duration`]
+    pub fn approximate_duration(&self) -> Duration {
+        self.unit.approximate_duration().saturating_mul(self.amount)
+    }
+}
+
+impl ThunkProcessor for RelativeAge {
+    type Error = ProcessError;
+    type Output<'a> = RelativeAge;
+
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
+        let mut words = unprocessed.split_whitespace();
+
+        let amount = words.next().unwrap_or_default().parse()?;
+        let unit = words.next().and_then(TimeUnit::from_name).ok_or(ProcessError::InvalidFormat)?;
+
+        match (words.next(), words.next()) {
+            (None, _) => Ok(RelativeAge { amount, unit }),
+            (Some(ago), None) if ago.eq_ignore_ascii_case("ago") => Ok(RelativeAge { amount, unit }),
+            _ => Err(ProcessError::InvalidFormat),
+        }
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        Ok(Cow::Owned(processed.to_string()))
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+impl Display for RelativeAge {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        if self.amount == 1 {
+            write!(f, "1 {}", self.unit.name())
+        } else {
+            write!(f, "{} {}s", self.amount, self.unit.name())
+        }
+    }
+}
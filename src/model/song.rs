@@ -1,11 +1,17 @@
-use crate::serde::{GJFormat, PercentDecoder, ProcessError, Thunk};
-use dash_rs_derive::Dash;
-use serde::{Deserialize, Serialize};
-use std::{
+use crate::{
+    model::GameVersion,
+    serde::{GJFormat, PercentDecoder, ProcessError, Thunk},
+};
+use alloc::{
     borrow::Cow,
-    fmt::{Display, Formatter},
+    format,
+    string::{String, ToString},
+    vec::Vec,
 };
-use variant_partial_eq::VariantPartialEq;
+use core::fmt::{Display, Formatter};
+use dash_rs_derive::{Dash, DashPartialEq};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 /// Struct modelling a [`NewgroundsSong`]
 ///
@@ -15,7 +21,12 @@ use variant_partial_eq::VariantPartialEq;
 ///
 /// ### Unused indices:
 /// The following indices aren't used by the Geometry Dash servers: `9`
-#[derive(Debug, VariantPartialEq, Serialize, Deserialize, Clone, Dash)]
+///
+/// ### Unknown indices:
+/// Indices other than the ones mapped to fields below (e.g. `9` or `11`) are not preserved. They
+/// are dropped when parsing (or rejected with the `strict-format` feature enabled), and are thus
+/// not written back when serializing a song.
+#[derive(Debug, DashPartialEq, Serialize, Deserialize, Clone, Dash)]
 pub struct NewgroundsSong<'a> {
     /// The newgrounds id of this [`NewgroundsSong`]
     #[dash(index = 1)]
@@ -25,8 +36,14 @@ pub struct NewgroundsSong<'a> {
     #[dash(index = 2)]
     pub name: Cow<'a, str>,
 
+    /// The newgrounds id of the artist of this [`NewgroundsSong`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `3`. Older JSON representations of songs stored it in an
+    /// `index_3` field, which is still accepted when deserializing.
     #[dash(index = 3)]
-    pub index_3: u64,
+    #[serde(alias = "index_3")]
+    pub artist_id: u64,
 
     /// The artist of this [`NewgroundsSong`]
     #[dash(index = 4)]
@@ -36,14 +53,36 @@ pub struct NewgroundsSong<'a> {
     #[dash(index = 5)]
     pub filesize: f64,
 
+    /// The id of the YouTube video of this [`NewgroundsSong`], if any
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `6`, and is empty for most songs. Older JSON representations
+    /// of songs stored it in an `index_6` field, which is still accepted when deserializing.
     #[dash(index = 6)]
-    pub index_6: Option<Cow<'a, str>>,
+    #[serde(alias = "index_6")]
+    pub youtube_video_id: Option<Cow<'a, str>>,
 
+    /// The id of the YouTube channel of the artist of this [`NewgroundsSong`], if any
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `7`, and is empty for most songs. Older JSON representations
+    /// of songs stored it in an `index_7` field, which is still accepted when deserializing.
     #[dash(index = 7)]
-    pub index_7: Option<Cow<'a, str>>,
+    #[serde(alias = "index_7")]
+    pub youtube_channel_id: Option<Cow<'a, str>>,
 
+    /// Whether the artist of this [`NewgroundsSong`] is verified (scouted) on newgrounds, which is
+    /// required for their songs to be usable in Geometry Dash
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `8`. Older JSON representations of songs stored the raw
+    /// value in an `index_8` field, which is still accepted when deserializing.
+    ///
+    /// Besides `0` and `1`, the servers sometimes send an empty string here, which is read as
+    /// `false`. When serializing, unverified artists are always written as `0`.
     #[dash(index = 8)]
-    pub index_8: Cow<'a, str>,
+    #[serde(alias = "index_8", deserialize_with = "crate::util::serde_adapters::lenient_bool")]
+    pub is_verified: bool,
 
     /// The direct `audio.ngfiles.com` download link for this [`NewgroundsSong`]
     #[serde(borrow)]
@@ -57,19 +96,81 @@ impl<'de> GJFormat<'de> for NewgroundsSong<'de> {
 }
 
 impl<'a> NewgroundsSong<'a> {
+    /// Converts this [`NewgroundsSong`] into one that owns all its data, so that it no longer
+    /// borrows from the response it was parsed from
+    ///
+    /// This processes the song's download link, failing if it is not properly percent-encoded
     pub fn into_owned(self) -> Result<NewgroundsSong<'static>, ProcessError> {
         Ok(NewgroundsSong {
             song_id: self.song_id,
             name: Cow::Owned(self.name.into_owned()),
-            index_3: self.index_3,
+            artist_id: self.artist_id,
             artist: Cow::Owned(self.artist.into_owned()),
             filesize: self.filesize,
-            index_6: self.index_6.map(|cow| Cow::Owned(cow.into_owned())),
-            index_7: self.index_7.map(|cow| Cow::Owned(cow.into_owned())),
-            index_8: Cow::Owned(self.index_8.into_owned()),
+            youtube_video_id: self.youtube_video_id.map(|cow| Cow::Owned(cow.into_owned())),
+            youtube_channel_id: self.youtube_channel_id.map(|cow| Cow::Owned(cow.into_owned())),
+            is_verified: self.is_verified,
             link: Thunk::Processed(Cow::Owned(self.link.into_processed()?.into_owned())),
         })
     }
+
+    /// Processes the download link of this [`NewgroundsSong`] and checks that it points to one of
+    /// the [`KNOWN_SONG_HOSTS`]
+    ///
+    /// ## GD Internals:
+    /// For songs that have been removed from newgrounds, the servers sometimes send an empty link,
+    /// or `-1`/`-2` in place of one. These are reported as [`SongLinkError::Removed`].
+    pub fn direct_download_url(&self) -> Result<Cow<'_, str>, SongLinkError> {
+        let link = match self.link.as_processed()? {
+            Cow::Borrowed(link) => Cow::Borrowed(&**link),
+            Cow::Owned(link) => link,
+        };
+
+        if matches!(link.trim(), "" | "-1" | "-2") {
+            return Err(SongLinkError::Removed);
+        }
+
+        let without_scheme = link
+            .strip_prefix("https://")
+            .or_else(|| link.strip_prefix("http://"))
+            .unwrap_or(&link);
+        let host = without_scheme.split(['/', '?', ':']).next().unwrap_or_default();
+
+        if !KNOWN_SONG_HOSTS.iter().any(|known| known.eq_ignore_ascii_case(host)) {
+            return Err(SongLinkError::UnknownHost(host.to_string()));
+        }
+
+        Ok(link)
+    }
+}
+
+/// Hosts that [`NewgroundsSong`] download links are known to point to, see
+/// [`NewgroundsSong::direct_download_url`]
+///
+/// ## GD Internals:
+/// Most links point to newgrounds' audio CDN. Songs added to the song library in update 2.2 are
+/// hosted on RobTop's own CDN instead.
+pub const KNOWN_SONG_HOSTS: &[&str] = &[
+    "audio.ngfiles.com",
+    "audio-download.ngfiles.com",
+    "www.newgrounds.com",
+    "geometrydashfiles.b-cdn.net",
+];
+
+/// Errors that can occur when retrieving the download link of a [`NewgroundsSong`]
+#[derive(Debug, Error)]
+pub enum SongLinkError {
+    /// The link is not properly percent-encoded
+    #[error("{0}")]
+    Process(#[from] ProcessError),
+
+    /// The song has been removed, and the servers sent a placeholder instead of a link
+    #[error("song has been removed and cannot be downloaded")]
+    Removed,
+
+    /// The link points to a host not contained in [`KNOWN_SONG_HOSTS`]
+    #[error("song link points to unknown host {0}")]
+    UnknownHost(String),
 }
 
 /// Struct representing Geometry Dash's main songs.
@@ -104,7 +205,9 @@ impl MainSong {
     }
 }
 
-/// All current [`MainSong`]s, as of Geometry Dash 2.1
+/// All current [`MainSong`]s, as of Geometry Dash 2.2
+///
+/// To get the main songs available in some other version, use [`main_songs`].
 pub const MAIN_SONGS: [MainSong; 22] = [
     MainSong::new(0, "Stereo Madness", "ForeverBound"),
     MainSong::new(1, "Back on Track", "DJVI"),
@@ -127,9 +230,55 @@ pub const MAIN_SONGS: [MainSong; 22] = [
     MainSong::new(18, "Geometrical Dominator", "Waterflame"),
     MainSong::new(19, "Deadlocked", "F-777"),
     MainSong::new(20, "Fingerdash", "MDK"),
-    MainSong::new(21, "Geomtry Dash", "MDK"),
+    MainSong::new(21, "Dash", "MDK"),
+];
+
+/// The game versions in which the [`MAIN_SONGS`] were introduced, by their ID
+///
+/// ## GD Internals:
+/// Each main song was introduced together with the main level using it. Update 1.0 shipped with
+/// the first seven levels, and every update since added between one and three more, except for
+/// update 2.2, which only added "Dash". Versions are given in their
+/// [canonical](GameVersion::canonical) form, meaning the songs from update 1.0 are listed as
+/// [`GameVersion::Unknown`].
+pub const MAIN_SONG_VERSIONS: &[(u8, GameVersion)] = &[
+    (0, GameVersion::Unknown),
+    (1, GameVersion::Unknown),
+    (2, GameVersion::Unknown),
+    (3, GameVersion::Unknown),
+    (4, GameVersion::Unknown),
+    (5, GameVersion::Unknown),
+    (6, GameVersion::Unknown),
+    (7, GameVersion::Version { major: 1, minor: 1 }),
+    (8, GameVersion::Version { major: 1, minor: 2 }),
+    (9, GameVersion::Version { major: 1, minor: 3 }),
+    (10, GameVersion::Version { major: 1, minor: 4 }),
+    (11, GameVersion::Version { major: 1, minor: 5 }),
+    (12, GameVersion::Version { major: 0, minor: 7 }),
+    (13, GameVersion::Version { major: 1, minor: 7 }),
+    (14, GameVersion::Version { major: 1, minor: 8 }),
+    (15, GameVersion::Version { major: 1, minor: 9 }),
+    (16, GameVersion::Version { major: 2, minor: 0 }),
+    (17, GameVersion::Version { major: 2, minor: 0 }),
+    (18, GameVersion::Version { major: 2, minor: 1 }),
+    (19, GameVersion::Version { major: 2, minor: 1 }),
+    (20, GameVersion::Version { major: 2, minor: 1 }),
+    (21, GameVersion::Version { major: 2, minor: 2 }),
 ];
 
+/// The [`MainSong`]s available in the given game version, according to [`MAIN_SONG_VERSIONS`]
+///
+/// For [`GameVersion::Unknown`], this is the seven songs the game originally shipped with.
+pub fn main_songs(version: GameVersion) -> &'static [MainSong] {
+    // Main songs are numbered in the order in which they were introduced
+    let available = MAIN_SONG_VERSIONS
+        .iter()
+        .take_while(|(_, introduced)| *introduced <= version)
+        .count();
+
+    &MAIN_SONGS[..available]
+}
+
 /// Placeholder value for unknown [`MainSong`]s
 ///
 /// When resolving a ['MainSong'] by its ID, but you pass a wrong ID, or
@@ -140,8 +289,111 @@ pub const UNKNOWN: MainSong = MainSong::new(
     "Please either update to the newest version, or bug stadust about adding the new songs",
 );
 
+impl MainSong {
+    /// Returns an iterator over all [`MAIN_SONGS`]
+    pub fn iter() -> impl Iterator<Item = MainSong> {
+        MAIN_SONGS.iter().copied()
+    }
+
+    /// Looks up the main song with the given name
+    ///
+    /// The comparison ignores case, whitespace and punctuation, so `"cant let go"` finds "Can't
+    /// Let Go". For inexact matches, use [`MainSong::fuzzy_find`].
+    pub fn by_name(name: &str) -> Option<MainSong> {
+        let name = normalize_song_name(name);
+
+        MainSong::iter().find(|song| normalize_song_name(song.name) == name)
+    }
+
+    /// Finds the main song whose name best matches the given query, tolerating typos
+    ///
+    /// Exact matches (as by [`MainSong::by_name`]) are preferred, followed by songs whose name starts
+    /// with the query, e.g. `"electro"` finds "Electroman ADventures". Otherwise, the song with the
+    /// closest name is returned, as long as no more than a third of the query's characters have to
+    /// be changed to get to it. If multiple songs match equally well, the one introduced first is
+    /// returned.
+    ///
+    /// ```
+    /// # use dash_rs::model::song::MainSong;
+    /// assert_eq!(MainSong::fuzzy_find("polargiest").unwrap().main_song_id, 2);
+    /// assert_eq!(MainSong::fuzzy_find("time"), MainSong::by_name("Time Machine"));
+    /// assert_eq!(MainSong::fuzzy_find("nine circles"), None);
+    /// ```
+    pub fn fuzzy_find(query: &str) -> Option<MainSong> {
+        let query = normalize_song_name(query);
+
+        if query.is_empty() {
+            return None;
+        }
+
+        if let Some(song) = MainSong::iter().find(|song| normalize_song_name(song.name) == query) {
+            return Some(song);
+        }
+
+        if let Some(song) = MainSong::iter().find(|song| normalize_song_name(song.name).starts_with(&query)) {
+            return Some(song);
+        }
+
+        MainSong::iter()
+            .map(|song| (edit_distance(&normalize_song_name(song.name), &query), song))
+            .filter(|(distance, _)| *distance * 3 <= query.len())
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, song)| song)
+    }
+
+    /// The game version this main song was introduced in, according to [`MAIN_SONG_VERSIONS`].
+    /// [`None`] for [`UNKNOWN`]
+    pub fn introduced_in(self) -> Option<GameVersion> {
+        MAIN_SONG_VERSIONS
+            .iter()
+            .find(|(main_song_id, _)| *main_song_id == self.main_song_id)
+            .map(|(_, version)| *version)
+    }
+}
+
+/// Lowercases the given song name and strips everything that is not alphanumeric
+fn normalize_song_name(name: &str) -> Vec<u8> {
+    name.bytes()
+        .filter(u8::is_ascii_alphanumeric)
+        .map(|byte| byte.to_ascii_lowercase())
+        .collect()
+}
+
+/// The Levenshtein distance between `a` and `b`, i.e. the number of single byte insertions,
+/// deletions and substitutions needed to transform one into the other
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = Vec::with_capacity(b.len() + 1);
+
+    for (i, &byte_a) in a.iter().enumerate() {
+        current.clear();
+        current.push(i + 1);
+
+        for (j, &byte_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(byte_a != byte_b);
+
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+impl NewgroundsSong<'_> {
+    /// Returns a compact, human-readable one-line summary of this song, e.g. for use in chat
+    /// messages
+    ///
+    /// The summary contains the song's name, artist, ID and filesize, for example `At the Speed of
+    /// Light by Dimrain47 (467339, 9.56 MB)`.
+    pub fn summary(&self) -> String {
+        format!("{} by {} ({}, {:.2} MB)", self.name, self.artist, self.song_id, self.filesize)
+    }
+}
+
 impl Display for NewgroundsSong<'_> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "NewgroundsSong({}, {} by {})", self.song_id, self.name, self.artist)
     }
 }
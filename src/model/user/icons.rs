@@ -0,0 +1,197 @@
+//! Module for locating the sprites of a user's icons in the game's assets
+//!
+//! Geometry Dash stores each icon in its own sprite sheet, made up of multiple frames that are
+//! layered on top of each other and tinted with the player's colors. An [`IconDescriptor`]
+//! describes which frames make up an icon and how to color them, following the game's asset
+//! naming.
+//!
+//! ## GD Internals:
+//! The sprite sheets are located in the game's `Resources/icons` directory and named after the
+//! icon (e.g. `player_01`), with the `-hd` and `-uhd` suffixes for the higher resolution variants.
+//! Each sheet consists of a `.png` and a `.plist` file, the latter containing the positions of
+//! the frames listed in the descriptor.
+
+use crate::model::user::{
+    profile::{IconSlot, Profile},
+    searched::SearchedUser,
+    Color, IconType,
+};
+use alloc::{format, string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+/// The role of a single frame of an icon, which determines how it is colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LayerRole {
+    /// The main part of the icon, tinted with the primary color
+    Primary,
+
+    /// The secondary part of the icon, tinted with the secondary color
+    Secondary,
+
+    /// The glow outline, drawn behind all other layers and tinted with the secondary color. Only
+    /// drawn if the player has glow enabled
+    Glow,
+
+    /// Details drawn on top of the icon without any tint
+    ///
+    /// This layer is only described for icon types whose sprite sheets can contain such a frame
+    /// (see [`IconDescriptor::new`]). Even then, only some icons of these types have it, so
+    /// renderers should skip it if the frame is missing from the sheet.
+    Extra,
+
+    /// The dome of a UFO, drawn without any tint
+    Dome,
+}
+
+/// A single frame of an icon
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IconLayer {
+    /// The name of the frame in the icon's sprite sheet, e.g. `player_01_2_001.png`
+    pub frame: String,
+
+    /// What this frame is used for
+    pub role: LayerRole,
+
+    /// The color this frame is tinted with, or [`None`] if it is drawn as-is
+    pub color: Option<Color>,
+
+    /// For icons made up of multiple parts (robots and spiders), the 1-based index of the part
+    /// this frame belongs to
+    pub part: Option<u8>,
+}
+
+/// Description of the sprites making up a player's icon
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IconDescriptor {
+    /// The kind of icon described
+    pub slot: IconSlot,
+
+    /// The 1-based index of the icon
+    pub index: u16,
+
+    /// The name of the sprite sheet containing the icon's frames, e.g. `player_01`
+    pub sheet: String,
+
+    /// The frames making up the icon, in the order in which they are drawn. Layers with the
+    /// [`LayerRole::Glow`] role are only included if glow is enabled.
+    pub layers: Vec<IconLayer>,
+}
+
+impl IconDescriptor {
+    /// Describes the icon with the given index, as displayed with the given colors
+    ///
+    /// An index of `0` is treated the same as `1`, as the game does. Returns [`None`] for
+    /// [`IconSlot::DeathEffect`], as death effects are not icons.
+    ///
+    /// Balls, swings and jetpacks never have [`LayerRole::Extra`] frames, so no such layer is
+    /// described for them.
+    pub fn new(slot: IconSlot, index: u16, primary: Color, secondary: Color, glow: bool) -> Option<IconDescriptor> {
+        let prefix = match slot {
+            IconSlot::Cube => "player",
+            IconSlot::Ship => "ship",
+            IconSlot::Ball => "player_ball",
+            IconSlot::Ufo => "bird",
+            IconSlot::Wave => "dart",
+            IconSlot::Robot => "robot",
+            IconSlot::Spider => "spider",
+            IconSlot::Swing => "swing",
+            IconSlot::Jetpack => "jetpack",
+            IconSlot::DeathEffect => return None,
+        };
+        let has_extra = !matches!(slot, IconSlot::Ball | IconSlot::Swing | IconSlot::Jetpack);
+        let index = index.max(1);
+        let sheet = format!("{}_{:02}", prefix, index);
+
+        // Robots and spiders are animated, and thus split into multiple parts that are moved
+        // independently. The descriptor only covers the parts of the idle pose.
+        let parts: &[Option<u8>] = match slot {
+            IconSlot::Robot | IconSlot::Spider => &[Some(1), Some(2), Some(3), Some(4)],
+            _ => &[None],
+        };
+
+        let mut layers = Vec::new();
+
+        for &part in parts {
+            let base = match part {
+                Some(part) => format!("{}_{:02}", sheet, part),
+                None => sheet.clone(),
+            };
+            let mut layer = |suffix: &str, role: LayerRole, color: Option<Color>| {
+                layers.push(IconLayer {
+                    frame: format!("{}{}_001.png", base, suffix),
+                    role,
+                    color,
+                    part,
+                })
+            };
+
+            if glow {
+                layer("_glow", LayerRole::Glow, Some(secondary));
+            }
+            layer("_2", LayerRole::Secondary, Some(secondary));
+            if slot == IconSlot::Ufo {
+                layer("_3", LayerRole::Dome, None);
+            }
+            layer("", LayerRole::Primary, Some(primary));
+            if has_extra {
+                layer("_extra", LayerRole::Extra, None);
+            }
+        }
+
+        Some(IconDescriptor {
+            slot,
+            index,
+            sheet,
+            layers,
+        })
+    }
+}
+
+impl IconType {
+    /// The [`IconSlot`] of a [`Profile`] holding icons of this type, or [`None`] for
+    /// [`IconType::Unknown`]
+    pub fn slot(self) -> Option<IconSlot> {
+        match self {
+            IconType::Cube => Some(IconSlot::Cube),
+            IconType::Ship => Some(IconSlot::Ship),
+            IconType::Ball => Some(IconSlot::Ball),
+            IconType::Ufo => Some(IconSlot::Ufo),
+            IconType::Wave => Some(IconSlot::Wave),
+            IconType::Robot => Some(IconSlot::Robot),
+            IconType::Spider => Some(IconSlot::Spider),
+            IconType::Unknown(_) => None,
+        }
+    }
+}
+
+impl Profile<'_> {
+    /// Describes the icon this [`Profile`] has equipped in the given slot, see
+    /// [`IconDescriptor::new`]
+    pub fn icon(&self, slot: IconSlot) -> Option<IconDescriptor> {
+        let index = match slot {
+            IconSlot::Cube => self.cube_index,
+            IconSlot::Ship => self.ship_index.into(),
+            IconSlot::Ball => self.ball_index.into(),
+            IconSlot::Ufo => self.ufo_index.into(),
+            IconSlot::Wave => self.wave_index.into(),
+            IconSlot::Robot => self.robot_index.into(),
+            IconSlot::Spider => self.spider_index.into(),
+            IconSlot::Swing => self.swing_index.into(),
+            IconSlot::Jetpack => self.jetpack_index.into(),
+            IconSlot::DeathEffect => return None,
+        };
+
+        IconDescriptor::new(slot, index, self.primary_color, self.secondary_color, self.has_glow)
+    }
+}
+
+impl SearchedUser<'_> {
+    /// Describes the icon displayed next to this [`SearchedUser`], see [`IconDescriptor::new`]
+    ///
+    /// Returns [`None`] if the [`IconType`] is not known to dash-rs.
+    pub fn icon(&self) -> Option<IconDescriptor> {
+        let slot = self.icon_type.slot()?;
+
+        IconDescriptor::new(slot, self.icon_index, self.primary_color, self.secondary_color, self.has_glow)
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+pub mod icons;
 pub mod profile;
 pub mod searched;
 
@@ -45,6 +46,54 @@ impl From<u8> for ModLevel {
 
 crate::into_conversion!(ModLevel, u8);
 
+/// Enum representing the friendship between the user making a request and the user whose
+/// [`Profile`](profile::Profile) was requested
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub enum FriendState {
+    /// The users are not friends, and there are no pending friend requests between them. This is
+    /// also the state reported for requests made without authentication
+    None,
+
+    /// The users are friends
+    Friends,
+
+    /// The requested user has sent a friend request to the requesting user
+    IncomingRequest,
+
+    /// The requesting user has sent a friend request to the requested user
+    OutgoingRequest,
+
+    /// Unknown or invalid value. This variant will be constructed if robtop ever adds more
+    /// friendship states and will hold the internal game value associated with the new state
+    Unknown(u8),
+}
+
+impl From<FriendState> for u8 {
+    fn from(state: FriendState) -> u8 {
+        match state {
+            FriendState::None => 0,
+            FriendState::Friends => 1,
+            FriendState::IncomingRequest => 3,
+            FriendState::OutgoingRequest => 4,
+            FriendState::Unknown(inner) => inner,
+        }
+    }
+}
+
+impl From<u8> for FriendState {
+    fn from(i: u8) -> Self {
+        match i {
+            0 => FriendState::None,
+            1 => FriendState::Friends,
+            3 => FriendState::IncomingRequest,
+            4 => FriendState::OutgoingRequest,
+            i => FriendState::Unknown(i),
+        }
+    }
+}
+
+crate::into_conversion!(FriendState, u8);
+
 /// The type of icon displayed next a user's comment of next to their search result
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IconType {
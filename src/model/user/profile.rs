@@ -1,33 +1,32 @@
 use crate::{
-    model::user::{Color, ModLevel},
+    model::user::{Color, FriendState, ModLevel},
+    serde::{ProcessError, Thunk, ThunkProcessor},
     GJFormat,
 };
-use dash_rs_derive::Dash;
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+use dash_rs_derive::{Dash, DashPartialEq};
+use itoa::Buffer;
 use serde::{Deserialize, Serialize};
-use std::{
-    borrow::Cow,
-    fmt::{Display, Formatter},
-};
-use variant_partial_eq::VariantPartialEq;
 
 crate::dash_rs_newtype!(Youtube);
 crate::dash_rs_newtype!(Twitch);
 crate::dash_rs_newtype!(Twitter);
 
 impl Display for Youtube<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "https://www.youtube.com/channel/{}", self.0)
     }
 }
 
 impl Display for Twitch<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "https://www.twitch.tv/{}", self.0)
     }
 }
 
 impl Display for Twitter<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "https://www.twitter.com/{}", self.0)
     }
 }
@@ -40,8 +39,8 @@ impl Display for Twitter<'_> {
 ///
 /// ### Unused Indices
 /// The following indices aren't used by the Geometry Dash servers: `5`, `6`, `7`, `9`, `12`, `14`,
-/// `15`, `27`, `32`, `33`, `34`, `35`, `36`, `37`, `38`, `39`, `40`, `41`, `42`, `47`
-#[derive(Debug, Eq, VariantPartialEq, Clone, Serialize, Deserialize, Dash)]
+/// `15`, `27`, `32`, `33`, `34`, `35`, `36`, `37`, `41`, `42`, `47`
+#[derive(Debug, Eq, DashPartialEq, Clone, Serialize, Deserialize, Dash)]
 pub struct Profile<'a> {
     /// The [`Profile`]'s name
     #[dash(index = 1)]
@@ -152,27 +151,33 @@ pub struct Profile<'a> {
     #[dash(index = 30)]
     pub global_rank: Option<u32>,
 
-    // TODO: figure this value out
+    /// The friendship between the user who requested this [`Profile`] and its owner
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `31`. It is always `0` unless the profile was requested with
+    /// authentication. `2` is not used, `3` indicates an incoming and `4` an outgoing friend request
     #[dash(index = 31)]
-    pub index_31: Cow<'a, str>,
+    pub friend_state: FriendState,
 
-    // TODO: figure this value out
+    /// The number of unread messages. Only provided if this [`Profile`] was requested by its owner
     #[dash(index = 38)]
     #[dash(default)]
     #[dash(skip_serializing_if = "Option::is_none")]
-    pub index_38: Option<Cow<'a, str>>,
+    pub new_messages: Option<u32>,
 
-    // TODO: figure this value out
+    /// The number of unanswered friend requests. Only provided if this [`Profile`] was requested by
+    /// its owner
     #[dash(index = 39)]
     #[dash(default)]
     #[dash(skip_serializing_if = "Option::is_none")]
-    pub index_39: Option<Cow<'a, str>>,
+    pub new_friend_requests: Option<u32>,
 
-    // TODO: figure this value out
+    /// The number of friends added since the friends list was last viewed. Only provided if this
+    /// [`Profile`] was requested by its owner
     #[dash(index = 40)]
     #[dash(default)]
     #[dash(skip_serializing_if = "Option::is_none")]
-    pub index_40: Option<Cow<'a, str>>,
+    pub new_friends: Option<u32>,
 
     /// The 1-based index of the spider this [`Profile`] currently uses. Indexing of icons starts at
     /// the top left corner and then goes left-to-right and top-to-bottom
@@ -226,9 +231,516 @@ pub struct Profile<'a> {
     /// at the top left corner and then goes left-to-right and top-to-bottom
     #[dash(index = 54)]
     pub jetpack_index: u8,
+
+    /// The number of demons of each difficulty this [`Profile`] has beaten. Added in update 2.2
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `55`, as a comma separated list of counts. It is missing
+    /// from the responses of servers that have not been updated to 2.2.
+    #[dash(index = 55)]
+    #[dash(compare_with = "crate::util::option_variant_eq")]
+    pub demon_breakdown: Option<Thunk<'a, DemonBreakdown>>,
+
+    /// The number of non-demon classic levels of each difficulty this [`Profile`] has beaten.
+    /// Added in update 2.2
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `56`, as a comma separated list of counts
+    #[dash(index = 56)]
+    #[dash(compare_with = "crate::util::option_variant_eq")]
+    pub classic_completions: Option<Thunk<'a, ClassicCompletions>>,
+
+    /// The number of non-demon platformer levels of each difficulty this [`Profile`] has beaten.
+    /// Added in update 2.2
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `57`, as a comma separated list of counts
+    #[dash(index = 57)]
+    #[dash(compare_with = "crate::util::option_variant_eq")]
+    pub platformer_completions: Option<Thunk<'a, PlatformerCompletions>>,
 }
 
 impl<'de> GJFormat<'de> for Profile<'de> {
     const DELIMITER: &'static str = ":";
     const MAP_LIKE: bool = true;
 }
+
+impl Profile<'_> {
+    /// This [`Profile`]'s progress in classic levels, combining [`Profile::stars`] with the
+    /// classic parts of [`Profile::demon_breakdown`] and [`Profile::classic_completions`]
+    pub fn classic_stats(&self) -> Result<ModeStats, ProcessError> {
+        Ok(ModeStats {
+            rewards: self.stars,
+            demons: match self.demon_breakdown {
+                Some(ref breakdown) => Some(breakdown.as_processed()?.classic_total()),
+                None => None,
+            },
+            levels: match self.classic_completions {
+                Some(ref completions) => Some(completions.as_processed()?.total()),
+                None => None,
+            },
+        })
+    }
+
+    /// This [`Profile`]'s progress in platformer levels, combining [`Profile::moons`] with the
+    /// platformer parts of [`Profile::demon_breakdown`] and [`Profile::platformer_completions`]
+    pub fn platformer_stats(&self) -> Result<ModeStats, ProcessError> {
+        Ok(ModeStats {
+            rewards: self.moons,
+            demons: match self.demon_breakdown {
+                Some(ref breakdown) => Some(breakdown.as_processed()?.platformer_total()),
+                None => None,
+            },
+            levels: match self.platformer_completions {
+                Some(ref completions) => Some(completions.as_processed()?.total()),
+                None => None,
+            },
+        })
+    }
+
+    /// Returns a compact, human-readable one-line summary of this [`Profile`], e.g. for use in
+    /// chat messages
+    ///
+    /// The summary contains the user's name, global rank (if ranked) and their most important
+    /// stats, for example `RobTop (#42) | 1234 stars, 56 moons, 78 demons, 9 creator points`.
+    pub fn summary(&self) -> String {
+        let mut summary = String::from(&*self.name);
+
+        if let Some(rank) = self.global_rank {
+            summary.push_str(&format!(" (#{})", rank));
+        }
+
+        summary.push_str(&format!(
+            " | {} stars, {} moons, {} demons, {} creator points",
+            self.stars, self.moons, self.demons, self.creator_points
+        ));
+
+        summary
+    }
+
+    /// Computes the changes from this [`Profile`] to `other`, which is assumed to be a more recent
+    /// snapshot of the same user's profile.
+    ///
+    /// Numeric stats are reported as signed differences, so a user losing stars (e.g. after a
+    /// leaderboard ban) shows up as a negative value
+    pub fn diff(&self, other: &Profile<'_>) -> ProfileDelta {
+        let mut icon_changes = Vec::new();
+
+        let slots = [
+            (IconSlot::Cube, self.cube_index, other.cube_index),
+            (IconSlot::Ship, self.ship_index.into(), other.ship_index.into()),
+            (IconSlot::Ball, self.ball_index.into(), other.ball_index.into()),
+            (IconSlot::Ufo, self.ufo_index.into(), other.ufo_index.into()),
+            (IconSlot::Wave, self.wave_index.into(), other.wave_index.into()),
+            (IconSlot::Robot, self.robot_index.into(), other.robot_index.into()),
+            (IconSlot::Spider, self.spider_index.into(), other.spider_index.into()),
+            (IconSlot::Swing, self.swing_index.into(), other.swing_index.into()),
+            (IconSlot::Jetpack, self.jetpack_index.into(), other.jetpack_index.into()),
+            (
+                IconSlot::DeathEffect,
+                self.death_effect_index.into(),
+                other.death_effect_index.into(),
+            ),
+        ];
+
+        for &(slot, old, new) in slots.iter() {
+            if old != new {
+                icon_changes.push(IconChange::Icon { slot, old, new });
+            }
+        }
+
+        if self.primary_color != other.primary_color {
+            icon_changes.push(IconChange::PrimaryColor {
+                old: self.primary_color,
+                new: other.primary_color,
+            });
+        }
+
+        if self.secondary_color != other.secondary_color {
+            icon_changes.push(IconChange::SecondaryColor {
+                old: self.secondary_color,
+                new: other.secondary_color,
+            });
+        }
+
+        if self.has_glow != other.has_glow {
+            icon_changes.push(IconChange::Glow(other.has_glow));
+        }
+
+        ProfileDelta {
+            stars: i64::from(other.stars) - i64::from(self.stars),
+            moons: i64::from(other.moons) - i64::from(self.moons),
+            demons: i32::from(other.demons) - i32::from(self.demons),
+            diamonds: i32::from(other.diamonds) - i32::from(self.diamonds),
+            secret_coins: i16::from(other.secret_coins) - i16::from(self.secret_coins),
+            user_coins: i32::from(other.user_coins) - i32::from(self.user_coins),
+            creator_points: i32::from(other.creator_points) - i32::from(self.creator_points),
+            global_rank: if self.global_rank != other.global_rank {
+                Some((self.global_rank, other.global_rank))
+            } else {
+                None
+            },
+            icon_changes,
+        }
+    }
+}
+
+/// The number of demons of each difficulty a [`Profile`] has beaten
+///
+/// ## GD Internals:
+/// The counts are provided as a comma separated list in the order `easy`, `medium`, `hard`,
+/// `insane`, `extreme`, followed by the five platformer demon counts in the same order, and finally
+/// the `weekly` and `gauntlet` counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemonBreakdown {
+    /// The number of classic easy demons beaten
+    pub easy: u16,
+
+    /// The number of classic medium demons beaten
+    pub medium: u16,
+
+    /// The number of classic hard demons beaten
+    pub hard: u16,
+
+    /// The number of classic insane demons beaten
+    pub insane: u16,
+
+    /// The number of classic extreme demons beaten
+    pub extreme: u16,
+
+    /// The number of weekly demons beaten
+    pub weekly: u16,
+
+    /// The number of gauntlet demons beaten
+    pub gauntlet: u16,
+
+    /// The number of platformer demons beaten, from easy to extreme
+    pub platformer_tiers: [u16; 5],
+}
+
+impl DemonBreakdown {
+    /// The number of classic demons beaten, summed over all difficulties
+    pub fn classic_total(&self) -> u32 {
+        [self.easy, self.medium, self.hard, self.insane, self.extreme]
+            .iter()
+            .map(|&count| u32::from(count))
+            .sum()
+    }
+
+    /// The number of platformer demons beaten, summed over all difficulties
+    pub fn platformer_total(&self) -> u32 {
+        self.platformer_tiers.iter().map(|&count| u32::from(count)).sum()
+    }
+}
+
+impl ThunkProcessor for DemonBreakdown {
+    type Error = ProcessError;
+    type Output<'a> = DemonBreakdown;
+
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
+        let [easy, medium, hard, insane, extreme, platformer_easy, platformer_medium, platformer_hard, platformer_insane, platformer_extreme, weekly, gauntlet] =
+            parse_counts(&unprocessed)?;
+
+        Ok(DemonBreakdown {
+            easy,
+            medium,
+            hard,
+            insane,
+            extreme,
+            weekly,
+            gauntlet,
+            platformer_tiers: [
+                platformer_easy,
+                platformer_medium,
+                platformer_hard,
+                platformer_insane,
+                platformer_extreme,
+            ],
+        })
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        let [platformer_easy, platformer_medium, platformer_hard, platformer_insane, platformer_extreme] = processed.platformer_tiers;
+
+        Ok(Cow::Owned(format_counts(&[
+            processed.easy,
+            processed.medium,
+            processed.hard,
+            processed.insane,
+            processed.extreme,
+            platformer_easy,
+            platformer_medium,
+            platformer_hard,
+            platformer_insane,
+            platformer_extreme,
+            processed.weekly,
+            processed.gauntlet,
+        ])))
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+/// The number of non-demon classic levels of each difficulty a [`Profile`] has beaten
+///
+/// ## GD Internals:
+/// The counts are provided as a comma separated list in the order of the fields of this struct
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassicCompletions {
+    /// The number of auto levels beaten
+    pub auto: u16,
+
+    /// The number of easy levels beaten
+    pub easy: u16,
+
+    /// The number of normal levels beaten
+    pub normal: u16,
+
+    /// The number of hard levels beaten
+    pub hard: u16,
+
+    /// The number of harder levels beaten
+    pub harder: u16,
+
+    /// The number of insane levels beaten
+    pub insane: u16,
+
+    /// The number of daily levels beaten
+    pub daily: u16,
+
+    /// The number of gauntlet levels beaten
+    pub gauntlet: u16,
+}
+
+impl ClassicCompletions {
+    /// The number of rated levels beaten, summed over all difficulties
+    pub fn total(&self) -> u32 {
+        [self.auto, self.easy, self.normal, self.hard, self.harder, self.insane]
+            .iter()
+            .map(|&count| u32::from(count))
+            .sum()
+    }
+}
+
+impl ThunkProcessor for ClassicCompletions {
+    type Error = ProcessError;
+    type Output<'a> = ClassicCompletions;
+
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
+        let [auto, easy, normal, hard, harder, insane, daily, gauntlet] = parse_counts(&unprocessed)?;
+
+        Ok(ClassicCompletions {
+            auto,
+            easy,
+            normal,
+            hard,
+            harder,
+            insane,
+            daily,
+            gauntlet,
+        })
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        Ok(Cow::Owned(format_counts(&[
+            processed.auto,
+            processed.easy,
+            processed.normal,
+            processed.hard,
+            processed.harder,
+            processed.insane,
+            processed.daily,
+            processed.gauntlet,
+        ])))
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+/// The number of non-demon platformer levels of each difficulty a [`Profile`] has beaten
+///
+/// ## GD Internals:
+/// The counts are provided as a comma separated list in the order of the fields of this struct
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformerCompletions {
+    /// The number of auto levels beaten
+    pub auto: u16,
+
+    /// The number of easy levels beaten
+    pub easy: u16,
+
+    /// The number of normal levels beaten
+    pub normal: u16,
+
+    /// The number of hard levels beaten
+    pub hard: u16,
+
+    /// The number of harder levels beaten
+    pub harder: u16,
+
+    /// The number of insane levels beaten
+    pub insane: u16,
+}
+
+impl PlatformerCompletions {
+    /// The number of rated levels beaten, summed over all difficulties
+    pub fn total(&self) -> u32 {
+        [self.auto, self.easy, self.normal, self.hard, self.harder, self.insane]
+            .iter()
+            .map(|&count| u32::from(count))
+            .sum()
+    }
+}
+
+impl ThunkProcessor for PlatformerCompletions {
+    type Error = ProcessError;
+    type Output<'a> = PlatformerCompletions;
+
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
+        let [auto, easy, normal, hard, harder, insane] = parse_counts(&unprocessed)?;
+
+        Ok(PlatformerCompletions {
+            auto,
+            easy,
+            normal,
+            hard,
+            harder,
+            insane,
+        })
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        Ok(Cow::Owned(format_counts(&[
+            processed.auto,
+            processed.easy,
+            processed.normal,
+            processed.hard,
+            processed.harder,
+            processed.insane,
+        ])))
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+/// A [`Profile`]'s progress in one of the game modes, see [`Profile::classic_stats`] and
+/// [`Profile::platformer_stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModeStats {
+    /// The number of stars (classic) or moons (platformer) collected
+    pub rewards: u32,
+
+    /// The number of demons beaten in this mode. [`None`] if the servers did not provide a
+    /// [`DemonBreakdown`]
+    pub demons: Option<u32>,
+
+    /// The number of rated non-demon levels beaten in this mode. [`None`] if the servers did not
+    /// provide the completion counts
+    pub levels: Option<u32>,
+}
+
+/// Parses a comma separated list of exactly `N` counts
+fn parse_counts<const N: usize>(unprocessed: &str) -> Result<[u16; N], ProcessError> {
+    let mut counts = [0; N];
+    let mut split = unprocessed.split(',');
+
+    for count in counts.iter_mut() {
+        *count = split.next().ok_or(ProcessError::IncorrectLength { expected: N })?.parse()?;
+    }
+
+    if split.next().is_some() {
+        return Err(ProcessError::IncorrectLength { expected: N });
+    }
+
+    Ok(counts)
+}
+
+fn format_counts(counts: &[u16]) -> String {
+    let mut buffer = Buffer::new();
+    let mut unprocessed = String::new();
+
+    for (idx, count) in counts.iter().enumerate() {
+        if idx != 0 {
+            unprocessed.push(',');
+        }
+        unprocessed.push_str(buffer.format(*count));
+    }
+
+    unprocessed
+}
+
+/// The changes between two snapshots of a [`Profile`], as computed by [`Profile::diff`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileDelta {
+    /// The change in [`Profile::stars`]
+    pub stars: i64,
+
+    /// The change in [`Profile::moons`]
+    pub moons: i64,
+
+    /// The change in [`Profile::demons`]
+    pub demons: i32,
+
+    /// The change in [`Profile::diamonds`]
+    pub diamonds: i32,
+
+    /// The change in [`Profile::secret_coins`]
+    pub secret_coins: i16,
+
+    /// The change in [`Profile::user_coins`]
+    pub user_coins: i32,
+
+    /// The change in [`Profile::creator_points`]
+    pub creator_points: i32,
+
+    /// The old and new [`Profile::global_rank`], if it changed
+    pub global_rank: Option<(Option<u32>, Option<u32>)>,
+
+    /// The changes made to the profile's icon set, in the order of [`IconSlot`]s, followed by
+    /// color and glow changes
+    pub icon_changes: Vec<IconChange>,
+}
+
+impl ProfileDelta {
+    /// Returns `true` iff nothing changed between the two compared snapshots
+    pub fn is_empty(&self) -> bool {
+        *self == ProfileDelta::default()
+    }
+}
+
+/// The different icons a [`Profile`] has equipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IconSlot {
+    Cube,
+    Ship,
+    Ball,
+    Ufo,
+    Wave,
+    Robot,
+    Spider,
+    Swing,
+    Jetpack,
+    DeathEffect,
+}
+
+/// A single change to the icon set of a [`Profile`], as reported in a [`ProfileDelta`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IconChange {
+    /// The icon in the given slot was changed from the `old` to the `new` 1-based index
+    Icon { slot: IconSlot, old: u16, new: u16 },
+
+    /// The primary color was changed
+    PrimaryColor { old: Color, new: Color },
+
+    /// The secondary color was changed
+    SecondaryColor { old: Color, new: Color },
+
+    /// Glow was turned on (`true`) or off (`false`)
+    Glow(bool),
+}
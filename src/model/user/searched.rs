@@ -2,17 +2,16 @@ use crate::{
     model::user::{Color, IconType},
     GJFormat,
 };
-use dash_rs_derive::Dash;
+use alloc::borrow::Cow;
+use dash_rs_derive::{Dash, DashPartialEq};
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
-use variant_partial_eq::VariantPartialEq;
 
 /// Struct modelling the partial user data returned by the `getGJUsers` endpoint.
 ///
 /// Note that no field `diamonds` exists here. This is consistent with Geometry Dash's behavior, as
 /// the GD server exhibit a bug where they do not provide diamonds information, although the client
 /// has the UI for it.
-#[derive(Debug, Clone, VariantPartialEq, Eq, Serialize, Deserialize, Dash)]
+#[derive(Debug, Clone, DashPartialEq, Eq, Serialize, Deserialize, Dash)]
 pub struct SearchedUser<'a> {
     /// This [`SearchedUser`]'s name
     #[dash(index = 1)]
@@ -69,7 +68,7 @@ pub struct SearchedUser<'a> {
 
     /// Values indicating whether this [`SearchedUser`] has glow activated or not.
     #[dash(index = 15)]
-    #[dash(serialize_with = "crate::util::true_to_two")]
+    #[dash(serialize_with = "crate::util::serde_adapters::true_to_two")]
     pub has_glow: bool,
 
     /// The [`SearchedUser`]'s unique account ID
@@ -0,0 +1,388 @@
+//! Module containing request structs for backing up an account's save data to the cloud and
+//! restoring it from there
+//!
+//! ## GD Internals:
+//! Cloud saves are not handled by the main boomlings servers. Instead, the game first asks
+//! `getAccountURL.php` for the server responsible for the account (see [`AccountUrlRequest`]), and
+//! sends the actual backup or sync request to the `database/` directory of the returned host (see
+//! [`account_base_url`]). These requests use a different secret than all other requests (see
+//! [`GD_22_ACCOUNTS`]).
+
+use crate::{
+    model::{
+        level::{self, LevelProcessError},
+        GameVersion,
+    },
+    request::{endpoints, Authentication, BaseRequest, GdRequest, RequestValidationError, GD_22, REQUEST_BASE_URL},
+    response::{self, Parsed, ResponseError},
+    util,
+};
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+};
+use core::fmt::{Display, Formatter};
+use serde::Serialize;
+
+pub const ACCOUNT_URL_ENDPOINT: &str = endpoints::ACCOUNT_URL.name;
+pub const BACKUP_ACCOUNT_ENDPOINT: &str = endpoints::BACKUP_ACCOUNT.name;
+pub const SYNC_ACCOUNT_ENDPOINT: &str = endpoints::SYNC_ACCOUNT.name;
+
+/// A `BaseRequest` instance that has all its fields set to the same values a Geometry Dash 2.2
+/// client would use for requests to the account servers, e.g. [`BackupAccountRequest`] and
+/// [`SyncAccountRequest`]
+pub const GD_22_ACCOUNTS: BaseRequest = BaseRequest::new(
+    GameVersion::Version { major: 2, minor: 2 },
+    GameVersion::Version { major: 3, minor: 8 },
+    "Wmfv3899gc9",
+);
+
+/// Turns the host returned in response to an [`AccountUrlRequest`] into the base URL to which
+/// [`BackupAccountRequest`]s and [`SyncAccountRequest`]s are sent (the equivalent of
+/// [`REQUEST_BASE_URL`] for cloud saves)
+pub fn account_base_url(host: &str) -> String {
+    format!("{}/database/", host.trim_end_matches('/'))
+}
+
+/// The operation for which the account server is being looked up
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(into = "u8")]
+pub enum AccountUrlType {
+    /// Looking up the server to upload save data to
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the numeric value `1` in the boomlings API
+    Backup,
+
+    /// Looking up the server to download save data from
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the numeric value `2` in the boomlings API
+    Sync,
+}
+
+impl From<AccountUrlType> for u8 {
+    fn from(url_type: AccountUrlType) -> Self {
+        match url_type {
+            AccountUrlType::Backup => 1,
+            AccountUrlType::Sync => 2,
+        }
+    }
+}
+
+/// Struct modelled after a request to `getAccountURL.php`.
+///
+/// The response is the host of the server holding the account's cloud save, which needs to be
+/// passed to [`account_base_url`] before sending the actual backup or sync request
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct AccountUrlRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The id of the account whose server to look up
+    ///
+    /// ## GD Internals:
+    /// This field is called `accountID` in the boomlings API
+    #[serde(rename = "accountID")]
+    pub account_id: u64,
+
+    /// Whether the save data is about to be backed up or synced
+    ///
+    /// ## GD Internals:
+    /// This field is called `type` in the boomlings API
+    #[serde(rename = "type")]
+    pub url_type: AccountUrlType,
+}
+
+impl<'a> AccountUrlRequest<'a> {
+    const_setter!(url_type: AccountUrlType);
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, ACCOUNT_URL_ENDPOINT)
+    }
+
+    pub const fn new(account_id: u64, url_type: AccountUrlType) -> Self {
+        Self::with_base(GD_22, account_id, url_type)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, account_id: u64, url_type: AccountUrlType) -> Self {
+        AccountUrlRequest {
+            base,
+            account_id,
+            url_type,
+        }
+    }
+}
+
+impl Display for AccountUrlRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for AccountUrlRequest<'_> {
+    type Response<'a> = &'a str;
+
+    const ENDPOINT: &'static str = ACCOUNT_URL_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<&str, ResponseError<'_>> {
+        response::parse_get_account_url_response(response)
+    }
+}
+
+/// The save data of an account, as it is transferred to and from the account servers
+///
+/// Both parts are kept in their encoded form, and only decoded on demand.
+///
+/// ## GD Internals:
+/// The save data consists of the contents of the two local save files, `CCGameManager.dat` and
+/// `CCLocalLevels.dat`, joined by a `;`. Each is a property list, gzip compressed and urlsafe base64
+/// encoded the same way level data is (but without the XOR applied to the files on disk, see
+/// [`SAVE_FILE`](crate::util::xor_keys::SAVE_FILE)).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SaveData<'a> {
+    /// The encoded contents of `CCGameManager.dat`, holding the account's progress, stats and
+    /// settings
+    pub game_manager: Cow<'a, str>,
+
+    /// The encoded contents of `CCLocalLevels.dat`, holding the account's created levels
+    pub local_levels: Cow<'a, str>,
+}
+
+impl SaveData<'_> {
+    /// Encodes the given plain property lists into save data
+    pub fn encode(game_manager: &str, local_levels: &str) -> Result<SaveData<'static>, LevelProcessError> {
+        Ok(SaveData {
+            game_manager: Cow::Owned(encode_save_file(game_manager)?),
+            local_levels: Cow::Owned(encode_save_file(local_levels)?),
+        })
+    }
+
+    /// Decodes the property list contained in `CCGameManager.dat`
+    pub fn decode_game_manager(&self) -> Result<String, LevelProcessError> {
+        decode_save_file(&self.game_manager)
+    }
+
+    /// Decodes the property list contained in `CCLocalLevels.dat`
+    pub fn decode_local_levels(&self) -> Result<String, LevelProcessError> {
+        decode_save_file(&self.local_levels)
+    }
+}
+
+impl Display for SaveData<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{};{}", self.game_manager, self.local_levels)
+    }
+}
+
+fn encode_save_file(plist: &str) -> Result<String, LevelProcessError> {
+    Ok(util::base64_encode(&level::compress(plist.as_bytes())?))
+}
+
+fn decode_save_file(encoded: &str) -> Result<String, LevelProcessError> {
+    level::decompress(&util::base64_decode(encoded)?)
+}
+
+/// Struct modelled after a request to `backupGJAccountNew.php`, uploading save data to the cloud
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BackupAccountRequest<'a> {
+    /// The base request data. Needs to use the account server's secret, see [`GD_22_ACCOUNTS`]
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account whose data to back up
+    pub authentication: Authentication<'a>,
+
+    /// The data to back up
+    ///
+    /// ## GD Internals:
+    /// This field is called `saveData` in the boomlings API
+    pub save_data: SaveData<'a>,
+}
+
+/// The form body of a [`BackupAccountRequest`]
+#[derive(Debug, Serialize)]
+pub struct BackupAccountForm<'a> {
+    base: BaseRequest<'a>,
+    authentication: Authentication<'a>,
+    #[serde(rename = "saveData")]
+    save_data: String,
+}
+
+impl<'a> BackupAccountRequest<'a> {
+    /// The full URL of this request's endpoint on the account server with the given base URL (see
+    /// [`account_base_url`])
+    pub fn to_url_on(&self, base_url: &str) -> String {
+        format!("{}{}", base_url, BACKUP_ACCOUNT_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>, save_data: SaveData<'a>) -> Self {
+        Self::with_base(GD_22_ACCOUNTS, authentication, save_data)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>, save_data: SaveData<'a>) -> Self {
+        BackupAccountRequest {
+            base,
+            authentication,
+            save_data,
+        }
+    }
+
+    fn form(&self) -> BackupAccountForm<'_> {
+        BackupAccountForm {
+            base: self.base,
+            authentication: self.authentication,
+            save_data: self.save_data.to_string(),
+        }
+    }
+}
+
+impl Display for BackupAccountRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self.form()))
+    }
+}
+
+impl GdRequest for BackupAccountRequest<'_> {
+    type Response<'a> = ();
+
+    const ENDPOINT: &'static str = BACKUP_ACCOUNT_ENDPOINT;
+
+    type Form<'s> = BackupAccountForm<'s> where Self: 's;
+
+    fn prepare(&self) -> Result<BackupAccountForm<'_>, RequestValidationError> {
+        Ok(self.form())
+    }
+
+    fn parse_response(response: &str) -> Result<(), ResponseError<'_>> {
+        response::parse_backup_gj_account_response(response)
+    }
+}
+
+/// Struct modelled after a request to `syncGJAccountNew.php`, downloading save data from the cloud
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct SyncAccountRequest<'a> {
+    /// The base request data. Needs to use the account server's secret, see [`GD_22_ACCOUNTS`]
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account whose data to download
+    pub authentication: Authentication<'a>,
+}
+
+impl<'a> SyncAccountRequest<'a> {
+    /// The full URL of this request's endpoint on the account server with the given base URL (see
+    /// [`account_base_url`])
+    pub fn to_url_on(&self, base_url: &str) -> String {
+        format!("{}{}", base_url, SYNC_ACCOUNT_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>) -> Self {
+        Self::with_base(GD_22_ACCOUNTS, authentication)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>) -> Self {
+        SyncAccountRequest { base, authentication }
+    }
+}
+
+impl Display for SyncAccountRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+/// The response to a [`SyncAccountRequest`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyncedAccount<'a> {
+    /// The account's save data
+    pub save_data: SaveData<'a>,
+
+    /// The version of the game that made the backup
+    pub game_version: GameVersion,
+
+    /// The binary version of the game that made the backup
+    pub binary_version: GameVersion,
+}
+
+impl GdRequest for SyncAccountRequest<'_> {
+    type Response<'a> = SyncedAccount<'a>;
+
+    const ENDPOINT: &'static str = SYNC_ACCOUNT_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<SyncedAccount<'_>, ResponseError<'_>> {
+        response::parse_sync_gj_account_response(response)
+    }
+
+    /// Unlike other responses, the sections of the sync response are separated by `;`
+    fn parse_response_with_raw(response: &str) -> Result<Parsed<'_, SyncedAccount<'_>>, ResponseError<'_>> {
+        Ok(Parsed {
+            value: response::parse_sync_gj_account_response(response)?,
+            raw_sections: response.split(';').collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::request::account::SaveData;
+    use crate::request::{
+        account::{account_base_url, AccountUrlRequest, AccountUrlType, SyncAccountRequest},
+        Authentication,
+    };
+    #[cfg(feature = "std")]
+    use alloc::borrow::Cow;
+
+    #[test]
+    fn serialize_account_url() {
+        let request = AccountUrlRequest::new(1710032, AccountUrlType::Sync);
+
+        assert_eq!(
+            super::super::to_string(request),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&type=2"
+        );
+    }
+
+    #[test]
+    fn serialize_sync_account() {
+        let authentication = Authentication::new(1710032, "0123456789abcdef0123456789abcdef01234567");
+        let request = SyncAccountRequest::new(authentication);
+
+        assert_eq!(
+            request.to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfv3899gc9&accountID=1710032&gjp2=0123456789abcdef0123456789abcdef01234567"
+        );
+        assert_eq!(
+            request.to_url_on(&account_base_url("http://www.robtopgames.org/")),
+            "http://www.robtopgames.org/database/accounts/syncGJAccountNew.php"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn save_data_roundtrip() {
+        let save_data = SaveData::encode("<plist><dict /></plist>", "<plist />").unwrap();
+
+        assert_eq!(save_data.decode_game_manager().unwrap(), "<plist><dict /></plist>");
+        assert_eq!(save_data.decode_local_levels().unwrap(), "<plist />");
+
+        let borrowed = SaveData {
+            game_manager: Cow::Borrowed("a"),
+            local_levels: Cow::Borrowed("b"),
+        };
+
+        assert_eq!(borrowed.to_string(), "a;b");
+    }
+}
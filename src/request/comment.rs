@@ -1,13 +1,24 @@
-//! Module containing request structs for retrieving profile/level comments
+//! Module containing request structs for retrieving profile/level comments, and for managing the
+//! comments on one's own profile
 
 use crate::{
-    model::level::Level,
-    request::{BaseRequest, GD_22, REQUEST_BASE_URL},
+    model::{
+        comment::{level::LevelComment, profile::ProfileComment},
+        level::Level,
+    },
+    request::{endpoints, Authentication, BaseRequest, GdRequest, Paginated, RequestValidationError, GD_22, REQUEST_BASE_URL},
+    response::{self, PageInfo, ResponseError},
+    util,
 };
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
 use serde::Serialize;
 
-pub const LEVEL_COMMENTS_ENDPOINT: &str = "getGJComments21.php";
-pub const PROFILE_COMMENT_ENDPOINT: &str = "getGJAccountComments20.php";
+pub const LEVEL_COMMENTS_ENDPOINT: &str = endpoints::LEVEL_COMMENTS.name;
+pub const PROFILE_COMMENT_ENDPOINT: &str = endpoints::PROFILE_COMMENTS.name;
+pub const UPLOAD_COMMENT_ENDPOINT: &str = endpoints::UPLOAD_COMMENT.name;
+pub const UPLOAD_ACCOUNT_COMMENT_ENDPOINT: &str = endpoints::UPLOAD_ACCOUNT_COMMENT.name;
+pub const DELETE_ACCOUNT_COMMENT_ENDPOINT: &str = endpoints::DELETE_ACCOUNT_COMMENT.name;
 
 /// The different orderings that can be requested for level comments
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
@@ -113,9 +124,41 @@ impl<'a> LevelCommentsRequest<'a> {
     }
 }
 
-impl ToString for LevelCommentsRequest<'_> {
-    fn to_string(&self) -> String {
-        super::to_string(self)
+impl Display for LevelCommentsRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for LevelCommentsRequest<'_> {
+    type Response<'a> = (Vec<LevelComment<'a>>, PageInfo);
+
+    const ENDPOINT: &'static str = LEVEL_COMMENTS_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<(Vec<LevelComment<'_>>, PageInfo), ResponseError<'_>> {
+        response::parse_get_gj_comments_response(response)
+    }
+}
+
+impl Paginated for LevelCommentsRequest<'_> {
+    const PAGE_INFO_SECTION: usize = 1;
+
+    fn current_page(&self) -> u32 {
+        self.page
+    }
+
+    fn set_page(&mut self, page: u32) {
+        self.page = page
+    }
+
+    fn page_size(&self) -> u32 {
+        self.limit
     }
 }
 
@@ -181,15 +224,207 @@ impl<'a> ProfileCommentsRequest<'a> {
     }
 }
 
-impl ToString for ProfileCommentsRequest<'_> {
-    fn to_string(&self) -> String {
-        super::to_string(self)
+impl Display for ProfileCommentsRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for ProfileCommentsRequest<'_> {
+    type Response<'a> = Vec<ProfileComment<'a>>;
+
+    const ENDPOINT: &'static str = PROFILE_COMMENT_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<Vec<ProfileComment<'_>>, ResponseError<'_>> {
+        response::parse_get_gj_acccount_comments_response(response)
+    }
+}
+
+impl Paginated for ProfileCommentsRequest<'_> {
+    const PAGE_INFO_SECTION: usize = 1;
+
+    fn current_page(&self) -> u32 {
+        self.page
+    }
+
+    fn set_page(&mut self, page: u32) {
+        self.page = page
+    }
+
+    // The servers always return pages of 10 objects for this endpoint
+    fn page_size(&self) -> u32 {
+        10
+    }
+}
+
+/// Request to post a comment on one's own profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UploadAccountCommentRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account posting the comment
+    pub authentication: Authentication<'a>,
+
+    /// The name of the user posting the comment
+    ///
+    /// ## GD Internals:
+    /// This field is called `userName` in the boomlings API. It is only used to compute the `chk`
+    /// of the request
+    pub user_name: &'a str,
+
+    /// The content of the comment to post
+    ///
+    /// ## GD Internals:
+    /// This field is called `comment` in the boomlings API, and is sent urlsafe base64 encoded
+    pub comment: &'a str,
+}
+
+/// The form body of an [`UploadAccountCommentRequest`], containing the derived fields
+#[derive(Debug, Serialize)]
+pub struct UploadAccountCommentForm<'a> {
+    base: BaseRequest<'a>,
+    authentication: Authentication<'a>,
+    #[serde(rename = "userName")]
+    user_name: &'a str,
+    comment: String,
+    #[serde(rename = "cType")]
+    comment_type: u8,
+    chk: String,
+}
+
+impl<'a> UploadAccountCommentRequest<'a> {
+    const_setter!(comment: &'a str);
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, UPLOAD_ACCOUNT_COMMENT_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>, user_name: &'a str, comment: &'a str) -> Self {
+        Self::with_base(GD_22, authentication, user_name, comment)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>, user_name: &'a str, comment: &'a str) -> Self {
+        UploadAccountCommentRequest {
+            base,
+            authentication,
+            user_name,
+            comment,
+        }
+    }
+}
+
+impl UploadAccountCommentRequest<'_> {
+    fn form(&self) -> UploadAccountCommentForm<'_> {
+        let comment = util::base64_encode(self.comment);
+
+        // The comment type (`1` for profile comments) is part of the chk's input
+        let chk = util::chk::generate_chk(&[self.user_name, &comment, "1"], util::salts::COMMENT, util::xor_keys::COMMENT_CHK);
+
+        UploadAccountCommentForm {
+            base: self.base,
+            authentication: self.authentication,
+            user_name: self.user_name,
+            comment,
+            comment_type: 1,
+            chk,
+        }
+    }
+}
+
+impl Display for UploadAccountCommentRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self.form()))
+    }
+}
+
+impl GdRequest for UploadAccountCommentRequest<'_> {
+    type Response<'a> = u64;
+
+    const ENDPOINT: &'static str = UPLOAD_ACCOUNT_COMMENT_ENDPOINT;
+
+    type Form<'s> = UploadAccountCommentForm<'s> where Self: 's;
+
+    fn prepare(&self) -> Result<UploadAccountCommentForm<'_>, RequestValidationError> {
+        Ok(self.form())
+    }
+
+    fn parse_response(response: &str) -> Result<u64, ResponseError<'_>> {
+        response::parse_upload_gj_acc_comment_response(response)
+    }
+}
+
+/// Request to delete a comment from one's own profile
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct DeleteAccountCommentRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account owning the comment
+    pub authentication: Authentication<'a>,
+
+    /// The id of the comment to delete
+    ///
+    /// ## GD Internals:
+    /// This field is called `commentID` in the boomlings API
+    #[serde(rename = "commentID")]
+    pub comment_id: u64,
+}
+
+impl<'a> DeleteAccountCommentRequest<'a> {
+    const_setter!(comment_id: u64);
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, DELETE_ACCOUNT_COMMENT_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>, comment_id: u64) -> Self {
+        Self::with_base(GD_22, authentication, comment_id)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>, comment_id: u64) -> Self {
+        DeleteAccountCommentRequest {
+            base,
+            authentication,
+            comment_id,
+        }
+    }
+}
+
+impl Display for DeleteAccountCommentRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for DeleteAccountCommentRequest<'_> {
+    type Response<'a> = ();
+
+    const ENDPOINT: &'static str = DELETE_ACCOUNT_COMMENT_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<(), ResponseError<'_>> {
+        response::parse_delete_gj_acc_comment_response(response)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::request::comment::{LevelCommentsRequest, ProfileCommentsRequest};
+    use crate::request::{
+        comment::{DeleteAccountCommentRequest, LevelCommentsRequest, ProfileCommentsRequest, UploadAccountCommentRequest},
+        Authentication,
+    };
 
     #[test]
     fn serialize_level_comments() {
@@ -220,4 +455,32 @@ mod tests {
             "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&total=0&page=2&accountID=1710032"
         );
     }
+
+    #[test]
+    fn serialize_upload_account_comment() {
+        let authentication = Authentication::new(1710032, "0123456789abcdef0123456789abcdef01234567");
+        let request = UploadAccountCommentRequest::new(authentication, "stadust", "Hello World");
+
+        assert_eq!(
+            request.to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&gjp2=0123456789abcdef0123456789abcdef01234567&\
+             userName=stadust&comment=SGVsbG8gV29ybGQ=&cType=1&chk=AAsACFVXCFEAVwMODA8BCw8AWlcKCFYKVQRfAV1QUV0MXgFRWABcCQ=="
+        );
+    }
+
+    #[test]
+    fn hash_password() {
+        assert_eq!(Authentication::hash_password("hunter2"), "dcc58eb53f0c1604c19240834e7fd12e73f9a9c7");
+    }
+
+    #[test]
+    fn serialize_delete_account_comment() {
+        let authentication = Authentication::new(1710032, "0123456789abcdef0123456789abcdef01234567");
+        let request = DeleteAccountCommentRequest::new(authentication, 1234);
+
+        assert_eq!(
+            request.to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&gjp2=0123456789abcdef0123456789abcdef01234567&commentID=1234"
+        );
+    }
 }
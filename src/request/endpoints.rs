@@ -0,0 +1,248 @@
+//! Module containing a registry of the known endpoints of the boomlings API
+//!
+//! Each endpoint is described by an [`EndpointInfo`], available both as a constant and via the
+//! [`Endpoint`] enum. The latter allows routing requests generically, e.g. in private server
+//! implementations, by looking up the endpoint a request was made to via [`Endpoint::from_name`].
+//!
+//! The `ENDPOINT` constants of the request structs in this crate are defined in terms of this
+//! registry.
+//!
+//! ## GD Internals:
+//! RobTop versions endpoints by appending a suffix to their name whenever their parameters or
+//! responses change in incompatible ways (e.g. `getGJComments21.php`). The versions listed here
+//! are the versions of the game client that first used the given endpoint name. Newer clients
+//! keep using the same name until the endpoint changes again.
+
+use crate::model::GameVersion;
+
+/// The HTTP method with which requests to an endpoint are made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// The request parameters are sent in the URL's query string
+    Get,
+
+    /// The request parameters are sent as an url-encoded form body. All known endpoints of the
+    /// boomlings API use this method
+    Post,
+}
+
+/// Whether requests to an endpoint need to be made on behalf of an account, see
+/// [`Authentication`](super::Authentication)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthRequirement {
+    /// The endpoint does not take any credentials
+    None,
+
+    /// The endpoint optionally takes credentials, and provides additional information if given
+    /// some
+    Optional,
+
+    /// The endpoint only succeeds if valid credentials are given
+    Required,
+}
+
+/// Description of an endpoint of the boomlings API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EndpointInfo {
+    /// The path of the endpoint, relative to the server's base URL (e.g.
+    /// [`REQUEST_BASE_URL`](super::REQUEST_BASE_URL) for the boomlings servers)
+    pub name: &'static str,
+
+    /// The HTTP method requests to this endpoint are made with
+    pub method: Method,
+
+    /// The version of the first game client using this endpoint
+    pub min_version: GameVersion,
+
+    /// Whether requests to this endpoint need to be authenticated
+    pub auth: AuthRequirement,
+}
+
+macro_rules! endpoints {
+    ($($(#[$attr:meta])* $variant:ident => $constant:ident($name:literal, $method:ident, ($major:literal, $minor:literal), $auth:ident),)*) => {
+        $(
+            $(#[$attr])*
+            pub const $constant: EndpointInfo = EndpointInfo {
+                name: $name,
+                method: Method::$method,
+                min_version: GameVersion::Version { major: $major, minor: $minor },
+                auth: AuthRequirement::$auth,
+            };
+        )*
+
+        /// Enum containing all endpoints in this registry
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Endpoint {
+            $(
+                $(#[$attr])*
+                $variant,
+            )*
+        }
+
+        impl Endpoint {
+            /// All endpoints in this registry
+            pub const ALL: &'static [Endpoint] = &[$(Endpoint::$variant),*];
+
+            /// The description of this endpoint
+            pub const fn info(self) -> &'static EndpointInfo {
+                match self {
+                    $(Endpoint::$variant => &$constant,)*
+                }
+            }
+
+            /// Looks up the endpoint with the given name (e.g. `"getGJLevels21.php"`)
+            pub fn from_name(name: &str) -> Option<Endpoint> {
+                match name {
+                    $($name => Some(Endpoint::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+endpoints! {
+    /// Downloading a level, see [`LevelRequest`](super::level::LevelRequest)
+    DownloadLevel => DOWNLOAD_LEVEL("downloadGJLevel22.php", Post, (2, 1), Optional),
+
+    /// Retrieving lists of levels, see [`LevelsRequest`](super::level::LevelsRequest)
+    SearchLevels => SEARCH_LEVELS("getGJLevels21.php", Post, (2, 1), Optional),
+
+    /// Uploading a level, see [`LevelUploadRequest`](super::level::LevelUploadRequest)
+    UploadLevel => UPLOAD_LEVEL("uploadGJLevel21.php", Post, (2, 1), Required),
+
+    /// Deleting one of the own levels, see [`DeleteLevelRequest`](super::level::DeleteLevelRequest)
+    DeleteLevel => DELETE_LEVEL("deleteGJLevelUser20.php", Post, (2, 0), Required),
+
+    /// Updating the description of one of the own levels, see
+    /// [`UpdateDescriptionRequest`](super::level::UpdateDescriptionRequest)
+    UpdateDescription => UPDATE_DESCRIPTION("updateGJDesc20.php", Post, (2, 0), Required),
+
+    /// Retrieving information about the current daily level or weekly demon
+    DailyLevel => DAILY_LEVEL("getGJDailyLevel.php", Post, (2, 0), None),
+
+    /// Retrieving the list of map packs
+    MapPacks => MAP_PACKS("getGJMapPacks21.php", Post, (2, 1), None),
+
+    /// Retrieving the list of gauntlets
+    Gauntlets => GAUNTLETS("getGJGauntlets21.php", Post, (2, 1), None),
+
+    /// Retrieving information about a newgrounds song
+    SongInfo => SONG_INFO("getGJSongInfo.php", Post, (2, 0), None),
+
+    /// Retrieving the comments on a level, see
+    /// [`LevelCommentsRequest`](super::comment::LevelCommentsRequest)
+    LevelComments => LEVEL_COMMENTS("getGJComments21.php", Post, (2, 1), None),
+
+    /// Retrieving the comments on a profile, see
+    /// [`ProfileCommentsRequest`](super::comment::ProfileCommentsRequest)
+    ProfileComments => PROFILE_COMMENTS("getGJAccountComments20.php", Post, (2, 0), None),
+
+    /// Posting a comment on a level
+    UploadComment => UPLOAD_COMMENT("uploadGJComment21.php", Post, (2, 1), Required),
+
+    /// Posting a comment on the own profile, see
+    /// [`UploadAccountCommentRequest`](super::comment::UploadAccountCommentRequest)
+    UploadAccountComment => UPLOAD_ACCOUNT_COMMENT("uploadGJAccComment20.php", Post, (2, 0), Required),
+
+    /// Deleting a comment from the own profile, see
+    /// [`DeleteAccountCommentRequest`](super::comment::DeleteAccountCommentRequest)
+    DeleteAccountComment => DELETE_ACCOUNT_COMMENT("deleteGJAccComment20.php", Post, (2, 0), Required),
+
+    /// Retrieving a user's profile, see [`UserRequest`](super::user::UserRequest)
+    UserInfo => USER_INFO("getGJUserInfo20.php", Post, (2, 0), Optional),
+
+    /// Searching for users, see [`UserSearchRequest`](super::user::UserSearchRequest)
+    SearchUsers => SEARCH_USERS("getGJUsers20.php", Post, (2, 0), None),
+
+    /// Retrieving the leaderboards
+    Scores => SCORES("getGJScores20.php", Post, (2, 0), Optional),
+
+    /// Liking or disliking a level, comment or list
+    LikeItem => LIKE_ITEM("likeGJItem211.php", Post, (2, 1), Optional),
+
+    /// Requesting moderator access for an account, see
+    /// [`RequestModRequest`](super::moderator::RequestModRequest)
+    RequestModAccess => REQUEST_MOD_ACCESS("requestUserAccess.php", Post, (2, 0), Required),
+
+    /// Suggesting a star rating for a level, as a moderator, see
+    /// [`SuggestStarsRequest`](super::moderator::SuggestStarsRequest)
+    SuggestStars => SUGGEST_STARS("suggestGJStars20.php", Post, (2, 0), Required),
+
+    /// Looking up the server holding an account's cloud save, see
+    /// [`AccountUrlRequest`](super::account::AccountUrlRequest)
+    AccountUrl => ACCOUNT_URL("getAccountURL.php", Post, (2, 0), None),
+
+    /// Uploading an account's save data to the cloud, see
+    /// [`BackupAccountRequest`](super::account::BackupAccountRequest)
+    BackupAccount => BACKUP_ACCOUNT("accounts/backupGJAccountNew.php", Post, (2, 1), Required),
+
+    /// Downloading an account's save data from the cloud, see
+    /// [`SyncAccountRequest`](super::account::SyncAccountRequest)
+    SyncAccount => SYNC_ACCOUNT("accounts/syncGJAccountNew.php", Post, (2, 1), Required),
+
+    /// Logging into an account
+    Login => LOGIN("accounts/loginGJAccount.php", Post, (2, 0), None),
+
+    /// Registering a new account
+    Register => REGISTER("accounts/registerGJAccount.php", Post, (2, 0), None),
+
+    /// Retrieving lists of level lists. Added in update 2.2
+    SearchLists => SEARCH_LISTS("getGJLevelLists.php", Post, (2, 2), Optional),
+
+    /// Uploading a level list. Added in update 2.2
+    UploadList => UPLOAD_LIST("uploadGJLevelList.php", Post, (2, 2), Required),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::request::{
+        account::{AccountUrlRequest, BackupAccountRequest, SyncAccountRequest},
+        comment::{DeleteAccountCommentRequest, LevelCommentsRequest, ProfileCommentsRequest, UploadAccountCommentRequest},
+        endpoints::{AuthRequirement, Endpoint},
+        level::{DeleteLevelRequest, LevelRequest, LevelUploadRequest, LevelsRequest, UpdateDescriptionRequest},
+        moderator::{RequestModRequest, SuggestStarsRequest},
+        user::{UserRequest, UserSearchRequest},
+        GdRequest,
+    };
+
+    #[test]
+    fn lookup_by_name() {
+        for &endpoint in Endpoint::ALL {
+            assert_eq!(Endpoint::from_name(endpoint.info().name), Some(endpoint));
+        }
+
+        assert_eq!(Endpoint::from_name("getGJLevels20.php"), None);
+    }
+
+    #[test]
+    fn requests_are_registered() {
+        let registered = [
+            (LevelRequest::ENDPOINT, AuthRequirement::Optional),
+            (LevelsRequest::ENDPOINT, AuthRequirement::Optional),
+            (LevelUploadRequest::ENDPOINT, AuthRequirement::Required),
+            (DeleteLevelRequest::ENDPOINT, AuthRequirement::Required),
+            (UpdateDescriptionRequest::ENDPOINT, AuthRequirement::Required),
+            (LevelCommentsRequest::ENDPOINT, AuthRequirement::None),
+            (ProfileCommentsRequest::ENDPOINT, AuthRequirement::None),
+            (UploadAccountCommentRequest::ENDPOINT, AuthRequirement::Required),
+            (DeleteAccountCommentRequest::ENDPOINT, AuthRequirement::Required),
+            (UserRequest::ENDPOINT, AuthRequirement::Optional),
+            (UserSearchRequest::ENDPOINT, AuthRequirement::None),
+            (AccountUrlRequest::ENDPOINT, AuthRequirement::None),
+            (BackupAccountRequest::ENDPOINT, AuthRequirement::Required),
+            (SyncAccountRequest::ENDPOINT, AuthRequirement::Required),
+            (RequestModRequest::ENDPOINT, AuthRequirement::Required),
+            (SuggestStarsRequest::ENDPOINT, AuthRequirement::Required),
+        ];
+
+        for (name, auth) in registered {
+            assert_eq!(
+                Endpoint::from_name(name).map(|endpoint| endpoint.info().auth),
+                Some(auth),
+                "{}",
+                name
+            );
+        }
+    }
+}
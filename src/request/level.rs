@@ -1,14 +1,66 @@
 use crate::{
     model::{
-        level::{DemonRating, LevelLength, LevelRating},
+        level::{DemonRating, Description, Level, LevelLength, LevelRating, ListedLevel, Password},
         song::MainSong,
+        GameVersion,
     },
-    request::{BaseRequest, GD_22, REQUEST_BASE_URL},
+    request::{endpoints, Authentication, BaseRequest, GdRequest, Paginated, RequestValidationError, GD_22, GD_WORLD, REQUEST_BASE_URL},
+    response::{self, ResponseError},
+    serde::InternalProxy,
+    util,
+};
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt::{Display, Formatter},
 };
 use serde::{Deserialize, Serialize, Serializer};
 
-pub const DOWNLOAD_LEVEL_ENDPOINT: &str = "downloadGJLevel22.php";
-pub const SEARCH_LEVEL_ENDPOINT: &str = "getGJLevels21.php";
+/// The endpoint for downloading levels, see [`LevelRequest`]
+///
+/// ## GD Internals:
+/// Despite its name, this endpoint is used by both the 2.1 and the 2.2 client. Older versions
+/// used `downloadGJLevel21.php` and below, which private servers might still provide.
+pub const DOWNLOAD_LEVEL_ENDPOINT: &str = endpoints::DOWNLOAD_LEVEL.name;
+
+/// The endpoint for retrieving lists of levels, see [`LevelsRequest`]
+///
+/// ## GD Internals:
+/// This endpoint is used by both the 2.1 and the 2.2 client. The filters added in update 2.2
+/// were added to this endpoint instead of introducing a new one.
+pub const SEARCH_LEVEL_ENDPOINT: &str = endpoints::SEARCH_LEVELS.name;
+
+/// The endpoint for uploading levels, see [`LevelUploadRequest`]
+///
+/// ## GD Internals:
+/// This endpoint is used by both the 2.1 and the 2.2 client.
+pub const UPLOAD_LEVEL_ENDPOINT: &str = endpoints::UPLOAD_LEVEL.name;
+
+/// The endpoint for deleting levels, see [`DeleteLevelRequest`]
+pub const DELETE_LEVEL_ENDPOINT: &str = endpoints::DELETE_LEVEL.name;
+
+/// The endpoint for updating level descriptions, see [`UpdateDescriptionRequest`]
+pub const UPDATE_DESCRIPTION_ENDPOINT: &str = endpoints::UPDATE_DESCRIPTION.name;
+
+/// A `BaseRequest` instance that has all its fields set to the same values a Geometry Dash 2.2
+/// client would use for [`DeleteLevelRequest`]s
+///
+/// ## GD Internals:
+/// Deleting levels uses a different secret than [`GD_22`]. It is not the only request to do so:
+/// requests to the account servers use
+/// [`GD_22_ACCOUNTS`](crate::request::account::GD_22_ACCOUNTS), and moderator actions use
+/// [`GD_22_MOD`](crate::request::GD_22_MOD).
+pub const GD_22_DELETE: BaseRequest = BaseRequest::new(
+    GameVersion::Version { major: 2, minor: 2 },
+    GameVersion::Version { major: 3, minor: 8 },
+    "Wmfv2898gc9",
+);
+
+/// The level id to request for downloading the current daily level
+pub const DAILY_LEVEL_ID: i64 = -1;
+
+/// The level id to request for downloading the current weekly demon
+pub const WEEKLY_LEVEL_ID: i64 = -2;
 
 /// Struct modelled after a request to `downloadGJLevel22.php`.
 ///
@@ -23,10 +75,13 @@ pub struct LevelRequest<'a> {
 
     /// The ID of the level to download
     ///
+    /// Negative values do not refer to actual levels, but select the current daily
+    /// ([`DAILY_LEVEL_ID`]) or weekly ([`WEEKLY_LEVEL_ID`]) level instead.
+    ///
     /// ## GD Internals:
     /// This field is called `levelID` in the boomlings API
     #[serde(rename = "levelID")]
-    pub level_id: u64,
+    pub level_id: i64,
 
     /// Some weird field the Geometry Dash Client sends along
     ///
@@ -42,9 +97,11 @@ pub struct LevelRequest<'a> {
     pub extra: bool,
 }
 
-impl From<u64> for LevelRequest<'_> {
-    fn from(lid: u64) -> Self {
-        LevelRequest::new(lid)
+impl TryFrom<u64> for LevelRequest<'_> {
+    type Error = RequestValidationError;
+
+    fn try_from(lid: u64) -> Result<Self, RequestValidationError> {
+        LevelRequest::try_new(lid)
     }
 }
 
@@ -75,7 +132,46 @@ impl<'a> LevelRequest<'a> {
     /// Uses a default [`BaseRequest`], and sets the
     /// `inc` field to `true` and `extra` to `false`, as are the default
     /// values set the by the Geometry Dash Client
+    ///
+    /// # Panics
+    /// Panics for ids above [`i64::MAX`], which the servers would interpret as one of the negative
+    /// special level ids. Use [`LevelRequest::try_new`] to handle those gracefully.
     pub const fn new(level_id: u64) -> LevelRequest<'static> {
+        assert!(level_id <= i64::MAX as u64, "level id out of range");
+
+        LevelRequest::special(level_id as i64)
+    }
+
+    /// Constructs a new `LevelRequest` to retrieve the level with the given id, like
+    /// [`LevelRequest::new`]
+    ///
+    /// Fails with [`RequestValidationError::InvalidLevelId`] for ids above [`i64::MAX`], which the
+    /// servers would interpret as one of the negative special level ids.
+    pub fn try_new(level_id: u64) -> Result<LevelRequest<'static>, RequestValidationError> {
+        i64::try_from(level_id)
+            .map(LevelRequest::special)
+            .map_err(|_| RequestValidationError::InvalidLevelId(level_id))
+    }
+
+    /// Constructs a new `LevelRequest` to retrieve the current daily level
+    ///
+    /// The response to this request contains additional data, use
+    /// [`parse_download_gj_special_level_response`](crate::response::parse_download_gj_special_level_response)
+    /// to process it.
+    pub const fn daily() -> LevelRequest<'static> {
+        LevelRequest::special(DAILY_LEVEL_ID)
+    }
+
+    /// Constructs a new `LevelRequest` to retrieve the current weekly demon
+    ///
+    /// The response to this request contains additional data, use
+    /// [`parse_download_gj_special_level_response`](crate::response::parse_download_gj_special_level_response)
+    /// to process it.
+    pub const fn weekly() -> LevelRequest<'static> {
+        LevelRequest::special(WEEKLY_LEVEL_ID)
+    }
+
+    const fn special(level_id: i64) -> LevelRequest<'static> {
         LevelRequest {
             base: GD_22,
             level_id,
@@ -89,9 +185,25 @@ impl<'a> LevelRequest<'a> {
     }
 }
 
-impl ToString for LevelRequest<'_> {
-    fn to_string(&self) -> String {
-        super::to_string(self)
+impl Display for LevelRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for LevelRequest<'_> {
+    type Response<'a> = Level<'a>;
+
+    const ENDPOINT: &'static str = DOWNLOAD_LEVEL_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<Level<'_>, ResponseError<'_>> {
+        response::parse_download_gj_level_response(response)
     }
 }
 
@@ -103,8 +215,17 @@ impl ToString for LevelRequest<'_> {
 #[derive(Debug, Clone, Hash, Serialize, Deserialize, Default)]
 pub struct CompletionFilter {
     /// The list of level ids to filter
-    #[serde(rename = "completedLevels", default, skip_serializing_if = "Option::is_none")]
-    // TODO: we have to get this wrapped inside parenthesis somehow
+    ///
+    /// ## GD Internals:
+    /// This field is called `completedLevels` in the boomlings API. The ids are comma separated and
+    /// enclosed in parenthesis, which the request serializer takes care of when serializing, and
+    /// [`parenthesized_id_list`](util::serde_adapters::parenthesized_id_list) when deserializing
+    #[serde(
+        rename = "completedLevels",
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "util::serde_adapters::parenthesized_id_list"
+    )]
     ids: Option<Vec<u64>>,
 
     /// if `true`, only the levels matching the ids in [`ids`](CompletionFilter.ids) will
@@ -112,14 +233,14 @@ pub struct CompletionFilter {
     /// be excluded.
     ///
     /// Mutually exclusive with [`CompletionFilter::exclude_given`]
-    #[serde(rename = "onlyCompleted")]
+    #[serde(rename = "onlyCompleted", deserialize_with = "util::serde_adapters::lenient_bool")]
     only_search_given: bool,
 
     /// if `true`, the levels in [`ids`](CompletionFilter.ids) will be excluded, if `false`, only
     /// the levels matching the ids in [`ids`](CompletionFilter.ids) will be searched.
     ///
     /// Mutually exclusive with [`CompletionFilter::only_search_given`]
-    #[serde(rename = "uncompleted")]
+    #[serde(rename = "uncompleted", deserialize_with = "util::serde_adapters::lenient_bool")]
     exclude_given: bool,
 }
 
@@ -143,6 +264,22 @@ impl CompletionFilter {
             exclude_given: true,
         }
     }
+
+    /// Checks that this filter does not both restrict the search to and exclude its levels, and
+    /// that it contains level ids if either is set
+    pub fn validate(&self) -> Result<(), RequestValidationError> {
+        if self.only_search_given && self.exclude_given {
+            return Err(RequestValidationError::ConflictingCompletionFilter);
+        }
+
+        let has_ids = self.ids.as_ref().is_some_and(|ids| !ids.is_empty());
+
+        if (self.only_search_given || self.exclude_given) && !has_ids {
+            return Err(RequestValidationError::MissingCompletionIds);
+        }
+
+        Ok(())
+    }
 }
 
 /// Struct containing the various search filters provided by the Geometry Dash
@@ -189,6 +326,32 @@ pub struct SearchFilters {
     #[serde(rename = "star")]
     pub rated: bool,
 
+    /// Only retrieve levels that have not been star rated
+    ///
+    /// Mutually exclusive with [`SearchFilters::rated`]
+    ///
+    /// ## GD Internals:
+    /// This field is called `noStar` in the boomlings API and needs to be converted to an integer.
+    /// It is omitted from the request unless set.
+    #[serde(rename = "noStar", default, skip_serializing_if = "is_false")]
+    pub no_star: bool,
+
+    /// Only retrieve legendary levels. Added in update 2.2
+    ///
+    /// ## GD Internals:
+    /// This value needs to be converted to an integer for the boomlings API. It is omitted from the
+    /// request unless set, since older servers do not know about it.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub legendary: bool,
+
+    /// Only retrieve mythic levels. Added in update 2.2
+    ///
+    /// ## GD Internals:
+    /// This value needs to be converted to an integer for the boomlings API. It is omitted from the
+    /// request unless set, since older servers do not know about it.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub mythic: bool,
+
     /// Optionally only retrieve levels that match the given `SongFilter`
     ///
     /// ## GD Internals:
@@ -198,7 +361,8 @@ pub struct SearchFilters {
     /// request. To filter
     /// by a newgrounds
     /// song, set `customSong`
-    /// to `1` and `song` to the newgrounds ID of the custom song.
+    /// to `1` and `song` to the newgrounds ID of the custom song. Songs from the music library
+    /// added in update 2.2 are filtered by the same way, using their library ID.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub song: Option<SongFilter>,
 
@@ -219,6 +383,7 @@ pub struct SearchFilters {
     /// * `completedLevels` is a list of levels ids that have been completed. It needs to be
     ///   provided if, and only if, either `uncompleted` or `onlyCompleted` are set to `1`. The ids
     ///   are comma seperated and enclosed by parenthesis.
+    ///
     /// If no completion filtering is desired, both boolean fields are set to `0` and
     /// `completedLevels` is omitted.
     pub completion: CompletionFilter,
@@ -226,8 +391,42 @@ pub struct SearchFilters {
 
 impl SearchFilters {
     /// Limit search results to star rated levels
+    ///
+    /// Resets the [`SearchFilters::no_star`] filter, as these are mutually exclusive
     pub const fn rated(mut self) -> Self {
         self.rated = true;
+        self.no_star = false;
+        self
+    }
+
+    /// Limit search results to levels that have not been star rated
+    ///
+    /// Resets the [`SearchFilters::rated`] filter, as these are mutually exclusive
+    pub const fn no_star(mut self) -> Self {
+        self.no_star = true;
+        self.rated = false;
+        self
+    }
+
+    /// Stop limiting search results to featured levels
+    ///
+    /// Note that the servers do not support excluding featured levels from the results, only
+    /// including them exclusively. Use [`SearchFilters::no_star`] to only find levels that have
+    /// not been rated at all.
+    pub const fn unfeatured(mut self) -> Self {
+        self.featured = false;
+        self
+    }
+
+    /// Limit search results to legendary levels
+    pub const fn legendary(mut self) -> Self {
+        self.legendary = true;
+        self
+    }
+
+    /// Limit search results to mythic levels
+    pub const fn mythic(mut self) -> Self {
+        self.mythic = true;
         self
     }
 
@@ -276,11 +475,34 @@ impl SearchFilters {
         self
     }
 
+    /// Limit search results to levels with the main song of the given name, as found by
+    /// [`MainSong::by_name`]
+    ///
+    /// Inexact names are rejected, since searching for the wrong song silently returns unrelated
+    /// levels. Use [`MainSong::fuzzy_find`] and [`SearchFilters::main_song`] to match them anyway.
+    pub fn main_song_named(self, name: &str) -> Result<Self, RequestValidationError> {
+        match MainSong::by_name(name) {
+            Some(main_song) => Ok(self.main_song(main_song)),
+            None => Err(RequestValidationError::UnknownMainSong),
+        }
+    }
+
     /// Limit search results to levels that use a custom song matching the given id.
+    ///
+    /// This works for both newgrounds songs and songs from the music library added in update 2.2.
     pub fn custom_song(mut self, song_id: u64) -> Self {
         self.song = Some(SongFilter { song_id, is_custom: true });
         self
     }
+
+    /// Checks that no mutually exclusive filters were set at the same time
+    pub fn validate(&self) -> Result<(), RequestValidationError> {
+        if self.rated && self.no_star {
+            return Err(RequestValidationError::ConflictingStarFilters);
+        }
+
+        self.completion.validate()
+    }
 }
 
 /// Enum containing the various types of [`LevelsRequest`] possible
@@ -288,8 +510,8 @@ impl SearchFilters {
 /// ## GD Internals:
 /// + Unused values: `8`, `9`, `14`
 /// + The values `15` and `17` are only used in Geometry Dash World and are the
-/// same as `0` ([`LevelRequestType::Search`]) and `6` ([`LevelRequestType::Featured`]) respectively
-#[derive(Debug, Copy, Clone, PartialEq, Hash, Serialize, Deserialize, Default)]
+///   same as `0` ([`LevelRequestType::Search`]) and `6` ([`LevelRequestType::Featured`]) respectively
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(from = "i32", into = "i32")]
 pub enum LevelRequestType {
     /// A search request.
@@ -362,7 +584,7 @@ pub enum LevelRequestType {
     /// This variant is represented by the value `11` in requests
     Awarded,
 
-    /// Unknown how this works
+    /// Request to retrieve the levels of the creators listed in [`LevelsRequest::followed`]
     ///
     /// ## GD Internals:
     /// This variant is represented by the value `12` in requests
@@ -374,16 +596,86 @@ pub enum LevelRequestType {
     /// This variant is represented by the value `13` in requests
     Friends,
 
+    /// Request to retrieve the list of most liked levels as shown in GD World
+    ///
+    /// GD World can only browse a subset of online levels. Requests of this type should be made
+    /// with the [`GD_WORLD`] base request.
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `15` in requests.
+    WorldMostLiked,
+
     /// Request to retrieve the levels in the hall of fame
     ///
     /// ## GD Internals:
     /// This variant is represented by the value `16` in requests.
     HallOfFame,
 
+    /// Request to retrieve the list of featured levels as shown in GD World
+    ///
+    /// Requests of this type should be made with the [`GD_WORLD`] base
+    /// request.
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `17` in requests.
+    WorldFeatured,
+
     /// Unknown variant not yet mapped by dash-rs
     Unknown(i32),
 }
 
+/// The game version each known [`LevelRequestType`] was introduced in
+///
+/// The servers reject requests of types newer than the [`BaseRequest::game_version`] they were
+/// made with. [`LevelsRequest::validate`] checks requests against this table.
+///
+/// ## GD Internals:
+/// The GD World specific types `15` and `17` were introduced alongside GD World, during update 2.1.
+/// Since GD World makes its requests with the same [`BaseRequest`] as the main game (see
+/// [`GD_WORLD`]), the servers cannot tell whether such requests come from GD World. The types that
+/// already existed in update 1.0 are listed as [`GameVersion::Unknown`], as the game only started
+/// tracking its version in update 1.6.
+pub const LEVEL_REQUEST_TYPE_VERSIONS: &[(LevelRequestType, GameVersion)] = &[
+    (LevelRequestType::Search, GameVersion::Unknown),
+    (LevelRequestType::MostDownloaded, GameVersion::Unknown),
+    (LevelRequestType::MostLiked, GameVersion::Unknown),
+    (LevelRequestType::Trending, GameVersion::Unknown),
+    (LevelRequestType::Recent, GameVersion::Unknown),
+    (LevelRequestType::User, GameVersion::Unknown),
+    (LevelRequestType::Featured, GameVersion::Unknown),
+    (LevelRequestType::Magic, GameVersion::Unknown),
+    (LevelRequestType::MapPack, GameVersion::Version { major: 1, minor: 9 }),
+    (LevelRequestType::Awarded, GameVersion::Version { major: 1, minor: 9 }),
+    (LevelRequestType::Followed, GameVersion::Version { major: 2, minor: 0 }),
+    (LevelRequestType::Friends, GameVersion::Version { major: 2, minor: 0 }),
+    (LevelRequestType::WorldMostLiked, GameVersion::Version { major: 2, minor: 1 }),
+    (LevelRequestType::HallOfFame, GameVersion::Version { major: 2, minor: 1 }),
+    (LevelRequestType::WorldFeatured, GameVersion::Version { major: 2, minor: 1 }),
+];
+
+impl LevelRequestType {
+    /// Whether this type of request is only made by GD World, instead of the main game
+    pub const fn is_gd_world(self) -> bool {
+        matches!(self, LevelRequestType::WorldMostLiked | LevelRequestType::WorldFeatured)
+    }
+
+    /// The game version this type of request was introduced in, according to
+    /// [`LEVEL_REQUEST_TYPE_VERSIONS`]. [`None`] for [`LevelRequestType::Unknown`]
+    pub fn introduced_in(self) -> Option<GameVersion> {
+        LEVEL_REQUEST_TYPE_VERSIONS
+            .iter()
+            .find(|(request_type, _)| *request_type == self)
+            .map(|(_, version)| *version)
+    }
+
+    /// Whether requests of this type can be made with the given game version
+    ///
+    /// Unknown request types are assumed to be supported by every version.
+    pub fn is_supported_by(self, game_version: GameVersion) -> bool {
+        self.introduced_in().is_none_or(|introduced| game_version >= introduced)
+    }
+}
+
 impl From<i32> for LevelRequestType {
     fn from(value: i32) -> Self {
         use LevelRequestType::*;
@@ -401,7 +693,9 @@ impl From<i32> for LevelRequestType {
             11 => Awarded,
             12 => Followed,
             13 => Friends,
+            15 => WorldMostLiked,
             16 => HallOfFame,
+            17 => WorldFeatured,
             _ => Unknown(value),
         }
     }
@@ -422,7 +716,9 @@ impl From<LevelRequestType> for i32 {
             LevelRequestType::Awarded => 11,
             LevelRequestType::Followed => 12,
             LevelRequestType::Friends => 13,
+            LevelRequestType::WorldMostLiked => 15,
             LevelRequestType::HallOfFame => 16,
+            LevelRequestType::WorldFeatured => 17,
             LevelRequestType::Unknown(value) => value,
         }
     }
@@ -441,6 +737,13 @@ fn is_false(b: &bool) -> bool {
     !*b
 }
 
+fn has_duplicates(values: impl Iterator<Item = i32> + Clone) -> bool {
+    values
+        .clone()
+        .enumerate()
+        .any(|(idx, value)| values.clone().skip(idx + 1).any(|other| other == value))
+}
+
 /// Struct modelled after a request to `getGJLevels21.php`
 ///
 /// In the Geometry Dash API, this endpoint is used to retrieve a list of
@@ -469,7 +772,7 @@ pub struct LevelsRequest<'a> {
     /// ## GD Internals:
     /// This field is called `str` in the boomlings API
     #[serde(rename = "str")]
-    pub search_string: &'a str,
+    pub search_string: Cow<'a, str>,
 
     /// A list of level lengths to filter by
     ///
@@ -526,6 +829,17 @@ pub struct LevelsRequest<'a> {
     /// This value is ignored unless [`LevelsRequest::request_type`] is set to
     /// [`LevelRequestType::Search`]
     pub search_filters: SearchFilters,
+
+    /// The account ids of the creators whose levels should be retrieved
+    ///
+    /// This value has to be non-empty if, and only if, [`LevelsRequest::request_type`] is set to
+    /// [`LevelRequestType::Followed`]
+    ///
+    /// ## GD Internals:
+    /// This field is called `followed` in the boomlings API and needs to be converted to a comma
+    /// separated list of integers. It is omitted for all other request types.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub followed: Vec<u64>,
 }
 
 impl<'a> LevelsRequest<'a> {
@@ -535,8 +849,53 @@ impl<'a> LevelsRequest<'a> {
 
     const_setter!(request_type: LevelRequestType);
 
-    pub fn to_url(&self) -> String {
-        format!("{}{}", REQUEST_BASE_URL, SEARCH_LEVEL_ENDPOINT)
+    /// Returns the URL this request needs to be sent to, after [validating](LevelsRequest::validate)
+    /// the request
+    pub fn to_url(&self) -> Result<String, RequestValidationError> {
+        self.validate()?;
+
+        Ok(format!("{}{}", REQUEST_BASE_URL, SEARCH_LEVEL_ENDPOINT))
+    }
+
+    /// Checks this request for mutually exclusive options that were set at the same time, and
+    /// for a [`LevelRequestType`] not supported by the game version of its [`BaseRequest`]
+    pub fn validate(&self) -> Result<(), RequestValidationError> {
+        if !self.request_type.is_supported_by(self.base.game_version) {
+            return Err(RequestValidationError::UnsupportedRequestType {
+                request_type: self.request_type,
+                game_version: self.base.game_version,
+            });
+        }
+
+        if !self.ratings.is_empty() && self.demon_rating.is_some() {
+            return Err(RequestValidationError::ConflictingRatingFilters);
+        }
+
+        if self.followed.is_empty() == (self.request_type == LevelRequestType::Followed) {
+            return Err(RequestValidationError::MismatchedFollowedFilter);
+        }
+
+        if has_duplicates(self.lengths.iter().map(|length| length.value()))
+            || has_duplicates(self.ratings.iter().map(|rating| rating.value()))
+        {
+            return Err(RequestValidationError::DuplicateFilterValue);
+        }
+
+        self.search_filters.validate()
+    }
+
+    /// Constructs a new `LevelsRequest` retrieving the level list of the given type
+    ///
+    /// Uses a default [`BaseRequest`] (or [`GD_WORLD`] for [request types](LevelRequestType::is_gd_world)
+    /// only used by GD World) and no filters. For [`LevelRequestType::Search`] and
+    /// [`LevelRequestType::Followed`], prefer [`LevelsRequest::search`] and
+    /// [`LevelsRequest::followed`], which also set the required search parameters.
+    pub fn new(request_type: LevelRequestType) -> LevelsRequest<'static> {
+        LevelsRequest {
+            base: if request_type.is_gd_world() { GD_WORLD } else { GD_22 },
+            request_type,
+            ..Default::default()
+        }
     }
 
     pub fn with_base(base: BaseRequest<'a>) -> Self {
@@ -548,12 +907,31 @@ impl<'a> LevelsRequest<'a> {
 
     /// Turns this request into a [`LevelRequestType::Search`]-type request, with the search
     /// parameter set to the given string
-    pub const fn search(mut self, search_string: &'a str) -> Self {
-        self.search_string = search_string;
+    pub fn search(mut self, search_string: &'a str) -> Self {
+        self.search_string = Cow::Borrowed(search_string);
         self.request_type = LevelRequestType::Search;
         self
     }
 
+    /// Turns this request into a [`LevelRequestType::Followed`]-type request, retrieving levels by
+    /// the creators with the given account ids
+    pub fn followed(mut self, account_ids: Vec<u64>) -> Self {
+        self.followed = account_ids;
+        self.request_type = LevelRequestType::Followed;
+        self
+    }
+
+    /// Turns this request into one retrieving exactly the levels with the given ids
+    ///
+    /// ## GD Internals:
+    /// This sets the search string to the comma separated list of ids, and the request type to
+    /// [`LevelRequestType::MapPack`], which is how the game retrieves the levels of map packs.
+    pub fn with_ids(mut self, ids: &[u64]) -> Self {
+        self.search_string = Cow::Owned(util::format_id_list(ids, ','));
+        self.request_type = LevelRequestType::MapPack;
+        self
+    }
+
     /// Turns on filtering by level length (if not already on) and adds the given level length to
     /// the list of lengths to include in the search results
     pub fn with_length(mut self, length: LevelLength) -> Self {
@@ -588,9 +966,50 @@ impl<'a> LevelsRequest<'a> {
     }
 }
 
-impl ToString for LevelsRequest<'_> {
-    fn to_string(&self) -> String {
-        super::to_string(self)
+impl From<LevelRequestType> for LevelsRequest<'_> {
+    fn from(request_type: LevelRequestType) -> Self {
+        LevelsRequest::new(request_type)
+    }
+}
+
+impl Display for LevelsRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for LevelsRequest<'_> {
+    type Response<'a> = Vec<ListedLevel<'a>>;
+
+    const ENDPOINT: &'static str = SEARCH_LEVEL_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        self.validate()?;
+
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<Vec<ListedLevel<'_>>, ResponseError<'_>> {
+        response::parse_get_gj_levels_response(response)
+    }
+}
+
+impl Paginated for LevelsRequest<'_> {
+    const PAGE_INFO_SECTION: usize = 3;
+
+    fn current_page(&self) -> u32 {
+        self.page
+    }
+
+    fn set_page(&mut self, page: u32) {
+        self.page = page
+    }
+
+    // The servers always return pages of 10 objects for this endpoint
+    fn page_size(&self) -> u32 {
+        10
     }
 }
 
@@ -598,21 +1017,25 @@ impl ToString for LevelsRequest<'_> {
 #[derive(Debug, Clone, Copy, Hash)]
 struct DemonFilter(DemonRating);
 
-impl Serialize for DemonFilter {
-    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
-    where
-        S: Serializer,
-    {
-        let numerical_value = match self.0 {
+impl DemonFilter {
+    fn value(&self) -> i32 {
+        match self.0 {
             DemonRating::Unknown(value) => value,
             DemonRating::Easy => 1,
             DemonRating::Medium => 2,
             DemonRating::Hard => 3,
             DemonRating::Insane => 4,
             DemonRating::Extreme => 5,
-        };
+        }
+    }
+}
 
-        serializer.serialize_i32(numerical_value)
+impl Serialize for DemonFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i32(self.value())
     }
 }
 
@@ -620,12 +1043,9 @@ impl Serialize for DemonFilter {
 #[derive(Debug, Clone, Copy, Hash)]
 struct LengthFilter(LevelLength);
 
-impl Serialize for LengthFilter {
-    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
-    where
-        S: Serializer,
-    {
-        let numerical_value = match self.0 {
+impl LengthFilter {
+    fn value(&self) -> i32 {
+        match self.0 {
             LevelLength::Unknown(unknown) => unknown,
             LevelLength::Tiny => 0,
             LevelLength::Short => 1,
@@ -633,9 +1053,16 @@ impl Serialize for LengthFilter {
             LevelLength::Long => 3,
             LevelLength::ExtraLong => 4,
             LevelLength::Platformer => 5,
-        };
+        }
+    }
+}
 
-        serializer.serialize_i32(numerical_value)
+impl Serialize for LengthFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i32(self.value())
     }
 }
 
@@ -643,12 +1070,9 @@ impl Serialize for LengthFilter {
 #[derive(Debug, Clone, Copy, Hash)]
 struct RatingFilter(LevelRating);
 
-impl Serialize for RatingFilter {
-    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
-    where
-        S: Serializer,
-    {
-        let numerical_value = match self.0 {
+impl RatingFilter {
+    fn value(&self) -> i32 {
+        match self.0 {
             LevelRating::Unknown(value) => value,
             LevelRating::NotAvailable => -1,
             LevelRating::Auto => -3,
@@ -659,18 +1083,542 @@ impl Serialize for RatingFilter {
             LevelRating::Insane => 5,
             LevelRating::Demon(_) => -2, /* The value doesn't matter, since setting the request field "rating" to
                                           * -2 means "search for any demon, regardless of difficulty" */
-        };
+        }
+    }
+}
 
-        serializer.serialize_i32(numerical_value)
+impl Serialize for RatingFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i32(self.value())
+    }
+}
+
+/// Struct modelled after a request to `uploadGJLevel21.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to upload new levels, and to update existing
+/// ones. To reupload a downloaded level (e.g. when migrating it to a different server), use
+/// [`Level::prepare_reupload`].
+///
+/// ## GD Internals:
+/// The servers additionally validate uploads via a `seed2` value computed from the level data (see
+/// [`util::chk::level_seed2`]), which is automatically added when serializing this request.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
+pub struct LevelUploadRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account uploading the level
+    pub authentication: Authentication<'a>,
+
+    /// The name of the user uploading the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `userName` in the boomlings API
+    #[serde(rename = "userName")]
+    pub user_name: &'a str,
+
+    /// The ID of the level to update, or `0` to upload a new level
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+
+    /// The name of the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelName` in the boomlings API
+    #[serde(rename = "levelName")]
+    pub name: Cow<'a, str>,
+
+    /// The level's description, urlsafe base64 encoded
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelDesc` in the boomlings API
+    #[serde(rename = "levelDesc")]
+    pub description: Cow<'a, str>,
+
+    /// The version of the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelVersion` in the boomlings API
+    #[serde(rename = "levelVersion")]
+    pub version: u32,
+
+    /// The length of the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelLength` in the boomlings API, and is sent as an integer
+    #[serde(rename = "levelLength", serialize_with = "serialize_length")]
+    pub length: LevelLength,
+
+    /// The ID of the main song the level uses. Ignored if [`LevelUploadRequest::custom_song`] is
+    /// set
+    ///
+    /// ## GD Internals:
+    /// This field is called `audioTrack` in the boomlings API
+    #[serde(rename = "audioTrack")]
+    pub main_song: u8,
+
+    /// The ID of the newgrounds song the level uses, or `0` if it uses a main song
+    ///
+    /// ## GD Internals:
+    /// This field is called `songID` in the boomlings API
+    #[serde(rename = "songID")]
+    pub custom_song: u64,
+
+    /// Whether the level is an auto level
+    pub auto: bool,
+
+    /// The level's copy password
+    ///
+    /// ## GD Internals:
+    /// Unlike in download responses, the password is sent unencoded. `0` means the level cannot be
+    /// copied, `1` means it is free to copy
+    #[serde(serialize_with = "serialize_password")]
+    pub password: Password,
+
+    /// The ID of the level this level is a copy of, or `0` if it is an original level
+    pub original: u64,
+
+    /// Whether the level has two player mode enabled
+    ///
+    /// ## GD Internals:
+    /// This field is called `twoPlayer` in the boomlings API
+    #[serde(rename = "twoPlayer")]
+    pub two_player: bool,
+
+    /// The amount of objects in the level
+    pub objects: u32,
+
+    /// The amount of user coins in the level
+    pub coins: u8,
+
+    /// The amount of stars the creator requests for the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `requestedStars` in the boomlings API
+    #[serde(rename = "requestedStars")]
+    pub requested_stars: u8,
+
+    /// Whether the level should not be listed in searches
+    pub unlisted: bool,
+
+    /// Whether the level offers a low detail mode
+    pub ldm: bool,
+
+    /// The time in seconds spent in the editor working on the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `wt` in the boomlings API
+    #[serde(rename = "wt")]
+    pub editor_seconds: u32,
+
+    /// The time in seconds spent in the editor working on the levels this level is a copy of
+    ///
+    /// ## GD Internals:
+    /// This field is called `wt2` in the boomlings API
+    #[serde(rename = "wt2")]
+    pub editor_seconds_copies: u32,
+
+    /// The ids of the songs from the 2.2 music library used by the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `songIDs` in the boomlings API
+    #[serde(rename = "songIDs")]
+    pub songs_used: Vec<u64>,
+
+    /// The ids of the sound effects from the 2.2 SFX library used by the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `sfxIDs` in the boomlings API
+    #[serde(rename = "sfxIDs")]
+    pub sfx_used: Vec<u64>,
+
+    /// The level data, urlsafe base64 encoded and compressed, exactly as provided in download
+    /// responses
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelString` in the boomlings API
+    #[serde(rename = "levelString")]
+    pub level_string: Cow<'a, str>,
+
+    /// The level's `extraString`. See [`ExtraString`](crate::model::level::ExtraString)
+    ///
+    /// ## GD Internals:
+    /// This field is called `extraString` in the boomlings API
+    #[serde(rename = "extraString")]
+    pub extra_string: Cow<'a, str>,
+}
+
+fn serialize_length<S: Serializer>(length: &LevelLength, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_i32(length.to_serialize_proxy())
+}
+
+fn serialize_password<S: Serializer>(password: &Password, serializer: S) -> Result<S::Ok, S::Error> {
+    match *password {
+        Password::NoCopy => serializer.serialize_u32(0),
+        Password::FreeCopy => serializer.serialize_u32(1),
+        Password::PasswordCopy(password) => serializer.serialize_u32(password),
+    }
+}
+
+impl<'a> LevelUploadRequest<'a> {
+    const_setter! {
+        /// Sets the [`BaseRequest`] to be used
+        ///
+        /// Allows builder-style creation of requests
+        base[with_base]: BaseRequest<'a>
+    }
+
+    const_setter! {
+        /// Sets whether the level should be unlisted
+        ///
+        /// Allows builder-style creation of requests
+        unlisted: bool
+    }
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, UPLOAD_LEVEL_ENDPOINT)
+    }
+}
+
+/// The form actually sent for a [`LevelUploadRequest`], which includes the `seed2` computed from
+/// the level data
+#[derive(Debug, Serialize)]
+pub struct LevelUploadForm<'a> {
+    request: &'a LevelUploadRequest<'a>,
+    seed2: String,
+}
+
+impl LevelUploadRequest<'_> {
+    fn form(&self) -> LevelUploadForm<'_> {
+        LevelUploadForm {
+            request: self,
+            seed2: util::chk::level_seed2(&self.level_string),
+        }
+    }
+}
+
+impl Display for LevelUploadRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self.form()))
+    }
+}
+
+impl GdRequest for LevelUploadRequest<'_> {
+    type Response<'a> = u64;
+
+    const ENDPOINT: &'static str = UPLOAD_LEVEL_ENDPOINT;
+
+    type Form<'s> = LevelUploadForm<'s> where Self: 's;
+
+    fn prepare(&self) -> Result<LevelUploadForm<'_>, RequestValidationError> {
+        Ok(self.form())
+    }
+
+    fn parse_response(response: &str) -> Result<u64, ResponseError<'_>> {
+        response::parse_upload_gj_level_response(response)
+    }
+}
+
+/// Struct modelled after a request to `deleteGJLevelUser20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to delete one of the own levels from the
+/// servers.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct DeleteLevelRequest<'a> {
+    /// The base request data. Needs to use the secret of [`GD_22_DELETE`]
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account owning the level
+    pub authentication: Authentication<'a>,
+
+    /// The id of the level to delete
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+}
+
+impl<'a> DeleteLevelRequest<'a> {
+    const_setter!(level_id: u64);
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, DELETE_LEVEL_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>, level_id: u64) -> Self {
+        Self::with_base(GD_22_DELETE, authentication, level_id)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>, level_id: u64) -> Self {
+        DeleteLevelRequest {
+            base,
+            authentication,
+            level_id,
+        }
+    }
+}
+
+impl Display for DeleteLevelRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for DeleteLevelRequest<'_> {
+    type Response<'a> = ();
+
+    const ENDPOINT: &'static str = DELETE_LEVEL_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<(), ResponseError<'_>> {
+        response::parse_delete_gj_level_user_response(response)
+    }
+}
+
+/// Struct modelled after a request to `updateGJDesc20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to change the description of one of the own
+/// levels without reuploading it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpdateDescriptionRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account owning the level
+    pub authentication: Authentication<'a>,
+
+    /// The id of the level whose description to update
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    pub level_id: u64,
+
+    /// The new description
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelDesc` in the boomlings API, and is sent urlsafe base64 encoded
+    pub description: &'a str,
+}
+
+/// The form body of an [`UpdateDescriptionRequest`], containing the encoded description
+#[derive(Debug, Serialize)]
+pub struct UpdateDescriptionForm<'a> {
+    base: BaseRequest<'a>,
+    authentication: Authentication<'a>,
+    #[serde(rename = "levelID")]
+    level_id: u64,
+    #[serde(rename = "levelDesc")]
+    description: String,
+}
+
+impl<'a> UpdateDescriptionRequest<'a> {
+    const_setter!(description: &'a str);
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, UPDATE_DESCRIPTION_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>, level_id: u64, description: &'a str) -> Self {
+        Self::with_base(GD_22, authentication, level_id, description)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>, level_id: u64, description: &'a str) -> Self {
+        UpdateDescriptionRequest {
+            base,
+            authentication,
+            level_id,
+            description,
+        }
+    }
+
+    fn validate(&self) -> Result<(), RequestValidationError> {
+        Description(Cow::Borrowed(self.description))
+            .validate()
+            .map_err(|_| RequestValidationError::InvalidDescription)
+    }
+
+    fn form(&self) -> UpdateDescriptionForm<'_> {
+        UpdateDescriptionForm {
+            base: self.base,
+            authentication: self.authentication,
+            level_id: self.level_id,
+            description: util::base64_encode(self.description),
+        }
+    }
+}
+
+impl Display for UpdateDescriptionRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self.form()))
+    }
+}
+
+impl GdRequest for UpdateDescriptionRequest<'_> {
+    type Response<'a> = ();
+
+    const ENDPOINT: &'static str = UPDATE_DESCRIPTION_ENDPOINT;
+
+    type Form<'s> = UpdateDescriptionForm<'s> where Self: 's;
+
+    fn prepare(&self) -> Result<UpdateDescriptionForm<'_>, RequestValidationError> {
+        self.validate()?;
+
+        Ok(self.form())
+    }
+
+    fn parse_response(response: &str) -> Result<(), ResponseError<'_>> {
+        response::parse_update_gj_desc_response(response)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        model::level::LevelLength,
-        request::level::{CompletionFilter, LevelRequestType, LevelsRequest, SearchFilters},
+        model::{
+            level::{DemonRating, LevelLength, LevelRating},
+            GameVersion,
+        },
+        request::{
+            level::{
+                CompletionFilter, DeleteLevelRequest, LevelRequest, LevelRequestType, LevelsRequest, RatingFilter, SearchFilters,
+                UpdateDescriptionRequest,
+            },
+            Authentication, BaseRequest, GdRequest, RequestValidationError, GD_21, GD_22, GD_WORLD,
+        },
     };
+    use serde::{
+        de::value::{self, MapDeserializer},
+        Deserialize,
+    };
+
+    #[test]
+    fn serialize_daily_level_request() {
+        assert_eq!(
+            super::super::to_string(LevelRequest::weekly()),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&levelID=-2&inc=1&extra=0"
+        );
+    }
+
+    #[test]
+    fn levels_request_constructors() {
+        let request = LevelsRequest::new(LevelRequestType::Awarded);
+
+        assert_eq!(request.to_url().unwrap(), "https://www.boomlings.com/database/getGJLevels21.php");
+        assert_eq!(request.to_url().unwrap(), request.url());
+        assert_eq!(
+            request.to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=11&str=&len=-&diff=-&page=0&total=0&featured=0&original=0&\
+             twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0"
+        );
+        assert_eq!(LevelsRequest::from(LevelRequestType::Awarded).to_string(), request.to_string());
+        assert_eq!(LevelsRequest::new(LevelRequestType::WorldFeatured).base, GD_WORLD);
+        assert_eq!(GD_WORLD, GD_22);
+        assert!(LevelsRequest::new(LevelRequestType::WorldFeatured)
+            .to_string()
+            .contains("&type=17&"));
+        assert_eq!(LevelRequestType::from(15), LevelRequestType::WorldMostLiked);
+        assert_eq!(LevelRequest::new(10565740).to_url(), LevelRequest::new(10565740).url());
+        assert_eq!(
+            LevelRequest::try_new(u64::MAX).unwrap_err(),
+            RequestValidationError::InvalidLevelId(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn serialize_levels_request_with_ids() {
+        assert_eq!(
+            super::super::to_string(LevelsRequest::default().with_ids(&[5, 1337, 12345678])),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=10&str=5,1337,12345678&len=-&diff=-&page=0&total=0&featured=0&\
+             original=0&twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0"
+        );
+    }
+
+    #[test]
+    fn serialize_followed_levels_request() {
+        let request = LevelsRequest::default().followed(vec![71, 7226087]);
+
+        assert!(request.validate().is_ok());
+        assert_eq!(
+            super::super::to_string(request),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=12&str=&len=-&diff=-&page=0&total=0&featured=0&original=0&\
+             twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0&followed=71,7226087"
+        );
+
+        assert_eq!(
+            LevelsRequest::default().request_type(LevelRequestType::Followed).validate(),
+            Err(RequestValidationError::MismatchedFollowedFilter)
+        );
+    }
+
+    #[test]
+    fn validate_levels_request() {
+        let request =
+            LevelsRequest::default().search_filters(SearchFilters::default().completion_filter(CompletionFilter::exclude(vec![])));
+
+        assert_eq!(request.validate(), Err(RequestValidationError::MissingCompletionIds));
+        assert!(request.to_url().is_err());
+
+        let mut request = LevelsRequest::default().demon_rating(DemonRating::Hard);
+
+        assert!(request.validate().is_ok());
+
+        request.ratings.push(RatingFilter(LevelRating::Easy));
+
+        assert_eq!(request.validate(), Err(RequestValidationError::ConflictingRatingFilters));
+    }
+
+    #[test]
+    fn validate_request_type_version() {
+        let gd_19 = BaseRequest::new(GameVersion::Version { major: 1, minor: 9 }, GameVersion::Unknown, "Wmfd2893gb7");
+        let mut request = LevelsRequest::with_base(GD_21).request_type(LevelRequestType::HallOfFame);
+
+        assert!(request.to_url().is_ok());
+
+        request.base = gd_19;
+
+        assert_eq!(
+            request.to_url(),
+            Err(RequestValidationError::UnsupportedRequestType {
+                request_type: LevelRequestType::HallOfFame,
+                game_version: GameVersion::Version { major: 1, minor: 9 },
+            })
+        );
+        assert!(LevelsRequest::with_base(gd_19)
+            .request_type(LevelRequestType::Unknown(21))
+            .validate()
+            .is_ok());
+        assert_eq!(
+            LevelRequestType::WorldFeatured.introduced_in(),
+            Some(GameVersion::Version { major: 2, minor: 1 })
+        );
+        // 1.6 is encoded as 7, "Pre 1.6" as 10, so comparing numeric representations would get
+        // both of these wrong
+        let gd_16 = BaseRequest::new("1.6".parse().unwrap(), GameVersion::Unknown, "Wmfd2893gb7");
+        let pre_16 = BaseRequest::new(GameVersion::Unknown, GameVersion::Unknown, "Wmfd2893gb7");
+
+        assert!(LevelsRequest::with_base(gd_16).validate().is_ok());
+        assert!(LevelsRequest::with_base(pre_16).validate().is_ok());
+        assert!(LevelsRequest::with_base(gd_16)
+            .request_type(LevelRequestType::MapPack)
+            .validate()
+            .is_err());
+        assert!(LevelsRequest::with_base(pre_16)
+            .request_type(LevelRequestType::Awarded)
+            .validate()
+            .is_err());
+    }
 
     #[test]
     fn serialize_levels_request() {
@@ -692,4 +1640,122 @@ mod tests {
              22031889,22390740,22243264,21923305)&onlyCompleted=0&uncompleted=1"
         );
     }
+
+    #[test]
+    fn completion_filter_roundtrip() {
+        let request = LevelsRequest::default()
+            .search_filters(SearchFilters::default().completion_filter(CompletionFilter::limit_search(vec![87600, 22031889])));
+        let pairs = request.to_pairs().unwrap();
+        let deserializer = MapDeserializer::<_, value::Error>::new(pairs.iter().map(|(key, value)| (*key, value.as_str())));
+        let filter = CompletionFilter::deserialize(deserializer).unwrap();
+
+        assert_eq!(filter.ids, Some(vec![87600, 22031889]));
+        assert!(filter.only_search_given);
+        assert!(!filter.exclude_given);
+
+        let deserializer = MapDeserializer::<_, value::Error>::new(
+            [("completedLevels", "-"), ("onlyCompleted", "0"), ("uncompleted", "0")]
+                .iter()
+                .copied(),
+        );
+
+        assert_eq!(CompletionFilter::deserialize(deserializer).unwrap().ids, Some(vec![]));
+
+        let json = serde_json::to_string(&filter).unwrap();
+
+        assert_eq!(serde_json::from_str::<CompletionFilter>(&json).unwrap().ids, filter.ids);
+    }
+
+    #[test]
+    fn serialize_22_search_filters() {
+        let request = LevelsRequest::default().search_filters(SearchFilters::default().rated().no_star().legendary().mythic());
+
+        assert_eq!(
+            request.body().unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=0&str=&len=-&diff=-&page=0&total=0&featured=0&original=0&\
+             twoPlayer=0&coins=0&epic=0&star=0&noStar=1&legendary=1&mythic=1&onlyCompleted=0&uncompleted=0"
+        );
+
+        let mut filters = SearchFilters::default().featured().no_star().unfeatured();
+
+        assert!(!filters.featured);
+        assert!(filters.validate().is_ok());
+
+        filters.rated = true;
+
+        assert_eq!(filters.validate(), Err(RequestValidationError::ConflictingStarFilters));
+        assert_eq!(
+            LevelsRequest::default()
+                .with_length(LevelLength::Short)
+                .with_length(LevelLength::Short)
+                .validate(),
+            Err(RequestValidationError::DuplicateFilterValue)
+        );
+        assert_eq!(
+            LevelsRequest::default()
+                .with_rating(LevelRating::Demon(DemonRating::Easy))
+                .with_rating(LevelRating::Demon(DemonRating::Hard))
+                .validate(),
+            Err(RequestValidationError::DuplicateFilterValue)
+        );
+    }
+
+    #[test]
+    fn levels_request_to_pairs() {
+        let request = LevelsRequest::default()
+            .with_length(LevelLength::Medium)
+            .search_filters(SearchFilters::default().completion_filter(CompletionFilter::exclude(vec![87600, 22031889])));
+
+        let pairs = request.to_pairs().unwrap();
+
+        assert!(pairs.contains(&("len", "2".to_string())));
+        assert!(pairs.contains(&("diff", "-".to_string())));
+        assert!(pairs.contains(&("str", String::new())));
+        assert!(pairs.contains(&("completedLevels", "(87600,22031889)".to_string())));
+
+        let joined = pairs
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        assert_eq!(joined, request.body().unwrap());
+    }
+
+    #[test]
+    fn serialize_delete_level() {
+        let authentication = Authentication::new(1710032, "0123456789abcdef0123456789abcdef01234567");
+
+        assert_eq!(
+            DeleteLevelRequest::new(authentication, 62953227).to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfv2898gc9&accountID=1710032&gjp2=0123456789abcdef0123456789abcdef01234567&\
+             levelID=62953227"
+        );
+    }
+
+    #[test]
+    fn serialize_update_description() {
+        let authentication = Authentication::new(1710032, "0123456789abcdef0123456789abcdef01234567");
+        let request = UpdateDescriptionRequest::new(authentication, 62953227, "Noice level, hope you like it");
+
+        assert_eq!(
+            request.to_pairs().unwrap().last(),
+            Some(&("levelDesc", "Tm9pY2UgbGV2ZWwsIGhvcGUgeW91IGxpa2UgaXQ=".to_string()))
+        );
+        assert_eq!(
+            request.description("two\nlines").body(),
+            Err(RequestValidationError::InvalidDescription)
+        );
+    }
+
+    #[test]
+    fn main_song_named_requires_exact_name() {
+        let filters = SearchFilters::default().main_song_named("cant let go").unwrap();
+
+        assert_eq!(filters.song.map(|song| (song.song_id, song.is_custom)), Some((5, false)));
+        assert_eq!(
+            SearchFilters::default().main_song_named("clubstep 2").err(),
+            Some(RequestValidationError::UnknownMainSong)
+        );
+    }
 }
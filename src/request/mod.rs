@@ -5,8 +5,19 @@
 //! making/proxying requests for the boomlings servers seems rather useless to me, as they already
 //! contain a lot of boomlings-specific fields.
 
-use crate::{model::GameVersion, serde::RequestSerializer};
+use crate::{
+    model::GameVersion,
+    response::{self, Parsed, ResponseError},
+    serde::RequestSerializer,
+    util,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 macro_rules! const_setter {
     ($name: ident, $field: ident, $t: ty) => {
@@ -40,8 +51,11 @@ macro_rules! const_setter {
     }
 }
 
+pub mod account;
 pub mod comment;
+pub mod endpoints;
 pub mod level;
+pub mod moderator;
 pub mod user;
 
 pub const REQUEST_BASE_URL: &str = "https://www.boomlings.com/database/";
@@ -62,6 +76,29 @@ pub const GD_22: BaseRequest = BaseRequest::new(
     "Wmfd2893gb7",
 );
 
+/// A `BaseRequest` instance that has all its fields set to the same values a Geometry Dash 2.2
+/// client would use for moderator actions, e.g.
+/// [`SuggestStarsRequest`](moderator::SuggestStarsRequest)
+pub const GD_22_MOD: BaseRequest = BaseRequest::new(
+    GameVersion::Version { major: 2, minor: 2 },
+    GameVersion::Version { major: 3, minor: 8 },
+    "Wmfp3879gc3",
+);
+
+/// A `BaseRequest` instance for making the requests GD World makes (see
+/// [`LevelRequestType::is_gd_world`](level::LevelRequestType::is_gd_world))
+///
+/// This is an alias of [`GD_22`], and is guaranteed to stay identical to it. It only exists to
+/// document intent at the places GD World's requests are constructed.
+///
+/// ## GD Internals:
+/// Of the spin-off games, only GD World can browse online levels. It talks to the same servers as
+/// the main game, using the same versions and secret, so the servers cannot tell its requests
+/// apart from the main game's (see
+/// [`LEVEL_REQUEST_TYPE_VERSIONS`](level::LEVEL_REQUEST_TYPE_VERSIONS)). Meltdown and SubZero do
+/// not make any requests that dash-rs models.
+pub const GD_WORLD: BaseRequest = GD_22;
+
 /// Base data included in every request made
 ///
 /// The fields in this struct are only relevant when making a request to the
@@ -99,7 +136,7 @@ pub struct BaseRequest<'a> {
 
 impl BaseRequest<'_> {
     /// Constructs a new `BaseRequest` with the given values.
-    pub const fn new(game_version: GameVersion, binary_version: GameVersion, secret: &'static str) -> BaseRequest<'_> {
+    pub const fn new(game_version: GameVersion, binary_version: GameVersion, secret: &'static str) -> BaseRequest<'static> {
         BaseRequest {
             game_version,
             binary_version,
@@ -114,6 +151,189 @@ impl Default for BaseRequest<'static> {
     }
 }
 
+/// Credentials for requests that act on behalf of an account
+#[derive(Debug, Clone, Hash, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Authentication<'a> {
+    /// The id of the account the request is made for
+    ///
+    /// ## GD Internals:
+    /// This field is called `accountID` in the boomlings API
+    #[serde(rename = "accountID")]
+    pub account_id: u64,
+
+    /// The hashed password of the account. Use [`Authentication::hash_password`] to compute it
+    ///
+    /// ## GD Internals:
+    /// This field is called `gjp2` in the boomlings API
+    pub gjp2: &'a str,
+}
+
+impl<'a> Authentication<'a> {
+    /// Constructs new credentials from an account id and the `gjp2` password hash
+    pub const fn new(account_id: u64, gjp2: &'a str) -> Self {
+        Authentication { account_id, gjp2 }
+    }
+
+    /// Hashes a plain text password into the `gjp2` value the servers use for authentication
+    ///
+    /// ## GD Internals:
+    /// The `gjp2` is the hex-encoded SHA-1 digest of the password with the
+    /// [`GJP2`](util::salts::GJP2) salt appended
+    pub fn hash_password(password: &str) -> String {
+        let mut hasher = sha1_smol::Sha1::new();
+
+        hasher.update(password.as_bytes());
+        hasher.update(util::salts::GJP2.as_bytes());
+
+        hasher.digest().to_string()
+    }
+}
+
+/// Trait implemented by all requests, describing how to make them and how to process their
+/// responses
+///
+/// This allows any HTTP stack to drive dash-rs generically: Send a `POST` request with
+/// [`GdRequest::body`] as its url-encoded form body to [`GdRequest::url`], and process the response
+/// body using [`GdRequest::parse_response`].
+pub trait GdRequest {
+    /// The path of the endpoint this request is sent to, relative to the server's base URL (e.g.
+    /// [`REQUEST_BASE_URL`] for the boomlings servers)
+    const ENDPOINT: &'static str;
+
+    /// The type the response to this request is parsed into
+    type Response<'a>;
+
+    /// The data the form body of this request is serialized from
+    ///
+    /// For most requests, this is a reference to the request itself. Some requests however send
+    /// values derived from their fields (such as hashes or encoded strings) instead.
+    type Form<'s>: Serialize
+    where
+        Self: 's;
+
+    /// Validates this request, and returns the data its form body is serialized from
+    fn prepare(&self) -> Result<Self::Form<'_>, RequestValidationError>;
+
+    /// Serializes this request into the form body to be sent to the server, after validating it
+    fn body(&self) -> Result<String, RequestValidationError> {
+        self.prepare().map(to_string)
+    }
+
+    /// Serializes this request into the key-value pairs making up its form body, after validating
+    /// it
+    ///
+    /// Unlike [`GdRequest::body`], the values are not joined into RobTop's format, which allows
+    /// passing them to HTTP clients that encode form parameters themselves
+    fn to_pairs(&self) -> Result<Vec<(&'static str, String)>, RequestValidationError> {
+        self.prepare().map(to_pairs)
+    }
+
+    /// Parses the server's response to this request
+    fn parse_response(response: &str) -> Result<Self::Response<'_>, ResponseError<'_>>;
+
+    /// Parses the server's response to this request, additionally returning the raw sections of the
+    /// response, see [`parse_with_raw`](response::parse_with_raw)
+    fn parse_response_with_raw(response: &str) -> Result<Parsed<'_, Self::Response<'_>>, ResponseError<'_>> {
+        response::parse_with_raw(response, Self::parse_response)
+    }
+
+    /// The full URL of the endpoint on the boomlings servers this request is sent to
+    fn url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, Self::ENDPOINT)
+    }
+}
+
+/// Trait implemented by requests for lists of objects that the servers split into multiple pages
+///
+/// Responses to these requests contain a [`PageInfo`](response::PageInfo) section that allows
+/// determining whether further pages exist, see [`Paginated::page_info`].
+pub trait Paginated: GdRequest {
+    /// The index of the `#`-separated section of the response that contains the page info
+    const PAGE_INFO_SECTION: usize;
+
+    /// The page this request retrieves. The first page is page `0`
+    fn current_page(&self) -> u32;
+
+    /// Changes the page this request retrieves
+    fn set_page(&mut self, page: u32);
+
+    /// The maximal number of objects on the page retrieved by this request
+    fn page_size(&self) -> u32;
+
+    /// Extracts the page info from the server's response to this request, if present
+    fn page_info(response: &str) -> Option<response::PageInfo> {
+        response::sections::ResponseSections::new(response)
+            .section(Self::PAGE_INFO_SECTION)
+            .and_then(|section| response::PageInfo::from_section(section).ok())
+    }
+}
+
+/// Errors indicating that a request was configured in a way the servers cannot handle
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum RequestValidationError {
+    /// A [`CompletionFilter`](level::CompletionFilter) was set to both exclusively search and
+    /// exclude the given levels
+    #[error("completion filter cannot both restrict the search to and exclude the given levels")]
+    ConflictingCompletionFilter,
+
+    /// A [`CompletionFilter`](level::CompletionFilter) was set to restrict the search to or
+    /// exclude levels, but no level ids were given
+    #[error("completion filter is active, but no level ids were given")]
+    MissingCompletionIds,
+
+    /// A [`LevelsRequest`](level::LevelsRequest) filters by both a list of ratings and a specific
+    /// demon rating
+    #[error("cannot filter by level ratings and demon rating at the same time")]
+    ConflictingRatingFilters,
+
+    /// A [`LevelsRequest`](level::LevelsRequest) specifies followed creators, but is not of type
+    /// [`LevelRequestType::Followed`](level::LevelRequestType::Followed), or vice versa
+    #[error("followed creators must be given if, and only if, requesting levels of followed creators")]
+    MismatchedFollowedFilter,
+
+    /// [`SearchFilters`](level::SearchFilters) were set to only search for both rated and unrated
+    /// levels
+    #[error("cannot filter by rated and unrated levels at the same time")]
+    ConflictingStarFilters,
+
+    /// A [`LevelsRequest`](level::LevelsRequest) filters by the same level length or rating more
+    /// than once
+    #[error("level length and rating filters cannot contain duplicates")]
+    DuplicateFilterValue,
+
+    /// A [`SuggestStarsRequest`](moderator::SuggestStarsRequest) suggests a star rating outside of
+    /// the `1..=10` range
+    #[error("suggested star ratings must be between 1 and 10")]
+    InvalidStarSuggestion,
+
+    /// A [`LevelRequest`](level::LevelRequest) was constructed for a level id that does not fit
+    /// into the signed integer the servers expect
+    #[error("level id {0} is out of range")]
+    InvalidLevelId(u64),
+
+    /// An [`UpdateDescriptionRequest`](level::UpdateDescriptionRequest) sets a description the game
+    /// would not accept, see [`Description::validate`](crate::model::level::Description::validate)
+    #[error("description contains characters the game does not allow, or is too long")]
+    InvalidDescription,
+
+    /// No [`MainSong`](crate::model::song::MainSong) matching a given song name exists, see
+    /// [`SearchFilters::main_song_named`](level::SearchFilters::main_song_named)
+    #[error("no main song matches the given name")]
+    UnknownMainSong,
+
+    /// A [`LevelsRequest`](level::LevelsRequest) is of a
+    /// [`LevelRequestType`](level::LevelRequestType) that did not exist yet in the game version of
+    /// its [`BaseRequest`], see [`LEVEL_REQUEST_TYPE_VERSIONS`](level::LEVEL_REQUEST_TYPE_VERSIONS)
+    #[error("request type {request_type:?} is not supported by game version {game_version}")]
+    UnsupportedRequestType {
+        /// The unsupported request type
+        request_type: level::LevelRequestType,
+
+        /// The game version of the request
+        game_version: GameVersion,
+    },
+}
+
 pub(crate) fn to_string<S: Serialize>(request: S) -> String {
     let mut output = Vec::new();
     let mut serializer = RequestSerializer::new(&mut output);
@@ -122,3 +342,11 @@ pub(crate) fn to_string<S: Serialize>(request: S) -> String {
 
     String::from_utf8(output).unwrap()
 }
+
+pub(crate) fn to_pairs<S: Serialize>(request: S) -> Vec<(&'static str, String)> {
+    let mut serializer = RequestSerializer::pairs();
+
+    request.serialize(&mut serializer).unwrap();
+
+    serializer.into_pairs().unwrap()
+}
@@ -0,0 +1,197 @@
+//! Module containing request structs for the moderator workflow: requesting moderator access for
+//! an account and suggesting star ratings for levels
+//!
+//! ## GD Internals:
+//! Neither endpoint takes a `chk` field, so there is none to compute: the servers authenticate
+//! these requests via the account's `gjp2`, and by checking the account's moderator status. The
+//! `chk`-protected rating request is `rateGJStars211.php`, which is made by regular players and
+//! is not part of the moderator workflow. Suggesting ratings additionally requires the moderator
+//! secret (see [`GD_22_MOD`]), while requesting moderator access uses the common one.
+
+use crate::{
+    model::{level::Level, user::ModLevel},
+    request::{endpoints, Authentication, BaseRequest, GdRequest, RequestValidationError, GD_22, GD_22_MOD, REQUEST_BASE_URL},
+    response::{self, ResponseError},
+};
+use alloc::{format, string::String};
+use core::fmt::{Display, Formatter};
+use serde::Serialize;
+
+pub const REQUEST_MOD_ENDPOINT: &str = endpoints::REQUEST_MOD_ACCESS.name;
+pub const SUGGEST_STARS_ENDPOINT: &str = endpoints::SUGGEST_STARS.name;
+
+/// Struct modelled after a request to `requestUserAccess.php`.
+///
+/// In-game, this request is made when pressing the "Req" button in the account settings. The
+/// response is the [`ModLevel`] of the account, or [`ResponseError::NotFound`] if the account is
+/// not a moderator.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct RequestModRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the account requesting moderator access
+    pub authentication: Authentication<'a>,
+}
+
+impl<'a> RequestModRequest<'a> {
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, REQUEST_MOD_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>) -> Self {
+        Self::with_base(GD_22, authentication)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>) -> Self {
+        RequestModRequest { base, authentication }
+    }
+}
+
+impl Display for RequestModRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for RequestModRequest<'_> {
+    type Response<'a> = ModLevel;
+
+    const ENDPOINT: &'static str = REQUEST_MOD_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<ModLevel, ResponseError<'_>> {
+        response::parse_request_user_access_response(response)
+    }
+}
+
+/// Struct modelled after a request to `suggestGJStars20.php`.
+///
+/// In-game, this request is made when a moderator sends a level to RobTop for rating. It only
+/// succeeds if the account has moderator access.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct SuggestStarsRequest<'a> {
+    /// The base request data. Needs to use the moderator secret, see [`GD_22_MOD`]
+    pub base: BaseRequest<'a>,
+
+    /// The credentials of the moderator making the suggestion
+    pub authentication: Authentication<'a>,
+
+    /// The id of the level to suggest a rating for
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+
+    /// The amount of stars to suggest, between `1` and `10`
+    ///
+    /// ## GD Internals:
+    /// This field is called `stars` in the boomlings API
+    pub stars: u8,
+
+    /// Whether to suggest the level for being featured
+    ///
+    /// ## GD Internals:
+    /// This field is called `feature` in the boomlings API, and is sent as `0` or `1`
+    #[serde(rename = "feature")]
+    pub featured: bool,
+}
+
+impl<'a> SuggestStarsRequest<'a> {
+    const_setter!(stars: u8);
+
+    const_setter!(featured: bool);
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}", REQUEST_BASE_URL, SUGGEST_STARS_ENDPOINT)
+    }
+
+    pub const fn new(authentication: Authentication<'a>, level_id: u64, stars: u8) -> Self {
+        Self::with_base(GD_22_MOD, authentication, level_id, stars)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authentication: Authentication<'a>, level_id: u64, stars: u8) -> Self {
+        SuggestStarsRequest {
+            base,
+            authentication,
+            level_id,
+            stars,
+            featured: false,
+        }
+    }
+
+    /// Constructs a request suggesting the given rating for the given [`Level`]
+    pub fn for_level<S, U>(authentication: Authentication<'a>, level: &Level<'_, S, U>, stars: u8) -> Self {
+        Self::new(authentication, level.level_id, stars)
+    }
+
+    fn validate(&self) -> Result<(), RequestValidationError> {
+        if !(1..=10).contains(&self.stars) {
+            return Err(RequestValidationError::InvalidStarSuggestion);
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SuggestStarsRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for SuggestStarsRequest<'_> {
+    type Response<'a> = ();
+
+    const ENDPOINT: &'static str = SUGGEST_STARS_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        self.validate()?;
+
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<(), ResponseError<'_>> {
+        response::parse_suggest_gj_stars_response(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::request::{
+        moderator::{RequestModRequest, SuggestStarsRequest},
+        Authentication, GdRequest, RequestValidationError,
+    };
+
+    const AUTHENTICATION: Authentication = Authentication::new(1710032, "0123456789abcdef0123456789abcdef01234567");
+
+    #[test]
+    fn serialize_request_mod() {
+        assert_eq!(
+            RequestModRequest::new(AUTHENTICATION).to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&gjp2=0123456789abcdef0123456789abcdef01234567"
+        );
+    }
+
+    #[test]
+    fn serialize_suggest_stars() {
+        let request = SuggestStarsRequest::new(AUTHENTICATION, 62953227, 3).featured(true);
+
+        assert_eq!(request.base.secret, "Wmfp3879gc3");
+
+        assert_eq!(
+            request.body().unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfp3879gc3&accountID=1710032&gjp2=0123456789abcdef0123456789abcdef01234567&\
+             levelID=62953227&stars=3&feature=1"
+        );
+        assert_eq!(request.stars(11).body(), Err(RequestValidationError::InvalidStarSuggestion));
+    }
+}
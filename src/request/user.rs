@@ -1,13 +1,19 @@
 //! Module containing request definitions for retrieving users
 
 use crate::{
-    model::creator::Creator,
-    request::{BaseRequest, GD_22, REQUEST_BASE_URL},
+    model::{
+        creator::Creator,
+        user::{profile::Profile, searched::SearchedUser},
+    },
+    request::{endpoints, Authentication, BaseRequest, GdRequest, Paginated, RequestValidationError, GD_22, REQUEST_BASE_URL},
+    response::{self, ResponseError},
 };
+use alloc::{format, string::String};
+use core::fmt::{Display, Formatter};
 use serde::Serialize;
 
-pub const GET_USER_ENDPOINT: &str = "getGJUserInfo20.php";
-pub const SEARCH_USER_ENDPOINT: &str = "getGJUsers20.php";
+pub const GET_USER_ENDPOINT: &str = endpoints::USER_INFO.name;
+pub const SEARCH_USER_ENDPOINT: &str = endpoints::SEARCH_USERS.name;
 
 /// Struct modelled after a request to `getGJUserInfo20.php`.
 ///
@@ -24,13 +30,43 @@ pub struct UserRequest<'a> {
     /// This field is called `targetAccountID` in the boomlings API
     #[serde(rename = "targetAccountID")]
     pub user: u64,
+
+    /// The credentials of the user making this request, if any.
+    ///
+    /// If set, the returned [`Profile`] contains the friendship state between the two users, and
+    /// if a user requests their own profile, their number of unread messages and friend requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<Authentication<'a>>,
 }
 
-impl UserRequest<'_> {
+impl<'a> UserRequest<'a> {
+    const_setter! {
+        /// Sets the [`BaseRequest`] to be used
+        ///
+        /// Allows builder-style creation of requests
+        base[with_base]: BaseRequest<'a>
+    }
+
+    const_setter! {
+        /// Sets the account ID of the user whose profile to retrieve
+        ///
+        /// Allows builder-style creation of requests
+        user: u64
+    }
+
+    /// Makes this request on behalf of the account with the given credentials
+    ///
+    /// Allows builder-style creation of requests
+    pub const fn authenticated(mut self, authentication: Authentication<'a>) -> Self {
+        self.authentication = Some(authentication);
+        self
+    }
+
     pub const fn new(user_id: u64) -> UserRequest<'static> {
         UserRequest {
             base: GD_22,
             user: user_id,
+            authentication: None,
         }
     }
 
@@ -51,9 +87,25 @@ impl From<Creator<'_>> for UserRequest<'_> {
     }
 }
 
-impl ToString for UserRequest<'_> {
-    fn to_string(&self) -> String {
-        super::to_string(self)
+impl Display for UserRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for UserRequest<'_> {
+    type Response<'a> = Profile<'a>;
+
+    const ENDPOINT: &'static str = GET_USER_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<Profile<'_>, ResponseError<'_>> {
+        response::parse_get_gj_user_info_response(response)
     }
 }
 
@@ -88,9 +140,19 @@ pub struct UserSearchRequest<'a> {
 }
 
 impl<'a> UserSearchRequest<'a> {
+    const_setter!(total: u32);
+
+    const_setter!(page: u32);
+
+    const_setter!(search_string: &'a str);
+
     pub const fn new(search_string: &'a str) -> Self {
+        Self::with_base(GD_22, search_string)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, search_string: &'a str) -> Self {
         UserSearchRequest {
-            base: GD_22,
+            base,
             total: 0,
             page: 0,
             search_string,
@@ -114,8 +176,79 @@ impl<'a: 'b, 'b> From<&'b Creator<'a>> for UserSearchRequest<'b> {
     }
 }
 
-impl ToString for UserSearchRequest<'_> {
-    fn to_string(&self) -> String {
-        super::to_string(self)
+impl Display for UserSearchRequest<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self))
+    }
+}
+
+impl GdRequest for UserSearchRequest<'_> {
+    type Response<'a> = SearchedUser<'a>;
+
+    const ENDPOINT: &'static str = SEARCH_USER_ENDPOINT;
+
+    type Form<'s> = &'s Self where Self: 's;
+
+    fn prepare(&self) -> Result<&Self, RequestValidationError> {
+        Ok(self)
+    }
+
+    fn parse_response(response: &str) -> Result<SearchedUser<'_>, ResponseError<'_>> {
+        response::parse_get_gj_users_response(response)
+    }
+}
+
+impl Paginated for UserSearchRequest<'_> {
+    const PAGE_INFO_SECTION: usize = 1;
+
+    fn current_page(&self) -> u32 {
+        self.page
+    }
+
+    fn set_page(&mut self, page: u32) {
+        self.page = page
+    }
+
+    // The servers always return pages of 10 objects for this endpoint
+    fn page_size(&self) -> u32 {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::request::{
+        user::{UserRequest, UserSearchRequest},
+        Authentication,
+    };
+
+    #[test]
+    fn serialize_user() {
+        let request = UserRequest::new(1710032);
+
+        assert_eq!(
+            request.to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&targetAccountID=1710032"
+        );
+    }
+
+    #[test]
+    fn serialize_authenticated_user() {
+        let request = UserRequest::new(1710032).authenticated(Authentication::new(8451, "gjp2"));
+
+        assert_eq!(
+            request.to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&targetAccountID=1710032&accountID=8451&gjp2=gjp2"
+        );
+    }
+
+    #[test]
+    fn serialize_user_search() {
+        let request = UserSearchRequest::new("stardust1971").page(1).total(10);
+
+        assert_eq!(
+            request.to_string(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&total=10&page=1&str=stardust1971"
+        );
     }
 }
@@ -0,0 +1,735 @@
+//! Most likely temporary location of helper functions regarding the parsing of complete server
+//! responses.
+
+use alloc::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use sections::{fragments, ResponseSections};
+use thiserror::Error;
+
+pub mod sections;
+
+#[cfg(feature = "tracing")]
+use crate::request::{
+    account::{ACCOUNT_URL_ENDPOINT, BACKUP_ACCOUNT_ENDPOINT, SYNC_ACCOUNT_ENDPOINT},
+    comment::{
+        DELETE_ACCOUNT_COMMENT_ENDPOINT, LEVEL_COMMENTS_ENDPOINT, PROFILE_COMMENT_ENDPOINT, UPLOAD_ACCOUNT_COMMENT_ENDPOINT,
+        UPLOAD_COMMENT_ENDPOINT,
+    },
+    level::{DELETE_LEVEL_ENDPOINT, DOWNLOAD_LEVEL_ENDPOINT, SEARCH_LEVEL_ENDPOINT, UPDATE_DESCRIPTION_ENDPOINT, UPLOAD_LEVEL_ENDPOINT},
+    moderator::{REQUEST_MOD_ENDPOINT, SUGGEST_STARS_ENDPOINT},
+    user::{GET_USER_ENDPOINT, SEARCH_USER_ENDPOINT},
+};
+use crate::{
+    model::{
+        comment::{
+            level::{CommentUser, LevelComment},
+            profile::ProfileComment,
+            CommentBan,
+        },
+        creator::Creator,
+        level::{Level, ListedLevel},
+        song::NewgroundsSong,
+        user::{profile::Profile, searched::SearchedUser, ModLevel},
+        GameVersion,
+    },
+    request::account::{SaveData, SyncedAccount},
+    serde::GJFormat,
+    util, DeError,
+};
+
+// Since NoneError is not stabilized, we cannot do `impl From<NoneError> for ResponseError<'_>`, so
+// this is the next best thing
+macro_rules! section {
+    ($iter:expr) => {
+        match $iter.next() {
+            Some(section) => section,
+            None => return Err(ResponseError::UnexpectedFormat),
+        }
+    };
+}
+
+// Enters a span recording which endpoint the response being parsed belongs to, so that deserialization
+// errors can be attributed to it
+macro_rules! endpoint_span {
+    ($endpoint:expr) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("parse_response", endpoint = $endpoint).entered();
+    };
+}
+
+#[derive(Debug, Error)]
+pub enum ResponseError<'a> {
+    /// A deserializer error occured while processing some object contained in the response
+    #[error("{0}")]
+    De(DeError<'a>), // cannot use #[from] here due to non-'static lifetime
+
+    /// The response was of the form `"-1"`, which is RobTop's version of `HTTP 404 NOT FOUND`
+    #[error("not found")]
+    NotFound,
+
+    /// The response was not worked in the expected way (too few sections, etc.)
+    #[error("unexpected format")]
+    UnexpectedFormat,
+
+    /// The request was rejected because the account is banned from posting comments
+    #[error("{0}")]
+    CommentBan(CommentBan<'a>),
+}
+
+impl<'a> From<DeError<'a>> for ResponseError<'a> {
+    fn from(err: DeError<'a>) -> Self {
+        ResponseError::De(err)
+    }
+}
+
+/// A parsed response, together with the raw sections it was parsed from
+///
+/// This allows callers to archive the exact data returned by the servers (e.g. for test artifacts)
+/// without splitting the response a second time. See [`parse_with_raw`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parsed<'a, T> {
+    /// The parsed response
+    pub value: T,
+
+    /// The sections of the raw response, in the order in which they appeared
+    ///
+    /// ## GD Internals:
+    /// Responses consisting of multiple sections separate them with `#`. Responses without
+    /// separators consist of a single section.
+    pub raw_sections: Vec<&'a str>,
+}
+
+impl<'a, T> Parsed<'a, T> {
+    /// The raw section at the given index, if the response had that many sections
+    pub fn section(&self, index: usize) -> Option<&'a str> {
+        self.raw_sections.get(index).copied()
+    }
+
+    /// Transforms the parsed value, keeping the raw sections
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Parsed<'a, U> {
+        Parsed {
+            value: f(self.value),
+            raw_sections: self.raw_sections,
+        }
+    }
+}
+
+/// Parses a response using the given `parse_*` function, additionally returning its raw sections
+///
+/// ```
+/// # use dash_rs::response::{parse_with_raw, parse_upload_gj_level_response};
+/// let response = "62953227";
+/// let parsed = parse_with_raw(response, parse_upload_gj_level_response).unwrap();
+///
+/// assert_eq!(parsed.value, 62953227);
+/// assert_eq!(parsed.raw_sections, [response]);
+/// ```
+pub fn parse_with_raw<'a, T, F>(response: &'a str, parse: F) -> Result<Parsed<'a, T>, ResponseError<'a>>
+where
+    F: FnOnce(&'a str) -> Result<T, ResponseError<'a>>,
+{
+    Ok(Parsed {
+        value: parse(response)?,
+        raw_sections: ResponseSections::new(response).iter().collect(),
+    })
+}
+
+/// Lookup of creators and songs that are referenced by levels in a `getGJLevels` response, but
+/// missing from the response's creator or song sections
+///
+/// This allows resolving them from some other source, e.g. a database of previously seen
+/// creators and songs, see [`parse_get_gj_levels_response_with`]. Both methods default to not
+/// resolving anything.
+///
+/// ## GD Internals:
+/// The boomlings servers omit the creators of levels whose creator was deleted, and the songs of
+/// levels whose song was removed from Newgrounds. Some private servers omit these sections
+/// entirely.
+pub trait ResolveContext<'a> {
+    /// Looks up the creator with the given user id
+    fn creator(&mut self, _user_id: u64) -> Option<Creator<'a>> {
+        None
+    }
+
+    /// Looks up the newgrounds song with the given id
+    fn song(&mut self, _song_id: u64) -> Option<NewgroundsSong<'a>> {
+        None
+    }
+}
+
+/// Context that does not resolve anything, leaving creators and songs missing from the response
+/// unresolved
+impl ResolveContext<'_> for () {}
+
+pub fn parse_get_gj_levels_response(response: &str) -> Result<Vec<ListedLevel<'_>>, ResponseError<'_>> {
+    parse_get_gj_levels_response_with(response, &mut ())
+}
+
+/// Parses a `getGJLevels` response like [`parse_get_gj_levels_response`], but looks up creators
+/// and songs missing from the response in the given [`ResolveContext`]
+///
+/// The context is only consulted for ids that are not contained in the response itself.
+pub fn parse_get_gj_levels_response_with<'a, C: ResolveContext<'a> + ?Sized>(
+    response: &'a str, context: &mut C,
+) -> Result<Vec<ListedLevel<'a>>, ResponseError<'a>> {
+    endpoint_span!(SEARCH_LEVEL_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    let sections = ResponseSections::new(response);
+
+    let creators = sections
+        .creators()?
+        .map(Creator::from_gj_str)
+        .collect::<Result<Vec<Creator>, _>>()?;
+    let songs = sections
+        .songs()?
+        .map(NewgroundsSong::from_gj_str)
+        .collect::<Result<Vec<NewgroundsSong>, _>>()?;
+
+    sections
+        .levels()?
+        .map(|fragment| Ok(resolve_listed_level(Level::from_gj_str(fragment)?, &creators, &songs, context)))
+        .collect::<Result<_, _>>()
+}
+
+/// Checks the hash at the end of a `getGJLevels21.php` response against the levels contained in
+/// the response, see [`level_list_hash`](crate::util::chk::level_list_hash)
+///
+/// Returns `Ok(false)` if the hash does not match, which means the response was tampered with or
+/// got corrupted.
+pub fn verify_get_gj_levels_response(response: &str) -> Result<bool, ResponseError<'_>> {
+    endpoint_span!(SEARCH_LEVEL_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    let sections = ResponseSections::new(response);
+
+    let levels = sections.levels()?.map(Level::<()>::from_gj_str).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(util::chk::level_list_hash(&levels) == sections.hash()?)
+}
+
+/// The result of leniently parsing a response containing a list of objects
+///
+/// Fragments that fail to parse do not abort processing of the entire response. Instead, they are
+/// collected together with the error they caused.
+#[derive(Debug)]
+pub struct LenientResponse<'a, T> {
+    /// The objects that were successfully parsed
+    pub parsed: Vec<T>,
+
+    /// The raw fragments that could not be parsed, together with the error that occurred while
+    /// parsing them
+    pub failed: Vec<(&'a str, ResponseError<'a>)>,
+}
+
+impl<'a, T> Default for LenientResponse<'a, T> {
+    fn default() -> Self {
+        LenientResponse {
+            parsed: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T> LenientResponse<'a, T> {
+    fn parse_fragments<F>(fragments: impl Iterator<Item = &'a str>, parse: F) -> Self
+    where
+        F: Fn(&'a str) -> Result<T, ResponseError<'a>>,
+    {
+        let mut response = LenientResponse::default();
+
+        for fragment in fragments {
+            match parse(fragment) {
+                Ok(item) => response.parsed.push(item),
+                Err(err) => response.failed.push((fragment, err)),
+            }
+        }
+
+        response
+    }
+}
+
+/// Variant of [`parse_get_gj_levels_response`] that skips fragments which fail to parse instead of
+/// aborting
+///
+/// This applies to the level, creator and song sections alike. If a creator or song fragment is
+/// malformed, the levels referencing it will have their `creator` or `custom_song` field set to
+/// [`None`], the same as if the server had omitted it from the response. All malformed fragments
+/// are reported in [`LenientResponse::failed`].
+///
+/// Errors are still returned if the response as a whole is malformed (e.g. it is missing sections),
+/// or if it is RobTop's `"-1"`.
+pub fn parse_get_gj_levels_response_lenient(response: &str) -> Result<LenientResponse<'_, ListedLevel<'_>>, ResponseError<'_>> {
+    endpoint_span!(SEARCH_LEVEL_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    let sections = ResponseSections::new(response);
+
+    let levels = sections.levels()?;
+    let creators = LenientResponse::parse_fragments(sections.creators()?, |fragment| Ok(Creator::from_gj_str(fragment)?));
+    let songs = LenientResponse::parse_fragments(sections.songs()?, |fragment| Ok(NewgroundsSong::from_gj_str(fragment)?));
+
+    let mut result = LenientResponse::parse_fragments(levels, |fragment| {
+        Ok(resolve_listed_level(
+            Level::from_gj_str(fragment)?,
+            &creators.parsed,
+            &songs.parsed,
+            &mut (),
+        ))
+    });
+
+    result.failed.extend(creators.failed);
+    result.failed.extend(songs.failed);
+
+    Ok(result)
+}
+
+/// Associates the given level with its creator and custom song, if they are contained in the
+/// given lists or can be resolved via the given context
+fn resolve_listed_level<'a, C: ResolveContext<'a> + ?Sized>(
+    level: Level<'a, ()>, creators: &[Creator<'a>], songs: &[NewgroundsSong<'a>], context: &mut C,
+) -> ListedLevel<'a> {
+    // Note: Cloning is cheap because none of the Thunks is evaluated, so we only have references lying
+    // around.
+    let song = level.custom_song.map(|song_id| {
+        songs
+            .iter()
+            .find(|song| song.song_id == song_id)
+            .cloned()
+            .or_else(|| context.song(song_id))
+    });
+    let creator = creators
+        .iter()
+        .find(|creator| creator.user_id == level.creator)
+        .cloned()
+        .or_else(|| context.creator(level.creator));
+
+    level.map_parts(|_| song.flatten(), |_| creator)
+}
+
+/// Pagination information included in responses to requests for lists of objects
+///
+/// ## GD Internals:
+/// This is provided as a section of the form `total:offset:page_size`, following the objects
+/// themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PageInfo {
+    /// The total number of objects matching the request, across all pages
+    pub total: u32,
+
+    /// The index of the first object on the returned page
+    pub offset: u32,
+
+    /// The maximal number of objects per page
+    pub page_size: u32,
+}
+
+impl PageInfo {
+    /// Parses a `total:offset:page_size` page info section
+    pub fn from_section(section: &str) -> Result<PageInfo, ResponseError<'_>> {
+        let mut parts = section
+            .split(':')
+            .map(|part| part.parse::<u32>().map_err(|_| ResponseError::UnexpectedFormat));
+
+        Ok(PageInfo {
+            total: section!(parts)?,
+            offset: section!(parts)?,
+            page_size: section!(parts)?,
+        })
+    }
+
+    /// Whether there are objects on pages after the one this info was returned with
+    pub fn has_more(&self) -> bool {
+        self.offset.saturating_add(self.page_size) < self.total
+    }
+}
+
+/// Collects the levels from multiple pages of `getGJLevels` responses, e.g. when scraping all
+/// results of a search
+///
+/// Levels that appear on multiple pages (which can happen if the result list changes while
+/// scraping) are only kept once. The creator and song sections of all responses are merged, so a
+/// level can be associated with a creator or song that was only included in some other response.
+#[derive(Debug, Default)]
+pub struct LevelsAccumulator<'a> {
+    levels: Vec<Level<'a, ()>>,
+    level_ids: BTreeSet<u64>,
+    creators: BTreeMap<u64, Creator<'a>>,
+    songs: BTreeMap<u64, NewgroundsSong<'a>>,
+    page_info: Option<PageInfo>,
+    exhausted: bool,
+}
+
+impl<'a> LevelsAccumulator<'a> {
+    /// Constructs an accumulator that has not ingested any responses yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the contents of the given `getGJLevels` response, returning the number of levels that
+    /// were not already ingested from previous responses
+    ///
+    /// A `"-1"` response, which the servers return for pages past the last one, is not treated as
+    /// an error, but marks the results as exhausted.
+    pub fn ingest(&mut self, response: &'a str) -> Result<usize, ResponseError<'a>> {
+        endpoint_span!(SEARCH_LEVEL_ENDPOINT);
+
+        if response == "-1" {
+            self.exhausted = true;
+
+            return Ok(0);
+        }
+
+        let sections = ResponseSections::new(response);
+
+        let levels = sections.levels()?.map(Level::from_gj_str).collect::<Result<Vec<Level<()>>, _>>()?;
+        let creators = sections
+            .creators()?
+            .map(Creator::from_gj_str)
+            .collect::<Result<Vec<Creator>, _>>()?;
+        let songs = sections
+            .songs()?
+            .map(NewgroundsSong::from_gj_str)
+            .collect::<Result<Vec<NewgroundsSong>, _>>()?;
+        let page_info = sections.page_info()?;
+
+        for creator in creators {
+            self.creators.entry(creator.user_id).or_insert(creator);
+        }
+
+        for song in songs {
+            self.songs.entry(song.song_id).or_insert(song);
+        }
+
+        let before = self.levels.len();
+
+        for level in levels {
+            if self.level_ids.insert(level.level_id) {
+                self.levels.push(level);
+            }
+        }
+
+        self.exhausted = !page_info.has_more();
+        self.page_info = Some(page_info);
+
+        Ok(self.levels.len() - before)
+    }
+
+    /// Whether further pages with levels exist, as far as can be told from the responses ingested
+    /// so far
+    ///
+    /// This is `true` before the first response was ingested.
+    pub fn has_more(&self) -> bool {
+        !self.exhausted
+    }
+
+    /// The page info of the most recently ingested response
+    pub fn page_info(&self) -> Option<PageInfo> {
+        self.page_info
+    }
+
+    /// The number of distinct levels ingested so far
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns `true` iff no levels were ingested so far
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Associates all ingested levels with their creators and songs, in the order in which they
+    /// were first encountered
+    pub fn into_levels(self) -> Vec<ListedLevel<'a>> {
+        let LevelsAccumulator {
+            levels, creators, songs, ..
+        } = self;
+
+        levels
+            .into_iter()
+            .map(|level| {
+                let song = level.custom_song.and_then(|song_id| songs.get(&song_id).cloned());
+                let creator = creators.get(&level.creator).cloned();
+
+                level.map_parts(|_| song, |_| creator)
+            })
+            .collect()
+    }
+}
+
+pub fn parse_download_gj_level_response(response: &str) -> Result<Level<'_>, ResponseError<'_>> {
+    endpoint_span!(DOWNLOAD_LEVEL_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    Ok(Level::from_gj_str(ResponseSections::new(response).require(0)?)?)
+}
+
+/// Parses the response to a [`LevelRequest::daily`](crate::request::level::LevelRequest::daily) or
+/// [`LevelRequest::weekly`](crate::request::level::LevelRequest::weekly) request
+///
+/// Next to the level, whose [`LevelData::daily_index`](crate::model::level::LevelData::daily_index)
+/// will be set, these responses contain an additional section describing the level's creator.
+pub fn parse_download_gj_special_level_response(response: &str) -> Result<(Level<'_>, Option<Creator<'_>>), ResponseError<'_>> {
+    endpoint_span!(DOWNLOAD_LEVEL_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    // Format is level#hash#hash#creator
+    let sections = ResponseSections::new(response);
+
+    let level = Level::from_gj_str(sections.require(0)?)?;
+    let creator = match sections.section(3) {
+        Some(creator) if !creator.is_empty() => Some(Creator::from_gj_str(creator)?),
+        _ => None,
+    };
+
+    Ok((level, creator))
+}
+
+pub fn parse_get_gj_user_info_response(response: &str) -> Result<Profile<'_>, ResponseError<'_>> {
+    endpoint_span!(GET_USER_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    Ok(Profile::from_gj_str(response)?)
+}
+
+pub fn parse_get_gj_users_response(response: &str) -> Result<SearchedUser<'_>, ResponseError<'_>> {
+    endpoint_span!(SEARCH_USER_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    let sections = ResponseSections::new(response);
+
+    // In the past this used to be a paginating endpoint which performed an infix search on the user
+    // name. Now, it performs a full match, and since account names are unique, this endpoint returns at
+    // most one object anymore.
+    Ok(SearchedUser::from_gj_str(sections.require(0)?)?)
+}
+
+/// Parses the response to a `getGJComments` request into the comments on the requested page and
+/// the [`PageInfo`] needed to request further pages
+pub fn parse_get_gj_comments_response(response: &str) -> Result<(Vec<LevelComment<'_>>, PageInfo), ResponseError<'_>> {
+    endpoint_span!(LEVEL_COMMENTS_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    let sections = ResponseSections::new(response);
+
+    // The format here is very weird. We have a '|' separated list of (comment, user) pairs, and said
+    // pair is separated by a ':'
+
+    let comments = fragments(sections.require(0)?)
+        .map(|fragment| {
+            let mut parts = fragment.split(':');
+
+            if let (Some(raw_comment), Some(raw_user)) = (parts.next(), parts.next()) {
+                let mut comment = LevelComment::from_gj_str(raw_comment)?;
+
+                comment.user = if raw_user == "1~~9~~10~~11~~14~~15~~16~" {
+                    None
+                } else {
+                    Some(CommentUser::from_gj_str(raw_user)?)
+                };
+
+                Ok(comment)
+            } else {
+                Err(ResponseError::UnexpectedFormat)
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    let page_info = PageInfo::from_section(sections.require(1)?)?;
+
+    Ok((comments, page_info))
+}
+
+pub fn parse_get_gj_acccount_comments_response(response: &str) -> Result<Vec<ProfileComment<'_>>, ResponseError<'_>> {
+    endpoint_span!(PROFILE_COMMENT_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    fragments(ResponseSections::new(response).require(0)?)
+        .map(|fragment| Ok(ProfileComment::from_gj_str(fragment)?))
+        .collect()
+}
+
+/// Parses the response to an `uploadGJLevel` request into the ID of the uploaded level
+pub fn parse_upload_gj_level_response(response: &str) -> Result<u64, ResponseError<'_>> {
+    endpoint_span!(UPLOAD_LEVEL_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    response.parse().map_err(|_| ResponseError::UnexpectedFormat)
+}
+
+/// Parses the response to an `uploadGJComment` request into the ID of the newly posted comment
+pub fn parse_upload_gj_comment_response(response: &str) -> Result<u64, ResponseError<'_>> {
+    endpoint_span!(UPLOAD_COMMENT_ENDPOINT);
+
+    parse_comment_upload(response)
+}
+
+/// Parses the response to an `uploadGJAccComment` request into the ID of the newly posted comment
+pub fn parse_upload_gj_acc_comment_response(response: &str) -> Result<u64, ResponseError<'_>> {
+    endpoint_span!(UPLOAD_ACCOUNT_COMMENT_ENDPOINT);
+
+    parse_comment_upload(response)
+}
+
+/// Parses the response to a `deleteGJAccComment` request, which is `1` on success
+pub fn parse_delete_gj_acc_comment_response(response: &str) -> Result<(), ResponseError<'_>> {
+    endpoint_span!(DELETE_ACCOUNT_COMMENT_ENDPOINT);
+
+    match response {
+        "1" => Ok(()),
+        "-1" => Err(ResponseError::NotFound),
+        _ => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a `deleteGJLevelUser20` request, which is `1` on success
+pub fn parse_delete_gj_level_user_response(response: &str) -> Result<(), ResponseError<'_>> {
+    endpoint_span!(DELETE_LEVEL_ENDPOINT);
+
+    match response {
+        "1" => Ok(()),
+        "-1" => Err(ResponseError::NotFound),
+        _ => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a `updateGJDesc20` request, which is `1` on success
+pub fn parse_update_gj_desc_response(response: &str) -> Result<(), ResponseError<'_>> {
+    endpoint_span!(UPDATE_DESCRIPTION_ENDPOINT);
+
+    match response {
+        "1" => Ok(()),
+        "-1" => Err(ResponseError::NotFound),
+        _ => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a `getAccountURL` request, which is the host of the server holding the
+/// account's cloud save (see [`account_base_url`](crate::request::account::account_base_url))
+pub fn parse_get_account_url_response(response: &str) -> Result<&str, ResponseError<'_>> {
+    endpoint_span!(ACCOUNT_URL_ENDPOINT);
+
+    match response {
+        "-1" => Err(ResponseError::NotFound),
+        "" => Err(ResponseError::UnexpectedFormat),
+        _ => Ok(response),
+    }
+}
+
+/// Parses the response to a `backupGJAccountNew` request, which is `1` on success
+pub fn parse_backup_gj_account_response(response: &str) -> Result<(), ResponseError<'_>> {
+    endpoint_span!(BACKUP_ACCOUNT_ENDPOINT);
+
+    match response {
+        "1" => Ok(()),
+        "-1" => Err(ResponseError::NotFound),
+        _ => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a `syncGJAccountNew` request
+///
+/// ## GD Internals:
+/// The response consists of the two parts of the [`SaveData`], followed by the game and binary
+/// versions of the client that made the backup, all separated by `;`. Any further sections are
+/// ignored.
+pub fn parse_sync_gj_account_response(response: &str) -> Result<SyncedAccount<'_>, ResponseError<'_>> {
+    endpoint_span!(SYNC_ACCOUNT_ENDPOINT);
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    let mut sections = response.split(';');
+
+    let game_manager = section!(sections);
+    let local_levels = section!(sections);
+    let mut version = || {
+        section!(sections)
+            .parse::<u8>()
+            .map(GameVersion::from)
+            .map_err(|_| ResponseError::UnexpectedFormat)
+    };
+
+    Ok(SyncedAccount {
+        save_data: SaveData {
+            game_manager: Cow::Borrowed(game_manager),
+            local_levels: Cow::Borrowed(local_levels),
+        },
+        game_version: version()?,
+        binary_version: version()?,
+    })
+}
+
+/// Parses the response to a `requestUserAccess` request, which is the moderator level of the
+/// account on success
+pub fn parse_request_user_access_response(response: &str) -> Result<ModLevel, ResponseError<'_>> {
+    endpoint_span!(REQUEST_MOD_ENDPOINT);
+
+    match response {
+        "-1" => Err(ResponseError::NotFound),
+        _ => response
+            .parse::<u8>()
+            .map(ModLevel::from)
+            .map_err(|_| ResponseError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a `suggestGJStars20` request, which is `1` on success
+///
+/// ## GD Internals:
+/// The servers respond with `-2` if the account is not a moderator, which is reported as
+/// [`ResponseError::NotFound`], same as a general failure (`-1`).
+pub fn parse_suggest_gj_stars_response(response: &str) -> Result<(), ResponseError<'_>> {
+    endpoint_span!(SUGGEST_STARS_ENDPOINT);
+
+    match response {
+        "1" => Ok(()),
+        "-1" | "-2" => Err(ResponseError::NotFound),
+        _ => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+fn parse_comment_upload(response: &str) -> Result<u64, ResponseError<'_>> {
+    if let Some(ban) = CommentBan::from_response(response) {
+        return Err(ResponseError::CommentBan(ban));
+    }
+
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    response.parse().map_err(|_| ResponseError::UnexpectedFormat)
+}
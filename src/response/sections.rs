@@ -0,0 +1,97 @@
+//! Module containing helpers for splitting server responses into their sections, and sections into
+//! the fragments describing individual objects
+//!
+//! ## GD Internals:
+//! Responses containing multiple kinds of objects separate them into sections using `#`. Within a
+//! section, objects are separated using `|`, except for songs, whose own format already uses `~|~`
+//! as its delimiter, and which are therefore separated using `~:~`. For example, a `getGJLevels21.php` response has the layout
+//! `levels#creators#songs#page_info#hash`.
+
+use crate::response::{PageInfo, ResponseError};
+
+/// Splits a section into the fragments describing the individual objects it contains
+///
+/// Empty fragments, which the servers produce for example for empty creator sections, are
+/// included.
+pub fn fragments(section: &str) -> impl Iterator<Item = &str> + Clone {
+    section.split('|')
+}
+
+/// Splits a section of songs into the fragments describing the individual songs, skipping empty
+/// fragments
+pub fn song_fragments(section: &str) -> impl Iterator<Item = &str> + Clone {
+    section.split("~:~").filter(|fragment| !fragment.is_empty())
+}
+
+/// The `#`-separated sections of a server response
+///
+/// Splitting happens lazily on each access, so constructing this does not allocate. The typed
+/// accessors assume the layout of `getGJLevels21.php` responses (see the [module level
+/// documentation](self)). Other responses can be accessed via [`ResponseSections::section`].
+///
+/// ```
+/// # use dash_rs::response::sections::ResponseSections;
+/// let sections = ResponseSections::new("1:1|2:2#4:a|#1~|~3#9999:0:10#abc");
+///
+/// assert_eq!(sections.levels().unwrap().collect::<Vec<_>>(), ["1:1", "2:2"]);
+/// assert_eq!(sections.creators().unwrap().collect::<Vec<_>>(), ["4:a"]);
+/// assert_eq!(sections.page_info().unwrap().total, 9999);
+/// assert_eq!(sections.hash().unwrap(), "abc");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResponseSections<'a> {
+    response: &'a str,
+}
+
+impl<'a> ResponseSections<'a> {
+    pub const fn new(response: &'a str) -> Self {
+        ResponseSections { response }
+    }
+
+    /// Returns an iterator over all sections, in the order in which they appear in the response
+    pub fn iter(&self) -> impl Iterator<Item = &'a str> + Clone {
+        self.response.split('#')
+    }
+
+    /// The section at the given index, if the response has that many sections
+    pub fn section(&self, index: usize) -> Option<&'a str> {
+        self.iter().nth(index)
+    }
+
+    /// Like [`ResponseSections::section`], but fails with [`ResponseError::UnexpectedFormat`] if
+    /// the response does not have that many sections
+    pub fn require(&self, index: usize) -> Result<&'a str, ResponseError<'a>> {
+        self.section(index).ok_or(ResponseError::UnexpectedFormat)
+    }
+
+    /// The fragments of the level section, the first one
+    pub fn levels(&self) -> Result<impl Iterator<Item = &'a str> + Clone, ResponseError<'a>> {
+        self.require(0).map(fragments)
+    }
+
+    /// The fragments of the creator section, the second one
+    ///
+    /// Empty fragments are skipped, as the section is empty if none of the levels has a known
+    /// creator.
+    pub fn creators(&self) -> Result<impl Iterator<Item = &'a str> + Clone, ResponseError<'a>> {
+        self.require(1)
+            .map(|section| fragments(section).filter(|fragment| !fragment.is_empty()))
+    }
+
+    /// The fragments of the song section, the third one
+    pub fn songs(&self) -> Result<impl Iterator<Item = &'a str> + Clone, ResponseError<'a>> {
+        self.require(2).map(song_fragments)
+    }
+
+    /// The page info, contained in the fourth section
+    pub fn page_info(&self) -> Result<PageInfo, ResponseError<'a>> {
+        PageInfo::from_section(self.require(3)?)
+    }
+
+    /// The hash of the response's contents, contained in the fifth section
+    ///
+    /// See [`verify_get_gj_levels_response`](crate::response::verify_get_gj_levels_response).
+    pub fn hash(&self) -> Result<&'a str, ResponseError<'a>> {
+        self.require(4)
+    }
+}
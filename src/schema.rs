@@ -0,0 +1,119 @@
+//! Module for describing the RobTop formats of dash-rs' models
+//!
+//! For models whose [`Dash`] implementation is derived, the descriptions are generated from the
+//! same metadata that drives (de)serialization, so they always match what dash-rs actually reads
+//! and writes. Levels and level objects implement [`Dash`] by hand, and their descriptions are
+//! maintained by hand alongside their internal representations. In either case,
+//! [`Dash::INDICES`] is extracted from the descriptions, so the two cannot disagree.
+//!
+//! The descriptions are meant for generating documentation (e.g. index tables of the formats) and
+//! for private server implementations that need to know which indices to provide.
+//!
+//! [`FormatSchema`] implements [`Serialize`], so a schema can be exported as JSON via `serde_json`:
+//!
+//! ```
+//! use dash_rs::{model::creator::Creator, schema};
+//!
+//! let json = serde_json::to_string(&schema::describe::<Creator>()).unwrap();
+//!
+//! assert!(json.contains(r#""index":"1","field":"user_id","rust_type":"u64""#));
+//! ```
+
+use crate::Dash;
+use serde::Serialize;
+
+/// Description of the format of a single model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FormatSchema {
+    /// The name of the Rust type modelling this format, as given by [`core::any::type_name`]
+    ///
+    /// Like the output of `type_name`, this is meant for human consumption only and its exact
+    /// contents are not stable.
+    pub model: &'static str,
+
+    /// Descriptions of all indices of this format, in the order in which they are serialized
+    pub fields: &'static [FieldSchema],
+}
+
+impl FormatSchema {
+    /// Gets the description of the field at the given index, if this format has such an index
+    pub fn field(&self, index: &str) -> Option<&'static FieldSchema> {
+        self.fields.iter().find(|field| field.index == index)
+    }
+}
+
+/// Description of a single index of a model's format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FieldSchema {
+    /// The index in RobTop's format
+    pub index: &'static str,
+
+    /// The name of the field of the Rust model the value at this index ends up in
+    ///
+    /// Some indices of the level format contribute to the same field (e.g. the difficulty of a
+    /// level is spread over multiple indices), in which case multiple descriptions have the same
+    /// field name. Fields of nested structs are given as `outer.inner`.
+    pub field: &'static str,
+
+    /// The type of said field, as written in dash-rs' source code
+    pub rust_type: &'static str,
+
+    /// Whether the field is optional, i.e. an [`Option`]
+    pub optional: bool,
+
+    /// If the value at this index is wrapped in a [`Thunk`](crate::Thunk), the
+    /// [`ThunkProcessor`](crate::ThunkProcessor) used to process it
+    pub processor: Option<&'static str>,
+}
+
+impl FieldSchema {
+    /// Describes a non-optional field that is not wrapped in a thunk. Used for models whose [`Dash`]
+    /// implementation is not derived.
+    pub(crate) const fn new(index: &'static str, field: &'static str, rust_type: &'static str) -> Self {
+        FieldSchema {
+            index,
+            field,
+            rust_type,
+            optional: false,
+            processor: None,
+        }
+    }
+
+    pub(crate) const fn optional(self) -> Self {
+        FieldSchema { optional: true, ..self }
+    }
+
+    pub(crate) const fn processed_by(self, processor: &'static str) -> Self {
+        FieldSchema {
+            processor: Some(processor),
+            ..self
+        }
+    }
+}
+
+/// Extracts the indices from the given field descriptions, in order
+///
+/// This is how [`Dash::INDICES`] is derived from [`Dash::FIELDS`], so that the indices of a model
+/// only need to be listed once. `N` has to be the number of fields, which is checked when the
+/// containing constant is evaluated.
+pub(crate) const fn indices<const N: usize>(fields: &[FieldSchema]) -> [&'static str; N] {
+    assert!(fields.len() == N, "number of indices does not match the number of fields");
+
+    let mut indices = [""; N];
+    let mut i = 0;
+
+    while i < N {
+        indices[i] = fields[i].index;
+        i += 1;
+    }
+
+    indices
+}
+
+/// Describes the RobTop format of the given model
+pub fn describe<'de, T: Dash<'de>>() -> FormatSchema {
+    FormatSchema {
+        model: core::any::type_name::<T>(),
+        fields: T::FIELDS,
+    }
+}
@@ -0,0 +1,89 @@
+//! Module containing support for processing [`Thunk`]s into a caller-supplied [`Bump`] arena
+//!
+//! Processing thunks normally allocates a fresh [`String`](alloc::string::String) for every value
+//! whose processed form cannot borrow from the input (e.g. base64 encoded level descriptions, or
+//! decompressed level data). In batch pipelines, which process many objects and then discard them,
+//! these allocations dominate. With an arena, processed values instead borrow from memory allocated
+//! in the arena, which is freed all at once by [resetting](Bump::reset) or dropping it.
+
+use alloc::borrow::Cow;
+use base64::{DecodeError, DecodeSliceError};
+use bumpalo::{collections::Vec as BumpVec, Bump};
+use percent_encoding::percent_decode_str;
+
+use crate::serde::{Base64Decoder, PercentDecoder, PercentEncodeSet, ProcessError, Thunk, ThunkProcessor};
+
+/// Extension of [`ThunkProcessor`] for processors that can allocate their output in a [`Bump`]
+/// arena
+///
+/// Thunks using such processors can be processed via [`Thunk::process_in`]:
+///
+/// ```
+/// # use dash_rs::{bumpalo::Bump, PercentDecoder, Thunk};
+/// let arena = Bump::new();
+/// let mut thunk: Thunk<PercentDecoder> = Thunk::Unprocessed("Hello%20World".into());
+///
+/// assert_eq!(thunk.process_in(&arena).unwrap(), "Hello World");
+/// ```
+pub trait ArenaProcessor: ThunkProcessor {
+    /// Processes `unprocessed` like [`ThunkProcessor::from_unprocessed`], but allocates the memory
+    /// the output needs in `arena`
+    fn from_unprocessed_in<'a>(unprocessed: &'a str, arena: &'a Bump) -> Result<Self::Output<'a>, Self::Error>;
+}
+
+impl<'a, C: ArenaProcessor> Thunk<'a, C> {
+    /// Like [`Thunk::process`], but allocates the processed value in the given arena
+    ///
+    /// If this is a [`Thunk::Unprocessed`] variant owning its data, the data is copied into the
+    /// arena before processing.
+    pub fn process_in(&mut self, arena: &'a Bump) -> Result<&mut C::Output<'a>, C::Error> {
+        if let Thunk::Unprocessed(raw_data) = self {
+            let raw_data = match *raw_data {
+                Cow::Borrowed(raw_data) => raw_data,
+                Cow::Owned(ref raw_data) => arena.alloc_str(raw_data),
+            };
+
+            *self = Thunk::Processed(C::from_unprocessed_in(raw_data, arena)?);
+        }
+
+        match self {
+            Thunk::Processed(p) => Ok(p),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Decodes URL-safe base64 into a buffer allocated in `arena`
+pub(crate) fn decode_base64_in<'a>(encoded: &str, arena: &'a Bump) -> Result<&'a [u8], DecodeError> {
+    let mut buffer = BumpVec::new_in(arena);
+    buffer.resize(base64::decoded_len_estimate(encoded.len()), 0);
+
+    match crate::util::base64_decode_slice(encoded, &mut buffer) {
+        Ok(length) => buffer.truncate(length),
+        Err(DecodeSliceError::DecodeError(err)) => return Err(err),
+        // The estimate never underestimates the decoded length
+        Err(DecodeSliceError::OutputSliceTooSmall) => unreachable!(),
+    }
+
+    Ok(buffer.into_bump_slice())
+}
+
+impl ArenaProcessor for Base64Decoder {
+    fn from_unprocessed_in<'a>(unprocessed: &'a str, arena: &'a Bump) -> Result<Cow<'a, str>, ProcessError> {
+        let decoded = decode_base64_in(unprocessed, arena)?;
+
+        Ok(Cow::Borrowed(core::str::from_utf8(decoded)?))
+    }
+}
+
+impl<S: PercentEncodeSet> ArenaProcessor for PercentDecoder<S> {
+    fn from_unprocessed_in<'a>(unprocessed: &'a str, arena: &'a Bump) -> Result<Cow<'a, str>, ProcessError> {
+        if !unprocessed.contains('%') {
+            return Ok(Cow::Borrowed(unprocessed));
+        }
+
+        let decoded = BumpVec::from_iter_in(percent_decode_str(unprocessed), arena).into_bump_slice();
+
+        Ok(Cow::Borrowed(core::str::from_utf8(decoded)?))
+    }
+}
@@ -0,0 +1,135 @@
+//! Module containing support for deserializing byte input that might not be valid UTF-8
+//!
+//! Some private servers do not validate user provided strings, meaning their responses can
+//! contain invalid UTF-8 (most commonly in level names). Since dash-rs' models borrow from `&str`
+//! input, such byte input first needs to be converted into a string, which is what [`GJBytes`]
+//! does on a per-field basis.
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+};
+
+use itoa::Buffer;
+
+use crate::{
+    serde::{de::error::Error, GJFormat},
+    split::{Delimiter, DynamicDelimiter},
+};
+
+/// How invalid UTF-8 in byte input is handled, see [`GJBytes::decode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf8Policy<'p> {
+    /// Invalid UTF-8 in any field causes an error
+    Strict,
+
+    /// Invalid UTF-8 sequences in any value are replaced with `U+FFFD REPLACEMENT CHARACTER`
+    Lossy,
+
+    /// Invalid UTF-8 sequences are replaced only in the values of the given fields, and cause an
+    /// error in all other fields
+    ///
+    /// For map-like formats, fields are identified by their index. Otherwise, fields are identified
+    /// by their 1-based position, which is the index given to them in `#[dash(index = ...)]`
+    LossyFields(&'p [&'p str]),
+}
+
+impl Utf8Policy<'_> {
+    fn allows_replacement(&self, field: &str) -> bool {
+        match self {
+            Utf8Policy::Strict => false,
+            Utf8Policy::Lossy => true,
+            Utf8Policy::LossyFields(fields) => fields.contains(&field),
+        }
+    }
+}
+
+/// Byte input in one of RobTop's data formats, converted to UTF-8 according to some
+/// [`Utf8Policy`]
+///
+/// If the input is valid UTF-8, no allocation happens and models parsed via [`GJBytes::parse`]
+/// borrow from the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GJBytes<'a> {
+    data: Cow<'a, str>,
+}
+
+impl<'a> GJBytes<'a> {
+    /// Converts `input`, which is expected to be in the data format of `T`, into UTF-8
+    ///
+    /// Fails if `input` contains invalid UTF-8 in some field for which `policy` does not allow
+    /// replacement. Indices of map-like formats are never replaced.
+    pub fn decode<T: GJFormat<'a>>(input: &'a [u8], policy: Utf8Policy<'_>) -> Result<Self, Error<'a>> {
+        decode(input, T::DELIMITER, T::MAP_LIKE, policy).map(|data| GJBytes { data })
+    }
+
+    /// Returns `true` iff some invalid UTF-8 sequence had to be replaced while decoding
+    pub fn is_lossy(&self) -> bool {
+        matches!(self.data, Cow::Owned(_))
+    }
+
+    /// The decoded input
+    pub fn as_str(&self) -> &str {
+        &self.data
+    }
+
+    /// Parses the decoded input
+    pub fn parse<'b, T: GJFormat<'b>>(&'b self) -> Result<T, Error<'b>> {
+        T::from_gj_str(&self.data)
+    }
+}
+
+pub(crate) fn decode<'a>(
+    input: &'a [u8], delimiter: &'static str, map_like: bool, policy: Utf8Policy<'_>,
+) -> Result<Cow<'a, str>, Error<'a>> {
+    if let Ok(input) = core::str::from_utf8(input) {
+        return Ok(Cow::Borrowed(input));
+    }
+
+    let mut decoded = String::with_capacity(input.len());
+    let mut index = None;
+    let mut start = 0;
+    let searcher = DynamicDelimiter::new(delimiter);
+
+    for position in 0.. {
+        let end = searcher.find(&input[start..]).map(|offset| start + offset);
+        let token = &input[start..end.unwrap_or(input.len())];
+        let is_index = map_like && position % 2 == 0;
+
+        match core::str::from_utf8(token) {
+            Ok(token) if is_index => index = Some(token),
+            Ok(_) => (),
+            Err(error) => {
+                let mut buffer = Buffer::new();
+                let field = match (map_like, index) {
+                    (true, _) if is_index => None,
+                    (true, index) => index,
+                    (false, _) => Some(buffer.format(position + 1)),
+                };
+
+                if !field.is_some_and(|field| policy.allows_replacement(field)) {
+                    return Err(Error::Custom {
+                        message: "invalid UTF-8".to_owned(),
+                        index: if is_index { None } else { index },
+                        value: None,
+                        position: Some(start),
+                        source: Some(Box::new(error)),
+                    });
+                }
+            },
+        }
+
+        decoded.push_str(&String::from_utf8_lossy(token));
+
+        match end {
+            Some(end) => {
+                decoded.push_str(delimiter);
+                start = end + delimiter.len();
+            },
+            None => break,
+        }
+    }
+
+    Ok(Cow::Owned(decoded))
+}
@@ -1,6 +1,10 @@
 //! Module containing the error type for deserialization errors
 
-use std::fmt::Display;
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::fmt::Display;
 
 use thiserror::Error;
 
@@ -31,6 +35,26 @@ pub enum Error<'de> {
         /// Not available if the error is not related to any value (for instance if the format
         /// itself was malformed).
         value: Option<&'de str>,
+
+        /// The byte offset into the input at which the value that caused the error starts (or, if
+        /// the error is not related to any value, the offset of the index)
+        position: Option<usize>,
+
+        /// The underlying error, if the error was caused by some other error (for instance a
+        /// [`ParseIntError`](core::num::ParseIntError) when parsing a number)
+        #[source]
+        source: Option<Box<dyn core::error::Error + Send + Sync>>,
+    },
+
+    /// An index occurred more than once in map-like input, and the deserializer was configured to
+    /// reject duplicates (see [`DuplicateIndexPolicy::Error`](crate::serde::DuplicateIndexPolicy::Error))
+    #[error("duplicate index {index}")]
+    DuplicateIndex {
+        /// The duplicated index
+        index: &'de str,
+
+        /// The byte offset into the input of the index's second occurrence
+        position: Option<usize>,
     },
 
     /// A given [`Deserializer`](serde::Deserializer) function was not supported
@@ -47,6 +71,38 @@ impl serde::de::Error for Error<'_> {
             message: msg.to_string(),
             index: None,
             value: None,
+            position: None,
+            source: None,
+        }
+    }
+}
+
+impl<'de> Error<'de> {
+    /// The index of the field whose deserialization failed, if known
+    pub fn index(&self) -> Option<&'de str> {
+        match self {
+            Error::Custom { index, .. } => *index,
+            Error::DuplicateIndex { index, .. } => Some(index),
+            _ => None,
+        }
+    }
+
+    /// The value whose deserialization failed, if known
+    pub fn offending_value(&self) -> Option<&'de str> {
+        match self {
+            Error::Custom { value, .. } => *value,
+            _ => None,
+        }
+    }
+
+    /// The byte offset into the input at which the error occurred, if known
+    ///
+    /// This is the start of the [offending value](Error::offending_value) if there is one, and the
+    /// start of the [index](Error::index) otherwise.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            Error::Custom { position, .. } | Error::DuplicateIndex { position, .. } => *position,
+            _ => None,
         }
     }
 }
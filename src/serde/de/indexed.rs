@@ -1,31 +1,54 @@
 //! Module containing the deserializer for robtop's indexed data format
 
 use super::error::Error;
+use crate::split::{Delimiter, DynamicDelimiter, Split};
+use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, string::ToString, vec::Vec};
 use serde::{
     de,
-    de::{DeserializeSeed, Visitor},
+    de::{value::BorrowedStrDeserializer, DeserializeSeed, Visitor},
     Deserializer,
 };
-use std::str::Split;
 
 // Special versions of the trace and debug macros used in this module that are statically disabled
 // in release mode. We do not want to explicitly pass "release_max_level_off" feature to log because
 // we're in a library crate, and since features are additive, that would turn off release mode
 // logging in every crate that depends on dash-rs.
+//
+// With the `tracing` feature, events are emitted through `tracing` instead. Debug events are then also
+// emitted in release mode, as they are rare and `tracing` subscribers can cheaply filter them at runtime.
 macro_rules! trace {
     ($($t:tt)*) => {
-        #[cfg(debug_assertions)]
-        log::trace!($($t)*)
+        #[cfg(all(debug_assertions, not(feature = "tracing")))]
+        log::trace!($($t)*);
+        #[cfg(all(debug_assertions, feature = "tracing"))]
+        tracing::trace!($($t)*);
     };
 }
 
 macro_rules! debug {
     ($($t:tt)*) => {
-        #[cfg(debug_assertions)]
-        log::debug!($($t)*)
+        #[cfg(all(debug_assertions, not(feature = "tracing")))]
+        log::debug!($($t)*);
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($t)*);
     };
 }
 
+/// Policy for handling indices that occur more than once in map-like input
+///
+/// Some (private server) responses repeat indices, potentially with conflicting values.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DuplicateIndexPolicy {
+    /// Only the first occurrence of an index is deserialized, later ones are skipped
+    FirstWins,
+
+    /// Only the last occurrence of an index is deserialized, earlier ones are skipped
+    LastWins,
+
+    /// Duplicate indices cause deserialization to fail with [`Error::DuplicateIndex`]
+    Error,
+}
+
 /// Deserializer for RobTop's indexed data format
 ///
 /// This format is used in server responses and when storing model.level data. It is based around
@@ -35,17 +58,34 @@ macro_rules! debug {
 /// There are two variants of this format:
 ///
 /// * **Map-like**: Every second field is an key, which is almost always an integer. This key is
-/// unique and tells us which field follows.
+///   unique and tells us which field follows.
 /// * **List-like**: There are no keys, identification of
-/// fields has to occur based on the how many-th field they are. In this case the deserializer
-/// generates artificial indices (which just count up by 1 for each field) for error messages.
+///   fields has to occur based on the how many-th field they are. In this case the deserializer
+///   generates artificial indices (which just count up by 1 for each field) for error messages.
+///
+/// The type parameter determines how the delimiter is searched for. Deserializers constructed via
+/// [`IndexedDeserializer::new`] decide this at runtime, while
+/// [`GJFormat::from_gj_str`](crate::GJFormat::from_gj_str) uses delimiter types specialized to the
+/// format's [`DELIMITER`](crate::GJFormat::DELIMITER).
 #[derive(Debug)]
-pub struct IndexedDeserializer<'de> {
+pub struct IndexedDeserializer<'de, D = DynamicDelimiter> {
     map_like: bool,
-    splitter: Split<'de, &'de str>,
+    splitter: Split<'de, D>,
     input: &'de str,
     end_of_current_token: usize,
-    delimiter: &'de str,
+
+    /// The two most recently consumed tokens, most recent first.
+    ///
+    /// These are only needed to provide context in error messages. Keeping track of them here is
+    /// much cheaper than re-splitting the already consumed part of the input every time we need
+    /// them.
+    last_tokens: [Option<&'de str>; 2],
+
+    /// How to handle indices occurring more than once in map-like input.
+    ///
+    /// If not set, duplicates are passed on to the visitor, which for derived implementations
+    /// generally means an error.
+    duplicate_index_policy: Option<DuplicateIndexPolicy>,
 }
 
 impl<'de> IndexedDeserializer<'de> {
@@ -56,18 +96,35 @@ impl<'de> IndexedDeserializer<'de> {
     /// * *delimiter*: The delimiter separating the individual fields
     /// * *map_like*: Whether the input is in map-like format or not (meaning it is in list-like
     ///   format)
+    ///
+    /// # Panics
+    /// Panics if `delimiter` is empty
     pub fn new(source: &'de str, delimiter: &'static str, map_like: bool) -> Self {
-        trace!("Deserializing {} with delimiter '{}', maplike {}", source, delimiter, map_like);
+        IndexedDeserializer::with_delimiter(source, DynamicDelimiter::new(delimiter), map_like)
+    }
+}
+
+impl<'de, D: Delimiter> IndexedDeserializer<'de, D> {
+    /// Constructs a new `IndexedDeserializer` splitting the input at the given [`Delimiter`]
+    pub fn with_delimiter(source: &'de str, delimiter: D, map_like: bool) -> Self {
+        trace!("Deserializing {} with delimiter {:?}, maplike {}", source, delimiter, map_like);
 
         IndexedDeserializer {
-            splitter: source.split(delimiter),
+            splitter: Split::new(source, delimiter),
             map_like,
             input: source,
             end_of_current_token: source.as_ptr() as usize,
-            delimiter,
+            last_tokens: [None, None],
+            duplicate_index_policy: None,
         }
     }
 
+    /// Sets the policy for handling indices that occur more than once in map-like input
+    pub fn with_duplicate_index_policy(mut self, policy: DuplicateIndexPolicy) -> Self {
+        self.duplicate_index_policy = Some(policy);
+        self
+    }
+
     /// Returns the next token in the input string and consumes it.
     ///
     /// If the input string has already been fully consumed, returns [`Error::Eof`]. If the
@@ -76,6 +133,7 @@ impl<'de> IndexedDeserializer<'de> {
     fn consume_token(&mut self) -> Option<&'de str> {
         let tok = self.splitter.next()?;
         self.end_of_current_token = tok.as_ptr() as usize + tok.len();
+        self.last_tokens = [Some(tok), self.last_tokens[0]];
 
         trace!("Splitting off token {}, remaining input: {}", tok, &self.input[self.position()..]);
 
@@ -86,17 +144,46 @@ impl<'de> IndexedDeserializer<'de> {
         self.end_of_current_token - self.input.as_ptr() as usize
     }
 
+    /// Returns the byte offset of the given token into the input, or [`None`] if it is not a
+    /// sub-slice of the input
+    fn offset_of(&self, token: &str) -> Option<usize> {
+        let offset = (token.as_ptr() as usize).checked_sub(self.input.as_ptr() as usize)?;
+
+        (offset + token.len() <= self.input.len()).then_some(offset)
+    }
+
+    /// Returns the `nth` most recently consumed token, where `nth` is either `1` or `2`
     fn nth_last(&self, nth: usize) -> Option<&'de str> {
-        self.input[..self.position()].rsplit(self.delimiter).nth(nth - 1)
+        self.last_tokens.get(nth - 1).copied().flatten()
     }
 
+    /// Returns whether the token following the current one is the empty string (or whether there is
+    /// no next token at all)
     fn is_next_empty(&self) -> bool {
-        &self.input[self.position() + self.delimiter.len()..self.position() + 2 * self.delimiter.len()] == self.delimiter
+        let delimiter = self.splitter.delimiter().as_bytes();
+
+        match self.input.get(self.position() + delimiter.len()..) {
+            Some(rest) => rest.is_empty() || rest.as_bytes().starts_with(delimiter),
+            None => true,
+        }
     }
 
     fn is_eof(&self) -> bool {
         self.input.len() <= self.position()
     }
+
+    /// Returns the offset of the last occurrence of every index in the remaining input, assuming
+    /// the most recently consumed token was the given index (which is included)
+    fn last_index_positions(&self, index: &'de str) -> BTreeMap<&'de str, usize> {
+        let rest = &self.input[self.position() - index.len()..];
+
+        // Every second token starting from the current one is an index. Collecting into a map keeps
+        // the position of the last occurrence of each one
+        Split::new(rest, self.splitter.delimiter())
+            .step_by(2)
+            .map(|index| (index, index.as_ptr() as usize - self.input.as_ptr() as usize))
+            .collect()
+    }
 }
 
 macro_rules! delegate_to_from_str {
@@ -121,13 +208,15 @@ macro_rules! delegate_to_from_str {
                     message: error.to_string(),
                     index: None,
                     value: Some(token),
+                    position: self.offset_of(token),
+                    source: Some(Box::new(error)),
                 }),
             }
         }
     };
 }
 
-impl<'a, 'de> Deserializer<'de> for &'a mut IndexedDeserializer<'de> {
+impl<'de, D: Delimiter> Deserializer<'de> for &mut IndexedDeserializer<'de, D> {
     type Error = Error<'de>;
 
     delegate_to_from_str!(deserialize_i8, visit_i8);
@@ -179,6 +268,8 @@ impl<'a, 'de> Deserializer<'de> for &'a mut IndexedDeserializer<'de> {
                 message: "Expected 0, 1, 2, 10 or the empty string".to_owned(),
                 index: None,
                 value: Some(value),
+                position: self.offset_of(value),
+                source: None,
             }),
         }
     }
@@ -271,6 +362,8 @@ impl<'a, 'de> Deserializer<'de> for &'a mut IndexedDeserializer<'de> {
         visitor.visit_seq(SeqAccess {
             deserializer: self,
             index: 0,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
         })
     }
 
@@ -292,7 +385,13 @@ impl<'a, 'de> Deserializer<'de> for &'a mut IndexedDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_map(MapAccess { deserializer: self })
+        visitor.visit_map(MapAccess {
+            deserializer: self,
+            seen_indices: Vec::new(),
+            last_index_positions: None,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
+        })
     }
 
     fn deserialize_struct<V>(
@@ -301,8 +400,13 @@ impl<'a, 'de> Deserializer<'de> for &'a mut IndexedDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        // The index field is recorded by our SeqAccess/MapAccess implementations whenever they start
+        // deserializing a new field
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("deserialize", model = _name, index = tracing::field::Empty).entered();
+
         if self.map_like {
-            visitor.visit_map(MapAccess { deserializer: self })
+            self.deserialize_map(visitor)
         } else {
             self.deserialize_seq(visitor)
         }
@@ -339,25 +443,29 @@ impl<'a, 'de> Deserializer<'de> for &'a mut IndexedDeserializer<'de> {
         debug!(
             "Ignored token {:?}. Preceding token (potentially an unmapped index) was {:?}",
             _token,
-            self.nth_last(1)
+            self.nth_last(2)
         );
 
         visitor.visit_none()
     }
 }
 
-const INDICES: [&str; 50] = [
+pub(super) const INDICES: [&str; 50] = [
     "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "21", "22", "23", "24",
     "25", "26", "27", "28", "29", "30", "31", "32", "33", "34", "35", "36", "37", "38", "39", "40", "41", "42", "43", "44", "45", "46",
     "47", "48", "49", "50",
 ];
 
-struct SeqAccess<'a, 'de> {
-    deserializer: &'a mut IndexedDeserializer<'de>,
+struct SeqAccess<'a, 'de, D> {
+    deserializer: &'a mut IndexedDeserializer<'de, D>,
     index: usize,
+
+    /// The span in which the sequence is being deserialized, whose `index` field is kept up to date
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
 }
 
-impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
+impl<'a, 'de, D: Delimiter> de::SeqAccess<'de> for SeqAccess<'a, 'de, D> {
     type Error = Error<'de>;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error<'de>>
@@ -368,24 +476,52 @@ impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
 
         trace!("Deserializing list entry at index {}", self.index);
 
+        #[cfg(feature = "tracing")]
+        self.span.record("index", self.index);
+
         match seed.deserialize(&mut *self.deserializer) {
             Err(Error::Eof) => Ok(None),
-            Err(Error::Custom { message, value, .. }) => Err(Error::Custom {
+            Err(Error::Custom {
                 message,
-                value: value.or_else(|| self.deserializer.nth_last(1)),
-                index: Some(INDICES.get(self.index - 1).unwrap_or(&">=51")),
-            }),
+                value,
+                position,
+                source,
+                ..
+            }) => {
+                let value = value.or_else(|| self.deserializer.nth_last(1));
+
+                debug!("Failed to deserialize value {:?}: {}", value, message);
+
+                Err(Error::Custom {
+                    message,
+                    value,
+                    index: Some(INDICES.get(self.index - 1).unwrap_or(&">=51")),
+                    position: position.or_else(|| value.and_then(|value| self.deserializer.offset_of(value))),
+                    source,
+                })
+            },
             Err(err) => Err(err),
             Ok(item) => Ok(Some(item)),
         }
     }
 }
 
-struct MapAccess<'a, 'de> {
-    deserializer: &'a mut IndexedDeserializer<'de>,
+struct MapAccess<'a, 'de, D> {
+    deserializer: &'a mut IndexedDeserializer<'de, D>,
+
+    /// The indices processed so far. Only tracked if a [`DuplicateIndexPolicy`] is set.
+    seen_indices: Vec<&'de str>,
+
+    /// The position of the last occurrence of each index, computed when processing the first index
+    /// with [`DuplicateIndexPolicy::LastWins`]
+    last_index_positions: Option<BTreeMap<&'de str, usize>>,
+
+    /// The span in which the map is being deserialized, whose `index` field is kept up to date
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
 }
 
-impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
+impl<'a, 'de, D: Delimiter> de::MapAccess<'de> for MapAccess<'a, 'de, D> {
     type Error = Error<'de>;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error<'de>>
@@ -394,13 +530,26 @@ impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
     {
         trace!("Processing a map key");
 
-        match seed.deserialize(&mut *self.deserializer) {
+        let result = match self.deserializer.duplicate_index_policy {
+            Some(policy) => self.next_unique_key_seed(seed, policy),
+            None => seed.deserialize(&mut *self.deserializer),
+        };
+
+        match result {
             Err(Error::Eof) => Ok(None),
-            Err(Error::Custom { message, .. }) => Err(Error::Custom {
-                message,
-                value: None,
-                index: self.deserializer.nth_last(1),
-            }),
+            Err(Error::Custom { message, source, .. }) => {
+                let index = self.deserializer.nth_last(1);
+
+                debug!("Failed to deserialize index {:?}: {}", index, message);
+
+                Err(Error::Custom {
+                    message,
+                    value: None,
+                    index,
+                    position: index.and_then(|index| self.deserializer.offset_of(index)),
+                    source,
+                })
+            },
             Err(err) => Err(err),
             Ok(item) => Ok(Some(item)),
         }
@@ -412,22 +561,80 @@ impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
     {
         trace!("Processing a map value",);
 
+        #[cfg(feature = "tracing")]
+        self.span.record("index", self.deserializer.nth_last(1));
+
         match seed.deserialize(&mut *self.deserializer) {
-            Err(Error::Custom { message, value, .. }) => Err(Error::Custom {
+            Err(Error::Custom {
                 message,
-                value: value.or_else(|| self.deserializer.nth_last(1)),
-                index: self.deserializer.nth_last(2),
-            }),
+                value,
+                position,
+                source,
+                ..
+            }) => {
+                let value = value.or_else(|| self.deserializer.nth_last(1));
+
+                debug!("Failed to deserialize value {:?}: {}", value, message);
+
+                Err(Error::Custom {
+                    message,
+                    value,
+                    index: self.deserializer.nth_last(2),
+                    position: position.or_else(|| value.and_then(|value| self.deserializer.offset_of(value))),
+                    source,
+                })
+            },
             r => r,
         }
     }
 }
 
+impl<'a, 'de, D: Delimiter> MapAccess<'a, 'de, D> {
+    /// Deserializes the next index that should not be skipped according to the given policy
+    fn next_unique_key_seed<K>(&mut self, seed: K, policy: DuplicateIndexPolicy) -> Result<K::Value, Error<'de>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        loop {
+            let index = self.deserializer.consume_token().ok_or(Error::Eof)?;
+
+            let skip = match policy {
+                DuplicateIndexPolicy::FirstWins => self.seen_indices.contains(&index),
+                DuplicateIndexPolicy::LastWins => {
+                    let deserializer = &*self.deserializer;
+                    let positions = self
+                        .last_index_positions
+                        .get_or_insert_with(|| deserializer.last_index_positions(index));
+
+                    positions.get(index) != deserializer.offset_of(index).as_ref()
+                },
+                DuplicateIndexPolicy::Error if self.seen_indices.contains(&index) => {
+                    return Err(Error::DuplicateIndex {
+                        index,
+                        position: self.deserializer.offset_of(index),
+                    })
+                },
+                DuplicateIndexPolicy::Error => false,
+            };
+
+            if !skip {
+                self.seen_indices.push(index);
+
+                return seed.deserialize(BorrowedStrDeserializer::new(index));
+            }
+
+            let _value = self.deserializer.consume_token();
+
+            debug!("Skipping value {:?} of duplicate index {} ({:?})", _value, index, policy);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::serde::IndexedDeserializer;
+    use crate::serde::{DeError, DuplicateIndexPolicy, IndexedDeserializer};
     use serde::de::Deserialize;
-    use std::collections::HashMap;
+    use std::{collections::HashMap, error::Error, num::ParseIntError};
 
     const INPUT: &str = "1:hello:2:world";
 
@@ -463,4 +670,72 @@ mod tests {
 
         assert_eq!(vec, INPUT.split(':').collect::<Vec<_>>())
     }
+
+    #[test]
+    fn test_deserialize_map_like_last_empty_option() {
+        let mut deserializer = IndexedDeserializer::new("1:5:2:", ":", true);
+
+        let map = HashMap::<&str, Option<u32>>::deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(map.get("1"), Some(&Some(5)));
+        assert_eq!(map.get("2"), Some(&None));
+    }
+
+    #[test]
+    fn test_duplicate_index_policies() {
+        const INPUT: &str = "1:a:2:b:1:c";
+
+        let deserialize = |policy| {
+            let mut deserializer = IndexedDeserializer::new(INPUT, ":", true).with_duplicate_index_policy(policy);
+
+            HashMap::<&str, &str>::deserialize(&mut deserializer)
+        };
+
+        let first_wins = deserialize(DuplicateIndexPolicy::FirstWins).unwrap();
+        assert_eq!(first_wins.get("1"), Some(&"a"));
+        assert_eq!(first_wins.get("2"), Some(&"b"));
+
+        let last_wins = deserialize(DuplicateIndexPolicy::LastWins).unwrap();
+        assert_eq!(last_wins.get("1"), Some(&"c"));
+        assert_eq!(last_wins.get("2"), Some(&"b"));
+
+        let mut deserializer =
+            IndexedDeserializer::new("1:a:2:b:1:c:2:d:1:e", ":", true).with_duplicate_index_policy(DuplicateIndexPolicy::LastWins);
+        let last_wins = HashMap::<&str, &str>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(last_wins.get("1"), Some(&"e"));
+        assert_eq!(last_wins.get("2"), Some(&"d"));
+
+        let error = deserialize(DuplicateIndexPolicy::Error).unwrap_err();
+        assert!(matches!(error, DeError::DuplicateIndex { index: "1", .. }));
+        assert_eq!(error.position(), Some(8));
+    }
+
+    #[test]
+    fn test_deserialize_bool_encodings() {
+        let mut deserializer = IndexedDeserializer::new("1:0:2::3:1:4:2:5:10", ":", true);
+
+        let map = HashMap::<&str, bool>::deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(map.get("1"), Some(&false));
+        assert_eq!(map.get("2"), Some(&false));
+        assert_eq!(map.get("3"), Some(&true));
+        assert_eq!(map.get("4"), Some(&true));
+        assert_eq!(map.get("5"), Some(&true));
+
+        let mut deserializer = IndexedDeserializer::new("1:3", ":", true);
+
+        assert!(HashMap::<&str, bool>::deserialize(&mut deserializer).is_err());
+    }
+
+    #[test]
+    fn test_error_context() {
+        let mut deserializer = IndexedDeserializer::new("1:2:3:abc", ":", true);
+
+        let error = HashMap::<&str, u32>::deserialize(&mut deserializer).unwrap_err();
+
+        assert_eq!(error.index(), Some("3"));
+        assert_eq!(error.offending_value(), Some("abc"));
+        assert_eq!(error.position(), Some(6));
+        assert!(error.source().unwrap().is::<ParseIntError>());
+    }
 }
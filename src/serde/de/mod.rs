@@ -2,5 +2,8 @@
 //!
 //! All of these deserializers have the goal to use zero-allocations for maximum efficiency
 
+pub mod bytes;
 pub mod error;
 pub mod indexed;
+#[cfg(feature = "std")]
+pub mod reader;
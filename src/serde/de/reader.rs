@@ -0,0 +1,544 @@
+//! Module containing a deserializer for robtop's indexed data format that incrementally reads its
+//! input from a [`BufRead`]
+
+use super::{error::Error, indexed::INDICES};
+use crate::split::{Delimiter, DynamicDelimiter};
+use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec::Vec};
+use core::mem;
+use serde::{
+    de,
+    de::{DeserializeSeed, Visitor},
+    Deserializer,
+};
+use std::io::{self, BufRead};
+
+/// Deserializer for RobTop's indexed data format, reading its input from a [`BufRead`]
+///
+/// Unlike [`IndexedDeserializer`](crate::IndexedDeserializer), this does not require the entire
+/// input to be in memory. Only the current token (and the one following it, to support optional
+/// values) is buffered. Since tokens are not retained, strings cannot be borrowed from the input
+/// and are copied instead. For the same reason, errors cannot contain the offending value, and only
+/// contain the index if it is a small integer.
+///
+/// Errors while reading from the underlying reader are reported as [`Error::Custom`], with the
+/// [`io::Error`] as their source.
+#[derive(Debug)]
+pub struct ReaderDeserializer<R, D = DynamicDelimiter> {
+    reader: R,
+    delimiter: D,
+    map_like: bool,
+
+    /// The most recently consumed token
+    token: Vec<u8>,
+
+    /// The byte offset into the input at which [`token`](Self::token) starts
+    position: usize,
+
+    /// The token following the most recently consumed one, if it has already been read
+    lookahead: Option<(Vec<u8>, usize)>,
+
+    /// The byte offset into the input up to which the reader has been consumed
+    read_position: usize,
+
+    /// Whether the reader has been read until its end
+    exhausted: bool,
+}
+
+impl<R: BufRead> ReaderDeserializer<R> {
+    /// Constructs a new `ReaderDeserializer`
+    ///
+    /// # Arguments
+    /// * *reader*: The reader to read the input from
+    /// * *delimiter*: The delimiter separating the individual fields
+    /// * *map_like*: Whether the input is in map-like format or not (meaning it is in list-like
+    ///   format)
+    ///
+    /// # Panics
+    /// Panics if `delimiter` is empty
+    pub fn new(reader: R, delimiter: &'static str, map_like: bool) -> Self {
+        ReaderDeserializer::with_delimiter(reader, DynamicDelimiter::new(delimiter), map_like)
+    }
+}
+
+impl<R: BufRead, D: Delimiter> ReaderDeserializer<R, D> {
+    /// Constructs a new `ReaderDeserializer` splitting the input at the given [`Delimiter`]
+    pub fn with_delimiter(reader: R, delimiter: D, map_like: bool) -> Self {
+        ReaderDeserializer {
+            reader,
+            delimiter,
+            map_like,
+            token: Vec::new(),
+            position: 0,
+            lookahead: None,
+            read_position: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Reads the next token from the reader into [`lookahead`](Self::lookahead), unless it has
+    /// already been read or the input has been fully consumed
+    fn fill_lookahead(&mut self) -> Result<(), Error<'static>> {
+        if self.lookahead.is_some() || self.exhausted {
+            return Ok(());
+        }
+
+        // Reuse the allocation of the current token, which is about to be replaced anyway
+        let mut buffer = mem::take(&mut self.token);
+        buffer.clear();
+
+        let found_delimiter = read_until_delimiter(&mut self.reader, &self.delimiter, &mut buffer).map_err(|error| Error::Custom {
+            message: error.to_string(),
+            index: None,
+            value: None,
+            position: Some(self.read_position),
+            source: Some(Box::new(error)),
+        })?;
+
+        self.lookahead = Some((buffer, self.read_position));
+        self.read_position += self.lookahead.as_ref().map_or(0, |(token, _)| token.len());
+
+        if found_delimiter {
+            self.read_position += self.delimiter.as_bytes().len();
+        } else {
+            self.exhausted = true;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the next token and consumes it, or [`None`] if the input has been fully consumed.
+    fn consume_token(&mut self) -> Result<Option<&str>, Error<'static>> {
+        self.fill_lookahead()?;
+
+        let (token, position) = match self.lookahead.take() {
+            Some(lookahead) => lookahead,
+            None => return Ok(None),
+        };
+
+        self.token = token;
+        self.position = position;
+
+        match core::str::from_utf8(&self.token) {
+            Ok(token) => Ok(Some(token)),
+            Err(error) => Err(Error::Custom {
+                message: error.to_string(),
+                index: None,
+                value: None,
+                position: Some(self.position + error.valid_up_to()),
+                source: Some(Box::new(error)),
+            }),
+        }
+    }
+
+    /// Returns whether the token following the current one is the empty string (or whether there is
+    /// no next token at all)
+    fn is_next_empty(&mut self) -> Result<bool, Error<'static>> {
+        self.fill_lookahead()?;
+
+        Ok(self.lookahead.as_ref().is_none_or(|(token, _)| token.is_empty()))
+    }
+
+    /// Returns the most recently consumed token as a `&'static str`, if it is one of the indices
+    /// in [`INDICES`]
+    fn static_index(&self) -> Option<&'static str> {
+        INDICES.iter().find(|index| index.as_bytes() == self.token.as_slice()).copied()
+    }
+}
+
+/// Reads bytes from `reader` into `buffer` until the next occurrence of `delimiter` or the end of
+/// the input. The delimiter is consumed, but not appended to the buffer.
+///
+/// Returns whether a delimiter was found.
+fn read_until_delimiter<R: BufRead, D: Delimiter>(reader: &mut R, delimiter: &D, buffer: &mut Vec<u8>) -> io::Result<bool> {
+    let delimiter_bytes = delimiter.as_bytes();
+
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+
+        if available.is_empty() {
+            return Ok(false);
+        }
+
+        // A multi-byte delimiter can straddle the boundary between two chunks of input, in which case
+        // its beginning has already been appended to the buffer
+        let straddling = (1..delimiter_bytes.len())
+            .rev()
+            .find(|&split| buffer.ends_with(&delimiter_bytes[..split]) && available.starts_with(&delimiter_bytes[split..]));
+
+        if let Some(split) = straddling {
+            buffer.truncate(buffer.len() - split);
+            reader.consume(delimiter_bytes.len() - split);
+
+            return Ok(true);
+        }
+
+        match delimiter.find(available) {
+            Some(idx) => {
+                buffer.extend_from_slice(&available[..idx]);
+                reader.consume(idx + delimiter_bytes.len());
+
+                return Ok(true);
+            },
+            None => {
+                let length = available.len();
+
+                buffer.extend_from_slice(available);
+                reader.consume(length);
+            },
+        }
+    }
+}
+
+macro_rules! delegate_to_from_str {
+    ($deserialize_method:ident, $visitor_method:ident) => {
+        fn $deserialize_method<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+        where
+            V: Visitor<'de>,
+        {
+            let token = self.consume_token()?.ok_or(Error::Eof)?;
+
+            match token.parse() {
+                Ok(parsed) => visitor.$visitor_method(parsed),
+                Err(error) => Err(Error::Custom {
+                    message: error.to_string(),
+                    index: None,
+                    value: None,
+                    position: Some(self.position),
+                    source: Some(Box::new(error)),
+                }),
+            }
+        }
+    };
+}
+
+impl<'de, R: BufRead, D: Delimiter> Deserializer<'de> for &mut ReaderDeserializer<R, D> {
+    type Error = Error<'static>;
+
+    delegate_to_from_str!(deserialize_i8, visit_i8);
+
+    delegate_to_from_str!(deserialize_i16, visit_i16);
+
+    delegate_to_from_str!(deserialize_i32, visit_i32);
+
+    delegate_to_from_str!(deserialize_i64, visit_i64);
+
+    delegate_to_from_str!(deserialize_u8, visit_u8);
+
+    delegate_to_from_str!(deserialize_u16, visit_u16);
+
+    delegate_to_from_str!(deserialize_u32, visit_u32);
+
+    delegate_to_from_str!(deserialize_u64, visit_u64);
+
+    delegate_to_from_str!(deserialize_f32, visit_f32);
+
+    delegate_to_from_str!(deserialize_f64, visit_f64);
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_any"))
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        // See IndexedDeserializer::deserialize_bool for the different encodings of booleans
+        match self.consume_token()? {
+            Some("0") | Some("") | None => visitor.visit_bool(false),
+            Some("1") | Some("2") | Some("10") => visitor.visit_bool(true),
+            Some(_) => Err(Error::Custom {
+                message: "Expected 0, 1, 2, 10 or the empty string".to_owned(),
+                index: None,
+                value: None,
+                position: Some(self.position),
+                source: None,
+            }),
+        }
+    }
+
+    fn deserialize_char<V>(self, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_char"))
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.consume_token()?.ok_or(Error::Eof)?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.consume_token()?.ok_or(Error::Eof)?)
+    }
+
+    fn deserialize_bytes<V>(self, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_bytes"))
+    }
+
+    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_byte_buf"))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        if self.is_next_empty()? {
+            self.consume_token()?;
+
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_unit"))
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_unit_struct"))
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_newtype_struct"))
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(SeqAccess {
+            deserializer: self,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_tuple"))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self, _name: &'static str, _len: usize, _visitor: V,
+    ) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_tuple_struct"))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(MapAccess { deserializer: self })
+    }
+
+    fn deserialize_struct<V>(
+        self, _name: &'static str, _fields: &'static [&'static str], visitor: V,
+    ) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("deserialize", model = _name).entered();
+
+        if self.map_like {
+            self.deserialize_map(visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self, _name: &'static str, _variants: &'static [&'static str], _visitor: V,
+    ) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Unsupported("deserialize_enum"))
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'static>>
+    where
+        V: Visitor<'de>,
+    {
+        // See IndexedDeserializer::deserialize_ignored_any
+        self.consume_token()?;
+
+        visitor.visit_none()
+    }
+}
+
+struct SeqAccess<'a, R, D> {
+    deserializer: &'a mut ReaderDeserializer<R, D>,
+    index: usize,
+}
+
+impl<'de, R: BufRead, D: Delimiter> de::SeqAccess<'de> for SeqAccess<'_, R, D> {
+    type Error = Error<'static>;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error<'static>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.index += 1;
+
+        match seed.deserialize(&mut *self.deserializer) {
+            Err(Error::Eof) => Ok(None),
+            Err(Error::Custom {
+                message, position, source, ..
+            }) => Err(Error::Custom {
+                message,
+                value: None,
+                index: Some(INDICES.get(self.index - 1).unwrap_or(&">=51")),
+                position: position.or(Some(self.deserializer.position)),
+                source,
+            }),
+            Err(err) => Err(err),
+            Ok(item) => Ok(Some(item)),
+        }
+    }
+}
+
+struct MapAccess<'a, R, D> {
+    deserializer: &'a mut ReaderDeserializer<R, D>,
+}
+
+impl<'de, R: BufRead, D: Delimiter> de::MapAccess<'de> for MapAccess<'_, R, D> {
+    type Error = Error<'static>;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error<'static>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match seed.deserialize(&mut *self.deserializer) {
+            Err(Error::Eof) => Ok(None),
+            Err(Error::Custom {
+                message, position, source, ..
+            }) => Err(Error::Custom {
+                message,
+                value: None,
+                index: self.deserializer.static_index(),
+                position: position.or(Some(self.deserializer.position)),
+                source,
+            }),
+            Err(err) => Err(err),
+            Ok(item) => Ok(Some(item)),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error<'static>>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        // The index is gone once the value has been consumed
+        let index = self.deserializer.static_index();
+
+        match seed.deserialize(&mut *self.deserializer) {
+            Err(Error::Custom {
+                message, position, source, ..
+            }) => Err(Error::Custom {
+                message,
+                value: None,
+                index,
+                position: position.or(Some(self.deserializer.position)),
+                source,
+            }),
+            r => r,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, io::BufReader};
+
+    use serde::Deserialize;
+
+    use crate::serde::{DeError, ReaderDeserializer};
+
+    #[test]
+    fn test_delimiter_straddling_chunks() {
+        let input = "1~|~hello~|~2~|~~|~3~|~world";
+
+        // A capacity of 1 makes the delimiter straddle chunk boundaries everywhere
+        for capacity in 1..=4 {
+            let mut deserializer = ReaderDeserializer::new(BufReader::with_capacity(capacity, input.as_bytes()), "~|~", true);
+            let map = HashMap::<String, String>::deserialize(&mut deserializer).unwrap();
+
+            assert_eq!(map.len(), 3);
+            assert_eq!(map["1"], "hello");
+            assert_eq!(map["2"], "");
+            assert_eq!(map["3"], "world");
+        }
+    }
+
+    #[test]
+    fn test_option_and_trailing_empty() {
+        #[derive(Deserialize)]
+        struct Test {
+            #[serde(rename = "1")]
+            a: Option<u32>,
+            #[serde(rename = "2")]
+            b: Option<u32>,
+        }
+
+        let test = Test::deserialize(&mut ReaderDeserializer::new("1::2:5".as_bytes(), ":", true)).unwrap();
+        assert_eq!((test.a, test.b), (None, Some(5)));
+
+        let test = Test::deserialize(&mut ReaderDeserializer::new("2:5:1:".as_bytes(), ":", true)).unwrap();
+        assert_eq!((test.a, test.b), (None, Some(5)));
+    }
+
+    #[test]
+    fn test_error_context() {
+        let result = HashMap::<u8, u8>::deserialize(&mut ReaderDeserializer::new("1:2:3:a".as_bytes(), ":", true));
+
+        match result {
+            Err(DeError::Custom { index, position, .. }) => {
+                assert_eq!(index, Some("3"));
+                assert_eq!(position, Some(6));
+            },
+            _ => panic!("expected a custom error, got {:?}", result),
+        }
+    }
+}
@@ -1,13 +1,39 @@
+#[cfg(feature = "arena")]
+mod arena;
 mod de;
 mod ser;
 mod thunk;
 
-pub use de::{error::Error as DeError, indexed::IndexedDeserializer};
-pub use ser::{error::Error as SerError, indexed::IndexedSerializer, request::RequestSerializer};
-use serde::{Deserializer, Serializer};
-pub use thunk::{Base64Decoder, PercentDecoder, ProcessError, Thunk, ThunkProcessor};
+#[cfg(feature = "arena")]
+pub(crate) use arena::decode_base64_in;
+#[cfg(feature = "arena")]
+pub use arena::ArenaProcessor;
 
-use std::{borrow::Cow, io::Write};
+use crate::split::KnownDelimiter;
+pub use crate::split::{ByteDelimiter, Delimiter, DynamicDelimiter, ThreeByteDelimiter};
+#[cfg(feature = "std")]
+pub use de::reader::ReaderDeserializer;
+pub use de::{
+    bytes::{GJBytes, Utf8Policy},
+    error::Error as DeError,
+    indexed::{DuplicateIndexPolicy, IndexedDeserializer},
+};
+pub use ser::{error::Error as SerError, fields::RawFields, indexed::IndexedSerializer, request::RequestSerializer, write::Write};
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+pub use thunk::{
+    Base64Decoder, LegacySet, PercentDecoder, PercentEncodeSet, ProcessError, Rfc3986Set, RobtopSet, Thunk, ThunkProcessor, ThunkProxy,
+    LEGACY_SET, RFC3986_SET, ROBTOP_SET,
+};
+
+use core::{fmt::Formatter, ops::Deref};
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
 
 /// Trait for objects that can be (de)serialized from some Geometry Dash data format (e.g. an
 /// indexed description).
@@ -24,25 +50,136 @@ use std::{borrow::Cow, io::Write};
 ///
 /// [1]: https://serde.rs/lifetimes.html
 pub trait Dash<'de>: Sized {
+    /// The indices of all fields of this type in RobTop's data format, in the order in which they
+    /// are declared
+    const INDICES: &'static [&'static str];
+
+    /// Descriptions of all fields of this type in RobTop's data format, see
+    /// [`schema::describe`](crate::schema::describe)
+    const FIELDS: &'static [crate::schema::FieldSchema];
+
     fn dash_deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
     fn dash_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    /// Returns an iterator over the `(index, value)` pairs this object would be serialized into,
+    /// with each value in RobTop's representation
+    ///
+    /// Fields that would be omitted during serialization (e.g. optional fields that are not set)
+    /// are not included.
+    fn raw_fields(&self) -> Result<RawFields, SerError> {
+        let mut fields = Vec::new();
+
+        self.dash_serialize(ser::fields::FieldCollector::new(&mut fields))?;
+
+        Ok(RawFields::new(fields))
+    }
+}
+
+/// Deserializes an arbitrary type from RobTop's indexed data format
+///
+/// This allows modelling structures dash-rs does not know about (e.g. responses of private servers)
+/// using plain serde derives, without implementing [`Dash`] and [`GJFormat`]. In map-like
+/// formats, fields are identified by their index, so use `#[serde(rename = "<index>")]`.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Reward<'a> {
+///     #[serde(rename = "1")]
+///     id: u32,
+///     #[serde(rename = "2")]
+///     name: &'a str,
+/// }
+///
+/// let reward: Reward = dash_rs::from_indexed_str("1:10:2:Diamonds", ":", true).unwrap();
+///
+/// assert_eq!((reward.id, reward.name), (10, "Diamonds"));
+/// ```
+///
+/// # Panics
+/// Panics if `delimiter` is empty
+pub fn from_indexed_str<'de, T: Deserialize<'de>>(input: &'de str, delimiter: &'static str, map_like: bool) -> Result<T, DeError<'de>> {
+    T::deserialize(&mut IndexedDeserializer::new(input, delimiter, map_like))
+}
+
+/// Serializes an arbitrary type into RobTop's indexed data format, the counterpart to
+/// [`from_indexed_str`]
+pub fn to_indexed_string<T: Serialize + ?Sized>(value: &T, delimiter: &'static str, map_like: bool) -> Result<String, SerError> {
+    let mut buffer = Vec::new();
+
+    value.serialize(&mut IndexedSerializer::new(delimiter, &mut buffer, map_like))?;
+
+    Ok(String::from_utf8(buffer)?)
+}
+
+fn deserialize_with<'de, T: Dash<'de>, D: Delimiter>(input: &'de str, delimiter: D, map_like: bool) -> Result<T, DeError<'de>> {
+    T::dash_deserialize(&mut IndexedDeserializer::with_delimiter(input, delimiter, map_like))
 }
 
 pub trait GJFormat<'de>: Dash<'de> {
     const DELIMITER: &'static str;
     const MAP_LIKE: bool;
 
-    fn from_gj_str(input: &'de str) -> Result<Self, de::error::Error> {
-        let mut indexed_deserializer = IndexedDeserializer::new(input, Self::DELIMITER, Self::MAP_LIKE);
+    fn from_gj_str(input: &'de str) -> Result<Self, de::error::Error<'de>> {
+        // All arms are still type-checked and monomorphized for every format, but since the
+        // scrutinee is a constant, codegen folds the match down to the arm using the delimiter
+        // type specialized to this format. No runtime checks on the delimiter are thus needed
+        // while splitting
+        match const { KnownDelimiter::of(Self::DELIMITER) } {
+            KnownDelimiter::Colon => deserialize_with(input, ByteDelimiter::<b':'>, Self::MAP_LIKE),
+            KnownDelimiter::Comma => deserialize_with(input, ByteDelimiter::<b','>, Self::MAP_LIKE),
+            KnownDelimiter::Tilde => deserialize_with(input, ByteDelimiter::<b'~'>, Self::MAP_LIKE),
+            KnownDelimiter::TildePipeTilde => deserialize_with(input, ThreeByteDelimiter::<b'~', b'|', b'~'>, Self::MAP_LIKE),
+            KnownDelimiter::Other => deserialize_with(input, DynamicDelimiter::new(Self::DELIMITER), Self::MAP_LIKE),
+        }
+    }
+
+    /// Parses `input` like [`GJFormat::from_gj_str`], failing if it is not valid UTF-8
+    ///
+    /// The returned error identifies the field containing the invalid UTF-8. To replace invalid
+    /// UTF-8 instead, use [`GJBytes`].
+    fn from_gj_bytes(input: &'de [u8]) -> Result<Self, de::error::Error<'de>> {
+        match de::bytes::decode(input, Self::DELIMITER, Self::MAP_LIKE, Utf8Policy::Strict)? {
+            Cow::Borrowed(input) => Self::from_gj_str(input),
+            // The strict policy never replaces invalid UTF-8, so decoding would have failed instead
+            Cow::Owned(_) => unreachable!(),
+        }
+    }
 
-        Self::dash_deserialize(&mut indexed_deserializer)
+    /// Parses the data read from `reader`, without reading all of it into memory first
+    ///
+    /// Only the token currently being parsed is buffered (see [`ReaderDeserializer`]). As nothing
+    /// can be borrowed from the input, all strings in the returned object are owned, meaning it can
+    /// be used as a `Self` with any lifetime, such as `'static`.
+    ///
+    /// ```
+    /// # use dash_rs::{model::song::NewgroundsSong, GJFormat};
+    /// let input = "1~|~771277~|~2~|~Ugly Pug~|~3~|~1~|~4~|~F-777~|~5~|~4.2~|~6~|~~|~10~|~https%3A%2F%2Faudio.ngfiles.com%2F771000%2F771277_Ugly-Pug.mp3~|~7~|~~|~8~|~1";
+    /// let song = NewgroundsSong::from_gj_reader(input.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(song.name, "Ugly Pug");
+    /// ```
+    #[cfg(feature = "std")]
+    fn from_gj_reader<R: BufRead>(reader: R) -> Result<Self, de::error::Error<'static>> {
+        Self::dash_deserialize(&mut ReaderDeserializer::new(reader, Self::DELIMITER, Self::MAP_LIKE))
     }
 
+    /// Serializes this object into RobTop's data format. For map-like formats, indices are written
+    /// in ascending numeric order
     fn write_gj<W: Write>(&self, writer: W) -> Result<(), ser::error::Error> {
         let mut indexed_serializer = IndexedSerializer::new(Self::DELIMITER, writer, Self::MAP_LIKE);
 
         self.dash_serialize(&mut indexed_serializer)
     }
+
+    /// Serializes this object into a newly allocated [`String`]
+    fn to_gj_string(&self) -> Result<String, ser::error::Error> {
+        let mut buffer = Vec::new();
+
+        self.write_gj(&mut buffer)?;
+
+        Ok(String::from_utf8(buffer)?)
+    }
 }
 
 /// Trait describing an intermediate step between the raw Geomtry Dash data format, and the APIs
@@ -85,29 +222,84 @@ macro_rules! identity_conversion {
 
 identity_conversion!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char, bool);
 
-impl<'b, T: ToOwned + ?Sized + 'static> InternalProxy for Cow<'b, T> {
-    type DeserializeProxy = &'b T;
-    type SerializeProxy<'a> = &'a T where Self: 'a;
+/// Proxy through which string fields are deserialized
+///
+/// Borrows from the input if the deserializer allows it (as [`IndexedDeserializer`] does), and
+/// copies the string otherwise (as is needed for [`ReaderDeserializer`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StrProxy<'a>(pub Cow<'a, str>);
+
+impl<'a> From<&'a str> for StrProxy<'a> {
+    fn from(value: &'a str) -> Self {
+        StrProxy(Cow::Borrowed(value))
+    }
+}
+
+impl Deref for StrProxy<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Serialize for StrProxy<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for StrProxy<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StrProxyVisitor;
+
+        impl<'de> Visitor<'de> for StrProxyVisitor {
+            type Value = StrProxy<'de>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(StrProxy(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(StrProxy(Cow::Owned(v.to_owned())))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(StrProxy(Cow::Owned(v)))
+            }
+        }
+
+        deserializer.deserialize_str(StrProxyVisitor)
+    }
+}
+
+impl<'b> InternalProxy for Cow<'b, str> {
+    type DeserializeProxy = StrProxy<'b>;
+    type SerializeProxy<'a> = &'a str where Self: 'a;
 
     fn to_serialize_proxy(&self) -> Self::SerializeProxy<'_> {
         self.as_ref()
     }
 
     fn from_deserialize_proxy(from: Self::DeserializeProxy) -> Self {
-        Cow::Borrowed(from)
+        from.0
     }
 }
 
 impl<'b, T: ThunkProcessor> InternalProxy for Thunk<'b, T> {
-    type DeserializeProxy = &'b str;
-    type SerializeProxy<'a> = Cow<'a, str> where Self: 'a;
+    type DeserializeProxy = StrProxy<'b>;
+    type SerializeProxy<'a> = ThunkProxy<'a, T> where Self: 'a;
 
     fn to_serialize_proxy(&self) -> Self::SerializeProxy<'_> {
-        self.as_unprocessed().unwrap()
+        self.as_proxy()
     }
 
     fn from_deserialize_proxy(from: Self::DeserializeProxy) -> Self {
-        Thunk::Unprocessed(Cow::Borrowed(from))
+        Thunk::Unprocessed(from.0)
     }
 }
 
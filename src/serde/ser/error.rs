@@ -1,4 +1,5 @@
-use std::{fmt::Display, io};
+use alloc::string::{FromUtf8Error, String, ToString};
+use core::fmt::Display;
 
 use thiserror::Error;
 
@@ -13,11 +14,12 @@ pub enum Error {
     #[error("unsupported serializer function: {0}")]
     Unsupported(&'static str),
 
+    #[cfg(feature = "std")]
     #[error("io error: {0}")]
-    Io(#[from] io::Error),
+    Io(#[from] std::io::Error),
 
     #[error("failed utf8 conversion: {0}")]
-    Utf8(#[from] std::string::FromUtf8Error),
+    Utf8(#[from] FromUtf8Error),
 }
 
 impl serde::ser::Error for Error {
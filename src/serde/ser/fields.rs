@@ -0,0 +1,127 @@
+//! Module containing a serializer that collects the fields of a model into (index, value) pairs,
+//! see [`Dash::raw_fields`](crate::Dash::raw_fields)
+//!
+//! Unlike splitting the output of [`IndexedSerializer`], this works regardless of whether the
+//! values themselves contain the format's delimiter.
+
+use crate::serde::{IndexedSerializer, SerError as Error};
+use alloc::{string::String, vec, vec::Vec};
+use serde::{
+    ser::{Error as _, Impossible, SerializeStruct},
+    Serialize, Serializer,
+};
+
+/// Iterator over the fields of a model in RobTop's data format, as returned by
+/// [`Dash::raw_fields`](crate::Dash::raw_fields)
+///
+/// Yields `(index, value)` pairs in the order in which the fields would be serialized.
+#[derive(Debug, Clone)]
+pub struct RawFields {
+    inner: vec::IntoIter<(&'static str, String)>,
+}
+
+impl RawFields {
+    pub(crate) fn new(fields: Vec<(&'static str, String)>) -> Self {
+        RawFields { inner: fields.into_iter() }
+    }
+}
+
+impl Iterator for RawFields {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for RawFields {}
+
+/// Serializer collecting the fields of a model struct, with each value serialized as
+/// [`IndexedSerializer`] would
+pub(crate) struct FieldCollector<'f> {
+    fields: &'f mut Vec<(&'static str, String)>,
+}
+
+impl<'f> FieldCollector<'f> {
+    pub(crate) fn new(fields: &'f mut Vec<(&'static str, String)>) -> Self {
+        FieldCollector { fields }
+    }
+}
+
+impl Serializer for FieldCollector<'_> {
+    type Error = Error;
+    type Ok = ();
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+
+    unsupported!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+        serialize_bytes: &[u8]
+    );
+
+    unsupported_compound!();
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::Unsupported("serialize_none"))
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(Error::Unsupported("serialize_some"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::Unsupported("serialize_seq"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl SerializeStruct for FieldCollector<'_> {
+    type Error = Error;
+    type Ok = ();
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut buffer = Vec::new();
+
+        // With an empty delimiter, `None` values are serialized to the empty string, as they would
+        // be in the middle of the format
+        value.serialize(&mut IndexedSerializer::new("", &mut buffer, false))?;
+
+        self.fields.push((key, String::from_utf8(buffer).map_err(Error::custom)?));
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
@@ -1,11 +1,11 @@
-use crate::serde::ser::error::Error;
+use crate::serde::ser::{error::Error, write::Write};
+use core::fmt::Display;
 use dtoa::Float;
 use itoa::{Buffer, Integer};
 use serde::{
     ser::{Error as _, Impossible, SerializeStruct},
     Serialize, Serializer,
 };
-use std::{fmt::Display, io::Write};
 
 #[allow(missing_debug_implementations)]
 pub struct IndexedSerializer<W> {
@@ -44,7 +44,7 @@ where
         }
 
         let mut buffer = Buffer::new();
-        self.writer.write(buffer.format(int).as_bytes()).map_err(Error::custom)?;
+        self.writer.write_all(buffer.format(int).as_bytes())?;
 
         Ok(())
     }
@@ -57,7 +57,7 @@ where
         }
 
         let mut buffer = dtoa::Buffer::new();
-        self.writer.write(buffer.format(float).as_bytes()).map_err(Error::custom)?;
+        self.writer.write_all(buffer.format(float).as_bytes())?;
 
         Ok(())
     }
@@ -74,7 +74,7 @@ where
     }
 }
 
-impl<'a, W: Write> Serializer for &'a mut IndexedSerializer<W> {
+impl<W: Write> Serializer for &mut IndexedSerializer<W> {
     type Error = Error;
     type Ok = ();
     type SerializeMap = Impossible<(), Error>;
@@ -142,10 +142,23 @@ impl<'a, W: Write> Serializer for &'a mut IndexedSerializer<W> {
 
     // Here we serialize bytes by base64 encoding them, so it's always valid in Geometry Dash's format
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        use base64::{engine::general_purpose::URL_SAFE, write::EncoderWriter};
-        let mut enc = EncoderWriter::new(&mut self.writer, &URL_SAFE);
-        enc.write_all(v)?;
-        enc.finish()?;
+        use base64::{engine::general_purpose::URL_SAFE, Engine};
+
+        // Encode in chunks whose length is a multiple of 3, so that only the final chunk can end up padded
+        let mut buffer = [0u8; 1024];
+
+        if self.is_start {
+            self.is_start = false;
+        } else {
+            self.writer.write_all(self.delimiter)?;
+        }
+
+        for chunk in v.chunks(768) {
+            let encoded_len = URL_SAFE.encode_slice(chunk, &mut buffer).map_err(Error::custom)?;
+
+            self.writer.write_all(&buffer[..encoded_len])?;
+        }
+
         Ok(())
     }
 
@@ -154,9 +167,9 @@ impl<'a, W: Write> Serializer for &'a mut IndexedSerializer<W> {
         Ok(())
     }
 
-    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(self)
     }
@@ -173,18 +186,18 @@ impl<'a, W: Write> Serializer for &'a mut IndexedSerializer<W> {
         Err(Error::Unsupported("serialize_unit_variant"))
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::Unsupported("serialize_newtype_struct"))
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::Unsupported("serialize_newtype_variant"))
     }
@@ -222,21 +235,21 @@ impl<'a, W: Write> Serializer for &'a mut IndexedSerializer<W> {
         Err(Error::Unsupported("serialize_struct_variant"))
     }
 
-    fn collect_str<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn collect_str<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Display,
+        T: ?Sized + Display,
     {
         Err(Error::Unsupported("collect_str"))
     }
 }
 
-impl<'a, W: Write> SerializeStruct for &'a mut IndexedSerializer<W> {
+impl<W: Write> SerializeStruct for &mut IndexedSerializer<W> {
     type Error = Error;
     type Ok = ();
 
-    fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         if self.map_like {
             self.append(key)?;
@@ -1,5 +1,83 @@
 //! Module containing serde serializers for the various custom data formats RobTop uses.
 
+/// Implements the given [`Serializer`](serde::Serializer) methods for primitive types as unsupported
+macro_rules! unsupported {
+    ($($method: ident: $t: ty),*) => {
+        $(
+            fn $method(self, _v: $t) -> Result<Self::Ok, Self::Error> {
+                Err(crate::serde::SerError::Unsupported(stringify!($method)))
+            }
+        )*
+    };
+}
+
+/// Implements the [`Serializer`](serde::Serializer) methods for compound types other than structs and
+/// sequences as unsupported
+macro_rules! unsupported_compound {
+    () => {
+        fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_unit"))
+        }
+
+        fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_unit_struct"))
+        }
+
+        fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_unit_variant"))
+        }
+
+        fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
+        where
+            T: ?Sized + serde::Serialize,
+        {
+            Err(crate::serde::SerError::Unsupported("serialize_newtype_struct"))
+        }
+
+        fn serialize_newtype_variant<T>(
+            self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T,
+        ) -> Result<Self::Ok, Self::Error>
+        where
+            T: ?Sized + serde::Serialize,
+        {
+            Err(crate::serde::SerError::Unsupported("serialize_newtype_variant"))
+        }
+
+        fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_tuple"))
+        }
+
+        fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_tuple_struct"))
+        }
+
+        fn serialize_tuple_variant(
+            self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+        ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_tuple_variant"))
+        }
+
+        fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_map"))
+        }
+
+        fn serialize_struct_variant(
+            self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+        ) -> Result<Self::SerializeStructVariant, Self::Error> {
+            Err(crate::serde::SerError::Unsupported("serialize_struct_variant"))
+        }
+
+        fn collect_str<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+        where
+            T: ?Sized + core::fmt::Display,
+        {
+            Err(crate::serde::SerError::Unsupported("collect_str"))
+        }
+    };
+}
+
 pub mod error;
+pub mod fields;
 pub mod indexed;
 pub mod request;
+pub mod write;
@@ -12,41 +12,131 @@
 //!   them isn't supported. What happens if we include them programmatically is something yet to be
 //!   investigated) TODO GAME SPECIFIC
 
-use crate::serde::SerError as Error;
+use crate::serde::{ser::write::Write, SerError as Error};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Display;
 use dtoa::Float;
 use itoa::{Buffer, Integer};
 use serde::{
-    ser::{Error as _, Impossible, SerializeStruct},
+    ser::{Impossible, SerializeStruct},
     Serialize, Serializer,
 };
-use std::{fmt::Display, io::Write};
 
-#[allow(missing_debug_implementations)]
-pub struct RequestSerializer<W> {
+/// Sink for the key-value pairs a [`RequestSerializer`] produces
+pub trait RequestOutput {
+    /// The writer the value of a field is written to
+    type Value: Write;
+
+    /// Starts a new field with the given key. Its value is subsequently written to
+    /// [`RequestOutput::value`]
+    fn start_field(&mut self, key: &'static str) -> Result<(), Error>;
+
+    /// The writer for the value of the field started last
+    fn value(&mut self) -> &mut Self::Value;
+}
+
+/// [`RequestOutput`] joining the pairs into RobTop's `x-www-form-urlencoded`-like format
+#[derive(Debug)]
+pub struct FormOutput<W> {
     writer: W,
 
-    /// Value indicating whether this serializer has already serialized something. This is used to
-    /// check if we need to prepend the delimiter to the next field.
+    /// Value indicating whether a field has already been written. This is used to check if we need
+    /// to prepend the delimiter to the next field.
     is_start: bool,
 }
 
-impl<W> RequestSerializer<W> {
+impl<W: Write> RequestOutput for FormOutput<W> {
+    type Value = W;
+
+    fn start_field(&mut self, key: &'static str) -> Result<(), Error> {
+        if !self.is_start {
+            self.writer.write_all(b"&")?;
+        }
+
+        self.is_start = false;
+        self.writer.write_all(key.as_bytes())?;
+        self.writer.write_all(b"=")?;
+
+        Ok(())
+    }
+
+    fn value(&mut self) -> &mut W {
+        &mut self.writer
+    }
+}
+
+/// [`RequestOutput`] collecting the pairs into a list, allowing HTTP clients to encode them
+/// natively
+#[derive(Debug, Default)]
+pub struct PairOutput {
+    pairs: Vec<(&'static str, Vec<u8>)>,
+
+    /// The key of the field currently being written, whose value is collected in `value`
+    key: Option<&'static str>,
+    value: Vec<u8>,
+}
+
+impl PairOutput {
+    fn finish_field(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.pairs.push((key, core::mem::take(&mut self.value)));
+        }
+    }
+}
+
+impl RequestOutput for PairOutput {
+    type Value = Vec<u8>;
+
+    fn start_field(&mut self, key: &'static str) -> Result<(), Error> {
+        self.finish_field();
+        self.key = Some(key);
+
+        Ok(())
+    }
+
+    fn value(&mut self) -> &mut Vec<u8> {
+        &mut self.value
+    }
+}
+
+/// Serializer for requests
+///
+/// The same field and value formatting is used regardless of whether the result is a request body
+/// ([`RequestSerializer::new`]) or a list of key-value pairs ([`RequestSerializer::pairs`]).
+#[allow(missing_debug_implementations)]
+pub struct RequestSerializer<O> {
+    output: O,
+}
+
+impl<W> RequestSerializer<FormOutput<W>> {
+    /// Constructs a serializer writing a request body to the given writer
     pub fn new(writer: W) -> Self {
-        RequestSerializer { writer, is_start: true }
+        RequestSerializer {
+            output: FormOutput { writer, is_start: true },
+        }
     }
 }
 
-macro_rules! unsupported {
-    ($($method: ident: $t: ty),*) => {
-        $(
-            fn $method(self, _v: $t) -> Result<Self::Ok, Self::Error> {
-                Err(Error::Unsupported(stringify!($method)))
-            }
-        )*
-    };
+impl RequestSerializer<PairOutput> {
+    /// Constructs a serializer collecting the fields of a request into key-value pairs
+    pub fn pairs() -> Self {
+        RequestSerializer {
+            output: PairOutput::default(),
+        }
+    }
+
+    /// Returns the key-value pairs serialized so far
+    pub fn into_pairs(mut self) -> Result<Vec<(&'static str, String)>, Error> {
+        self.output.finish_field();
+        self.output
+            .pairs
+            .into_iter()
+            .map(|(key, value)| Ok((key, String::from_utf8(value)?)))
+            .collect()
+    }
 }
 
-impl<'a, W: Write> Serializer for &'a mut RequestSerializer<W> {
+impl<O: RequestOutput> Serializer for &mut RequestSerializer<O> {
     type Error = Error;
     type Ok = ();
     type SerializeMap = Impossible<(), Error>;
@@ -78,9 +168,9 @@ impl<'a, W: Write> Serializer for &'a mut RequestSerializer<W> {
         Err(Error::Unsupported("serialize_none"))
     }
 
-    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::Unsupported("serialize_some"))
     }
@@ -97,18 +187,18 @@ impl<'a, W: Write> Serializer for &'a mut RequestSerializer<W> {
         Err(Error::Unsupported("serialize_unit_variant"))
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::Unsupported("serialize_newtype_struct"))
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::Unsupported("serialize_newtype_variant"))
     }
@@ -145,30 +235,24 @@ impl<'a, W: Write> Serializer for &'a mut RequestSerializer<W> {
         Err(Error::Unsupported("serialize_struct_variant"))
     }
 
-    fn collect_str<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn collect_str<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Display,
+        T: ?Sized + Display,
     {
         Err(Error::Unsupported("collect_str"))
     }
 }
 
-impl<'a, W: Write> SerializeStruct for &'a mut RequestSerializer<W> {
+impl<O: RequestOutput> SerializeStruct for &mut RequestSerializer<O> {
     type Error = Error;
     type Ok = ();
 
-    fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        if !self.is_start {
-            self.writer.write(b"&").map_err(Error::custom)?;
-        }
-
-        // we cannot do self.is_start = false here because the first field might be a struct that was
-        // inlined, meaning no key/value pair is directly constructed! It has to happen when we actually
-        // write the first key, which occurs inside some nested ValueSerializer call.
-
+        // The field might be a struct that is inlined, in which case no key/value pair is directly
+        // constructed. Thus the key is only written once an actual value is serialized.
         value.serialize(&mut ValueSerializer {
             key: Some(key),
             serializer: self,
@@ -181,63 +265,53 @@ impl<'a, W: Write> SerializeStruct for &'a mut RequestSerializer<W> {
     }
 }
 
-struct ValueSerializer<'ser, W: Write> {
+struct ValueSerializer<'ser, O: RequestOutput> {
     key: Option<&'static str>,
-    serializer: &'ser mut RequestSerializer<W>,
+    serializer: &'ser mut RequestSerializer<O>,
 }
 
-impl<'ser, W: Write> ValueSerializer<'ser, W> {
-    fn write_key(&mut self) -> Result<(), Error> {
+impl<'ser, O: RequestOutput> ValueSerializer<'ser, O> {
+    /// Starts the field this value belongs to (unless it is part of a sequence, whose field has
+    /// already been started), and returns the writer for the value
+    fn writer(&mut self) -> Result<&mut O::Value, Error> {
         if let Some(key) = self.key {
-            self.serializer.writer.write_all(key.as_bytes()).map_err(Error::custom)?;
-            self.serializer.writer.write(b"=").map_err(Error::custom)?;
-
-            self.serializer.is_start = false;
+            self.serializer.output.start_field(key)?;
         }
-        Ok(())
+
+        Ok(self.serializer.output.value())
     }
 
     fn write_integer<I: Integer>(&mut self, int: I) -> Result<(), Error> {
-        self.write_key()?;
-
         let mut buffer = Buffer::new();
-        self.serializer.writer.write(buffer.format(int).as_bytes()).map_err(Error::custom)?;
+        self.writer()?.write_all(buffer.format(int).as_bytes())?;
 
         Ok(())
     }
 
     fn write_float<F: Float>(&mut self, float: F) -> Result<(), Error> {
-        self.write_key()?;
-
         let mut buffer = dtoa::Buffer::new();
-        self.serializer
-            .writer
-            .write(buffer.format(float).as_bytes())
-            .map_err(Error::custom)?;
+        self.writer()?.write_all(buffer.format(float).as_bytes())?;
 
         Ok(())
     }
 }
 
-impl<'ser, 'a, W: Write> Serializer for &'a mut ValueSerializer<'ser, W> {
+impl<'ser, 'a, O: RequestOutput> Serializer for &'a mut ValueSerializer<'ser, O> {
     type Error = Error;
     type Ok = ();
     type SerializeMap = Impossible<(), Error>;
-    type SerializeSeq = SerializeSeq<'a, W>;
-    type SerializeStruct = &'a mut RequestSerializer<W>;
+    type SerializeSeq = SerializeSeq<'a, O>;
+    type SerializeStruct = &'a mut RequestSerializer<O>;
     type SerializeStructVariant = Impossible<(), Error>;
     type SerializeTuple = Impossible<(), Error>;
     type SerializeTupleStruct = Impossible<(), Error>;
     type SerializeTupleVariant = Impossible<(), Error>;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        self.write_key()?;
-
         match v {
-            true => self.serializer.writer.write(b"1"),
-            false => self.serializer.writer.write(b"0"),
-        }
-        .map_err(Error::custom)?;
+            true => self.writer()?.write_all(b"1"),
+            false => self.writer()?.write_all(b"0"),
+        }?;
 
         Ok(())
     }
@@ -283,23 +357,16 @@ impl<'ser, 'a, W: Write> Serializer for &'a mut ValueSerializer<'ser, W> {
     }
 
     fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
-        self.write_key()?;
-
         // We don't need allocations for appending a single char
         // A buffer of size 4 is always enough to encode a char
         let mut char_buffer: [u8; 4] = [0; 4];
-        self.serializer
-            .writer
-            .write_all(v.encode_utf8(&mut char_buffer).as_bytes())
-            .map_err(Error::custom)?;
+        self.writer()?.write_all(v.encode_utf8(&mut char_buffer).as_bytes())?;
 
         Ok(())
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        self.write_key()?;
-
-        self.serializer.writer.write(v.as_bytes()).map_err(Error::custom)?;
+        self.writer()?.write_all(v.as_bytes())?;
 
         Ok(())
     }
@@ -309,12 +376,12 @@ impl<'ser, 'a, W: Write> Serializer for &'a mut ValueSerializer<'ser, W> {
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        self.write_key()
+        self.writer().map(|_| ())
     }
 
-    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(self)
     }
@@ -331,18 +398,18 @@ impl<'ser, 'a, W: Write> Serializer for &'a mut ValueSerializer<'ser, W> {
         Err(Error::Unsupported("serialize_unit_variant"))
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::Unsupported("serialize_newtype_struct"))
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::Unsupported("serialize_newtype_variant"))
     }
@@ -352,7 +419,7 @@ impl<'ser, 'a, W: Write> Serializer for &'a mut ValueSerializer<'ser, W> {
             return Err(Error::Unsupported("Nested sequences"));
         }
 
-        self.write_key()?;
+        self.writer()?;
 
         // This is a horrible hack. In `LevelsRequest` there is one particular field, namely
         // 'completedLevels`, that represents a list of values. In the entire freaking API, this is the only
@@ -390,12 +457,7 @@ impl<'ser, 'a, W: Write> Serializer for &'a mut ValueSerializer<'ser, W> {
             return Err(Error::Unsupported("struct inside sequence"));
         }
 
-        // If we inline a struct, that struct might not be the first field we serialize. However, we do not
-        // know whether the value we serialize is a struct or not until we reach this method, so the value
-        // serializer will already have appended an '&' for us. This means that we do not want to add
-        // another one for the first field of this nested struct.
-        self.serializer.is_start = true;
-
+        // Nested structs are inlined
         Ok(self.serializer)
     }
 
@@ -405,32 +467,32 @@ impl<'ser, 'a, W: Write> Serializer for &'a mut ValueSerializer<'ser, W> {
         Err(Error::Unsupported("serialize_struct_variant"))
     }
 
-    fn collect_str<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn collect_str<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Display,
+        T: ?Sized + Display,
     {
         Err(Error::Unsupported("collect_str"))
     }
 }
 
-pub(crate) struct SerializeSeq<'ser, W: Write> {
-    serializer: &'ser mut RequestSerializer<W>,
+pub(crate) struct SerializeSeq<'ser, O: RequestOutput> {
+    serializer: &'ser mut RequestSerializer<O>,
     is_start: bool,
     parenthesized: bool,
 }
 
-impl<'write, W: Write> serde::ser::SerializeSeq for SerializeSeq<'write, W> {
+impl<'write, O: RequestOutput> serde::ser::SerializeSeq for SerializeSeq<'write, O> {
     type Error = Error;
     type Ok = ();
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         if !self.is_start {
-            self.serializer.writer.write(b",").map_err(Error::custom)?;
+            self.serializer.output.value().write_all(b",")?;
         } else if self.parenthesized {
-            self.serializer.writer.write(b"(").map_err(Error::custom)?;
+            self.serializer.output.value().write_all(b"(")?;
         }
 
         self.is_start = false;
@@ -442,10 +504,10 @@ impl<'write, W: Write> serde::ser::SerializeSeq for SerializeSeq<'write, W> {
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
         if self.is_start {
-            self.serializer.writer.write(b"-").map_err(Error::custom)?; // empty sequence
+            self.serializer.output.value().write_all(b"-")?; // empty sequence
         }
         if self.parenthesized {
-            self.serializer.writer.write(b")").map_err(Error::custom)?;
+            self.serializer.output.value().write_all(b")")?;
         }
         Ok(())
     }
@@ -461,10 +523,7 @@ mod tests {
         let level_request = LevelRequest::default();
         let mut buffer = Vec::new();
 
-        let mut ser = RequestSerializer {
-            writer: &mut buffer,
-            is_start: true,
-        };
+        let mut ser = RequestSerializer::new(&mut buffer);
         level_request.serialize(&mut ser).unwrap();
 
         assert_eq!(
@@ -0,0 +1,36 @@
+//! Module containing the [`Write`] trait the serializers use as their output sink
+//!
+//! With the `std` feature enabled, this is simply [`std::io::Write`]. Without it, a minimal
+//! replacement is provided, which is implemented for [`Vec<u8>`].
+
+#[cfg(feature = "std")]
+pub use std::io::Write;
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::Write;
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use alloc::vec::Vec;
+
+    use crate::serde::ser::error::Error;
+
+    /// Minimal, `alloc`-only replacement for `std::io::Write`
+    pub trait Write {
+        /// Writes the entire buffer into this writer
+        fn write_all(&mut self, buf: &[u8]) -> Result<(), Error>;
+    }
+
+    impl Write for Vec<u8> {
+        fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+            self.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+            (**self).write_all(buf)
+        }
+    }
+}
@@ -1,15 +1,21 @@
-use base64::{engine::general_purpose::URL_SAFE, DecodeError, DecodeSliceError, Engine};
-use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
-use serde::{ser::Error as _, Deserialize, Serialize, Serializer};
-use std::{
+use alloc::{
     borrow::{Borrow, Cow},
+    string::{FromUtf8Error, String},
+};
+use base64::{DecodeError, DecodeSliceError};
+use core::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
     mem,
     num::ParseIntError,
     str::Utf8Error,
-    string::FromUtf8Error,
 };
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS, NON_ALPHANUMERIC};
+use serde::{ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
+use crate::serde::StrProxy;
+
 /// Enum modelling the different errors that can occur during processing of a [`Thunk`]
 ///
 /// ## Why is this a seperate enum
@@ -49,6 +55,10 @@ pub enum ProcessError {
 
     #[error("Received value that cannot be represented in Geometry Dash data format")]
     Unrepresentable,
+
+    /// The unprocessed value did not follow the format expected by the [`ThunkProcessor`]
+    #[error("Value does not match the expected format")]
+    InvalidFormat,
 }
 
 impl From<DecodeError> for ProcessError {
@@ -108,18 +118,30 @@ where
 /// This trait provides the means to translate from and into RobTop's representation for thunked
 /// data, while not being used in the (de)serialization into any other data format.
 pub trait ThunkProcessor {
-    type Error: std::error::Error;
+    type Error: core::error::Error;
     type Output<'a>;
 
     /// Takes some data from the [`Thunk::Unprocessed`] variant and processes it
     ///
     /// This function is *not* called automatically during deserialization from a RobTop data
     /// format.
-    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error>;
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error>;
 
     /// Takes some processed thunk value and converts it into RobTop-representation
     fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error>;
 
+    /// Serializes the RobTop-representation of some processed thunk value directly into
+    /// `serializer`
+    ///
+    /// This is what is used when a [`Thunk`] gets serialized into a RobTop data format. The default
+    /// implementation serializes the string returned by [`ThunkProcessor::as_unprocessed`].
+    /// Processors whose RobTop-representation is the URL-safe base64 encoding of some binary data
+    /// should instead pass that data to [`Serializer::serialize_bytes`], which dash-rs' serializers
+    /// base64-encode straight into their output. This avoids allocating the encoded string.
+    fn serialize_unprocessed<S: Serializer>(processed: &Self::Output<'_>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Self::as_unprocessed(processed).map_err(S::Error::custom)?)
+    }
+
     /// The presence of this function essentially forces [`ThunkProcessor::Output`] to be covariant
     /// in its lifetime.
     ///
@@ -134,7 +156,58 @@ pub trait ThunkProcessor {
     fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c>;
 }
 
+/// Proxy through which [`Thunk`]s are serialized into and deserialized from RobTop data formats
+///
+/// Deserialization always produces the [`ThunkProxy::Unprocessed`] variant, borrowing from the
+/// input where the deserializer allows it. Serialization of the [`ThunkProxy::Processed`] variant goes through
+/// [`ThunkProcessor::serialize_unprocessed`].
+pub enum ThunkProxy<'a, C: ThunkProcessor> {
+    Unprocessed(Cow<'a, str>),
+    Processed(&'a C::Output<'a>),
+}
+
+impl<'a, C: ThunkProcessor> Debug for ThunkProxy<'a, C>
+where
+    C::Output<'a>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ThunkProxy::Unprocessed(unprocessed) => f.debug_tuple("Unprocessed").field(unprocessed).finish(),
+            ThunkProxy::Processed(processed) => f.debug_tuple("Processed").field(processed).finish(),
+        }
+    }
+}
+
+impl<'a, C: ThunkProcessor> Serialize for ThunkProxy<'a, C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ThunkProxy::Unprocessed(unprocessed) => serializer.serialize_str(unprocessed),
+            ThunkProxy::Processed(processed) => C::serialize_unprocessed(processed, serializer),
+        }
+    }
+}
+
+impl<'de: 'a, 'a, C: ThunkProcessor> Deserialize<'de> for ThunkProxy<'a, C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        StrProxy::deserialize(deserializer).map(|proxy| ThunkProxy::Unprocessed(proxy.0))
+    }
+}
+
 impl<'a, C: ThunkProcessor> Thunk<'a, C> {
+    /// Returns a [`ThunkProxy`] through which this thunk can be serialized into a RobTop data format
+    pub fn as_proxy(&self) -> ThunkProxy<'_, C> {
+        match self {
+            Thunk::Unprocessed(unprocessed) => ThunkProxy::Unprocessed(Cow::Borrowed(unprocessed)),
+            Thunk::Processed(processed) => ThunkProxy::Processed(C::downcast_output_lifetime(processed)),
+        }
+    }
+
     /// If this is a [`Thunk::Unprocessed`] variant, calls [`ThunkProcessor::from_unprocessed`] and
     /// returns [`Thunk::Processed`]. Simply returns `self` if this is a [`Thunk::Processed`]
     /// variant
@@ -149,7 +222,7 @@ impl<'a, C: ThunkProcessor> Thunk<'a, C> {
         }
     }
 
-    pub fn as_unprocessed(&self) -> Result<Cow<str>, C::Error> {
+    pub fn as_unprocessed(&self) -> Result<Cow<'_, str>, C::Error> {
         match self {
             Thunk::Unprocessed(unprocessed) => Ok(Cow::Borrowed(unprocessed)),
             Thunk::Processed(content) => C::as_unprocessed(content),
@@ -178,22 +251,64 @@ impl<'a, C: ThunkProcessor> Thunk<'a, C> {
 /// Set of characters RobTop encodes when doing percent encoding
 ///
 /// This is a subset of [`percent_encoding::NON_ALPHANUMERIC`], since that encodes too many
-/// characters
+/// characters. All song links in our corpus of captured responses are reproduced exactly when
+/// re-encoding with this set.
 pub const ROBTOP_SET: &AsciiSet = &CONTROLS
-    .add(b' ')  // TODO: investigate if this is part of the set. Song links never contain spaces
+    .add(b' ')  // Never observed, as song links never contain spaces, but the game encodes them
     .add(b':')
     .add(b'/')
     .add(b'?')
     .add(b'~');
 
+/// Set of characters that need to be percent encoded according to
+/// [RFC 3986](https://datatracker.ietf.org/doc/html/rfc3986#section-2.3), i.e. everything except
+/// alphanumeric characters and `-`, `.`, `_` and `~`
+pub const RFC3986_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// Set of characters encoded in song links provided by the servers before update 2.0, which only
+/// ever percent encoded the characters `:`, `/` and `?`
+pub const LEGACY_SET: &AsciiSet = &CONTROLS.add(b':').add(b'/').add(b'?');
+
+/// Trait selecting the set of characters a [`PercentDecoder`] percent encodes
+pub trait PercentEncodeSet {
+    const SET: &'static AsciiSet;
+}
+
+/// Selects [`ROBTOP_SET`], the set of characters the game is observed to encode
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum RobtopSet {}
+
+/// Selects [`RFC3986_SET`], for producing standard-compliant URLs
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Rfc3986Set {}
+
+/// Selects [`LEGACY_SET`], for producing song links like those of pre-2.0 servers
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum LegacySet {}
+
+impl PercentEncodeSet for RobtopSet {
+    const SET: &'static AsciiSet = ROBTOP_SET;
+}
+
+impl PercentEncodeSet for Rfc3986Set {
+    const SET: &'static AsciiSet = RFC3986_SET;
+}
+
+impl PercentEncodeSet for LegacySet {
+    const SET: &'static AsciiSet = LEGACY_SET;
+}
+
+/// [`ThunkProcessor`] for percent encoded strings. Decoding accepts arbitrarily encoded input,
+/// while encoding uses the set of characters selected by `S`
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
-pub struct PercentDecoder;
+#[serde(bound = "")]
+pub struct PercentDecoder<S: PercentEncodeSet = RobtopSet>(PhantomData<S>);
 
-impl ThunkProcessor for PercentDecoder {
+impl<S: PercentEncodeSet> ThunkProcessor for PercentDecoder<S> {
     type Error = ProcessError;
     type Output<'a> = Cow<'a, str>;
 
-    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error> {
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
         match unprocessed {
             Cow::Borrowed(unprocessed) => percent_decode_str(unprocessed).decode_utf8().map_err(ProcessError::Utf8),
             Cow::Owned(unprocessed) => match percent_decode_str(&unprocessed).decode_utf8().map_err(ProcessError::Utf8)? {
@@ -204,7 +319,7 @@ impl ThunkProcessor for PercentDecoder {
     }
 
     fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
-        Ok(utf8_percent_encode(processed.as_ref(), ROBTOP_SET).into())
+        Ok(utf8_percent_encode(processed.as_ref(), S::SET).into())
     }
 
     fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
@@ -219,15 +334,19 @@ impl ThunkProcessor for Base64Decoder {
     type Error = ProcessError;
     type Output<'a> = Cow<'a, str>;
 
-    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error> {
-        let vec = URL_SAFE.decode(&*unprocessed)?;
+    fn from_unprocessed(unprocessed: Cow<'_, str>) -> Result<Self::Output<'_>, Self::Error> {
+        let vec = crate::util::base64_decode(&*unprocessed)?;
         let string = String::from_utf8(vec).map_err(ProcessError::FromUtf8)?;
 
         Ok(Cow::Owned(string))
     }
 
     fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
-        Ok(Cow::Owned(URL_SAFE.encode(&**processed)))
+        Ok(Cow::Owned(crate::util::base64_encode(&**processed)))
+    }
+
+    fn serialize_unprocessed<S: Serializer>(processed: &Self::Output<'_>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(processed.as_bytes())
     }
 
     fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
@@ -1,38 +1,217 @@
-//! Module exposing a ['memchr'] based iterator for splitting strings
+//! Module exposing a ['memchr'] based iterator for splitting strings, together with the
+//! [`Delimiter`]s it can split at
+//!
+//! Without the `memchr` feature, delimiters are searched for using plain byte-wise comparisons
+//! instead.
+//!
+//! The delimiters of all of RobTop's data formats consist of either a single byte (such as `':'`)
+//! or three bytes (`"~|~"`). For those, the zero-sized [`ByteDelimiter`] and [`ThreeByteDelimiter`]
+//! bake the delimiter into the splitting code at compile time. Arbitrary delimiters are supported
+//! via [`DynamicDelimiter`], which decides how to search for the delimiter at runtime.
 
-use memchr::memmem;
+#[cfg(feature = "memchr")]
+use alloc::boxed::Box;
+use core::fmt::Debug;
+#[cfg(feature = "memchr")]
+use memchr::memmem::Finder;
+
+/// Returns the offset of the first occurrence of `byte` in `haystack`
+#[cfg(feature = "memchr")]
+pub(crate) fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+    memchr::memchr(byte, haystack)
+}
+
+/// Returns the offset of the first occurrence of `byte` in `haystack`
+#[cfg(not(feature = "memchr"))]
+pub(crate) fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&candidate| candidate == byte)
+}
+
+/// A delimiter separating the fields of RobTop's indexed data format
+pub trait Delimiter: Debug {
+    /// The bytes making up this delimiter
+    fn as_bytes(&self) -> &[u8];
+
+    /// Returns the byte offset of the first occurrence of this delimiter in `haystack`
+    fn find(&self, haystack: &[u8]) -> Option<usize>;
+}
+
+impl<D: Delimiter> Delimiter for &D {
+    fn as_bytes(&self) -> &[u8] {
+        (**self).as_bytes()
+    }
+
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        (**self).find(haystack)
+    }
+}
+
+/// A delimiter consisting of the single byte `B`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByteDelimiter<const B: u8>;
+
+impl<const B: u8> ByteDelimiter<B> {
+    const BYTES: [u8; 1] = [B];
+}
+
+impl<const B: u8> Delimiter for ByteDelimiter<B> {
+    fn as_bytes(&self) -> &[u8] {
+        &Self::BYTES
+    }
+
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        find_byte(B, haystack)
+    }
+}
+
+/// A delimiter consisting of the three bytes `A`, `B` and `C`, such as `"~|~"`
+///
+/// Occurrences are found by searching for `A` and comparing the following two bytes, which avoids
+/// constructing a substring searcher for every input.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreeByteDelimiter<const A: u8, const B: u8, const C: u8>;
+
+impl<const A: u8, const B: u8, const C: u8> ThreeByteDelimiter<A, B, C> {
+    const BYTES: [u8; 3] = [A, B, C];
+}
+
+impl<const A: u8, const B: u8, const C: u8> Delimiter for ThreeByteDelimiter<A, B, C> {
+    fn as_bytes(&self) -> &[u8] {
+        &Self::BYTES
+    }
+
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let mut from = 0;
+
+        while let Some(idx) = find_byte(A, &haystack[from..]) {
+            let candidate = from + idx;
+
+            if haystack[candidate..].starts_with(&Self::BYTES) {
+                return Some(candidate);
+            }
+
+            from = candidate + 1;
+        }
+
+        None
+    }
+}
 
 #[derive(Debug)]
-pub struct Split<'de> {
-    input: &'de str,
+enum Searcher {
+    /// Delimiters consisting of a single byte (such as `':'`) can be searched for using plain
+    /// [`memchr::memchr`], which does not need any setup
+    Byte(u8),
+    #[cfg(feature = "memchr")]
+    Substring(Box<Finder<'static>>),
+    /// Never empty, as [`DynamicDelimiter::new`] rejects empty delimiters
+    #[cfg(not(feature = "memchr"))]
+    Substring(&'static [u8]),
+}
+
+/// A delimiter only known at runtime
+///
+/// This is used where the delimiter is not given by some [`GJFormat`](crate::GJFormat), e.g. in
+/// [`from_indexed_str`](crate::from_indexed_str).
+#[derive(Debug)]
+pub struct DynamicDelimiter {
     delimiter: &'static str,
-    finder: memmem::FindIter<'de, 'static>,
+    searcher: Searcher,
+}
+
+impl DynamicDelimiter {
+    /// Constructs a new `DynamicDelimiter` for the given delimiter
+    ///
+    /// # Panics
+    /// Panics if `delimiter` is empty, as splitting at it would never make progress
+    pub fn new(delimiter: &'static str) -> Self {
+        assert!(!delimiter.is_empty(), "delimiter must not be empty");
+
+        let searcher = match delimiter.as_bytes() {
+            [byte] => Searcher::Byte(*byte),
+            #[cfg(feature = "memchr")]
+            _ => Searcher::Substring(Box::new(Finder::new(delimiter))),
+            #[cfg(not(feature = "memchr"))]
+            _ => Searcher::Substring(delimiter.as_bytes()),
+        };
+
+        DynamicDelimiter { delimiter, searcher }
+    }
+}
+
+impl Delimiter for DynamicDelimiter {
+    fn as_bytes(&self) -> &[u8] {
+        self.delimiter.as_bytes()
+    }
+
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        match self.searcher {
+            Searcher::Byte(byte) => find_byte(byte, haystack),
+            #[cfg(feature = "memchr")]
+            Searcher::Substring(ref finder) => finder.find(haystack),
+            #[cfg(not(feature = "memchr"))]
+            Searcher::Substring(needle) => haystack.windows(needle.len()).position(|window| window == needle),
+        }
+    }
+}
+
+/// The delimiters used by the [`GJFormat`](crate::GJFormat)s dash-rs knows about, for which
+/// specialized [`Delimiter`] types exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KnownDelimiter {
+    Colon,
+    Comma,
+    Tilde,
+    TildePipeTilde,
+    Other,
+}
+
+impl KnownDelimiter {
+    pub(crate) const fn of(delimiter: &str) -> Self {
+        match delimiter.as_bytes() {
+            b":" => KnownDelimiter::Colon,
+            b"," => KnownDelimiter::Comma,
+            b"~" => KnownDelimiter::Tilde,
+            b"~|~" => KnownDelimiter::TildePipeTilde,
+            _ => KnownDelimiter::Other,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Split<'de, D> {
+    input: &'de str,
+    delimiter: D,
     end_of_last_delimiter: Option<usize>,
 }
 
-impl<'de> Split<'de> {
-    pub fn new(haystack: &'de str, needle: &'static str) -> Self {
+impl<'de, D: Delimiter> Split<'de, D> {
+    pub fn new(haystack: &'de str, delimiter: D) -> Self {
         Split {
             input: haystack,
-            delimiter: needle,
-            finder: memmem::find_iter(haystack.as_ref(), needle),
+            delimiter,
             end_of_last_delimiter: Some(0),
         }
     }
 
-    pub fn position(&self) -> usize {
-        self.end_of_last_delimiter.unwrap_or(self.input.len())
+    /// The delimiter this iterator splits at
+    pub fn delimiter(&self) -> &D {
+        &self.delimiter
+    }
+
+    fn find_delimiter(&self, from: usize) -> Option<usize> {
+        self.delimiter.find(&self.input.as_bytes()[from..]).map(|idx| idx + from)
     }
 }
 
-impl<'de> Iterator for Split<'de> {
+impl<'de, D: Delimiter> Iterator for Split<'de, D> {
     type Item = &'de str;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.end_of_last_delimiter {
-            Some(end_of_last) => match self.finder.next() {
+            Some(end_of_last) => match self.find_delimiter(end_of_last) {
                 Some(next) => {
-                    self.end_of_last_delimiter = Some(next + self.delimiter.len());
+                    self.end_of_last_delimiter = Some(next + self.delimiter.as_bytes().len());
                     Some(&self.input[end_of_last..next])
                 },
                 None => {
@@ -44,3 +223,45 @@ impl<'de> Iterator for Split<'de> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::split::{ByteDelimiter, Delimiter, DynamicDelimiter, Split, ThreeByteDelimiter};
+
+    #[test]
+    fn specialized_delimiters_match_dynamic() {
+        let input = "1~|~2~~|~~|~3~|4~|~";
+
+        assert_eq!(
+            Split::new(input, ThreeByteDelimiter::<b'~', b'|', b'~'>).collect::<Vec<_>>(),
+            Split::new(input, DynamicDelimiter::new("~|~")).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Split::new(input, ByteDelimiter::<b'~'>).collect::<Vec<_>>(),
+            Split::new(input, DynamicDelimiter::new("~")).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Split::new(input, ThreeByteDelimiter::<b'~', b'|', b'~'>).collect::<Vec<_>>(),
+            ["1", "2~", "", "3~|4", ""]
+        );
+    }
+
+    #[test]
+    fn dynamic_delimiter_finds_substrings() {
+        let haystack = "1~|2~|~3~|~";
+
+        for delimiter in ["~|~", "|~", "~|", "3~|~", "1~|2~|~3~|~"] {
+            assert_eq!(DynamicDelimiter::new(delimiter).find(haystack.as_bytes()), haystack.find(delimiter));
+        }
+
+        assert_eq!(DynamicDelimiter::new("~|~").find(b"~|"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "delimiter must not be empty")]
+    fn empty_dynamic_delimiter_is_rejected() {
+        DynamicDelimiter::new("");
+    }
+}
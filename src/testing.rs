@@ -0,0 +1,117 @@
+//! Module containing helpers for validating corpora of server responses against dash-rs' models
+//!
+//! These are the checks dash-rs' own test suite runs against its artifacts, exposed so that
+//! authors of private servers can verify that their responses are understood by dash-rs (and
+//! therefore, most likely, by the game).
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{DeError, GJFormat, IndexedDeserializer, SerError};
+
+/// A field whose value changed when loading and re-saving some object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDifference {
+    /// The index of the field. For list-like formats, this is the field's position
+    pub index: String,
+
+    /// The field's value in the original input, or [`None`] if the field was not present
+    pub original: Option<String>,
+
+    /// The field's value after re-saving, or [`None`] if the field was dropped
+    pub saved: Option<String>,
+}
+
+/// Errors that can occur during a [`roundtrip_check`]
+#[derive(Debug, Error)]
+pub enum RoundtripError<'a> {
+    /// The input could not be loaded
+    #[error("failed to load input: {0}")]
+    Load(DeError<'a>),
+
+    /// The loaded object could not be saved again
+    #[error("failed to save loaded object: {0}")]
+    Save(#[from] SerError),
+
+    /// The object was saved, but the result was not made up of the same fields as the input
+    #[error("{} field(s) changed when saving", .0.len())]
+    Mismatch(Vec<FieldDifference>),
+}
+
+/// Loads `raw` as a `T`, saves it again and compares the result with the input
+///
+/// The comparison is permissive in that it does not care about the order of fields in map-like
+/// formats, since RobTop's order is inconsistent anyway. It does however require all fields to
+/// be present in both, with equal values.
+pub fn roundtrip_check<'a, T: GJFormat<'a>>(raw: &'a str) -> Result<(), RoundtripError<'a>> {
+    let loaded = T::from_gj_str(raw).map_err(RoundtripError::Load)?;
+    let saved = loaded.to_gj_string()?;
+
+    let differences = compare_indexed_strings(raw, &saved, T::DELIMITER, T::MAP_LIKE);
+
+    if differences.is_empty() {
+        Ok(())
+    } else {
+        Err(RoundtripError::Mismatch(differences))
+    }
+}
+
+/// Compares two strings in RobTop's data format field-by-field, returning all fields whose value
+/// differs
+///
+/// For map-like formats, fields are matched up by their index, otherwise by their position.
+pub fn compare_indexed_strings(original: &str, saved: &str, delimiter: &'static str, map_like: bool) -> Vec<FieldDifference> {
+    let original = fields(original, delimiter, map_like);
+    let saved = fields(saved, delimiter, map_like);
+
+    let mut differences = Vec::new();
+
+    for (index, original_value) in &original {
+        match saved.get(index) {
+            Some(saved_value) if saved_value == original_value => (),
+            saved_value => differences.push(FieldDifference {
+                index: index.clone(),
+                original: Some(original_value.to_string()),
+                saved: saved_value.map(|value| value.to_string()),
+            }),
+        }
+    }
+
+    for (index, saved_value) in saved {
+        if !original.contains_key(&index) {
+            differences.push(FieldDifference {
+                index,
+                original: None,
+                saved: Some(saved_value.to_string()),
+            })
+        }
+    }
+
+    differences
+}
+
+fn fields<'a>(input: &'a str, delimiter: &'static str, map_like: bool) -> BTreeMap<String, &'a str> {
+    let mut deserializer = IndexedDeserializer::new(input, delimiter, map_like);
+
+    // Deserializing into strings cannot fail, except for odd numbers of fields in map-like formats,
+    // in which case we simply compare what we could parse
+    if map_like {
+        BTreeMap::<&str, &str>::deserialize(&mut deserializer)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(index, value)| (index.to_string(), value))
+            .collect()
+    } else {
+        Vec::<&str>::deserialize(&mut deserializer)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(position, value)| (position.to_string(), value))
+            .collect()
+    }
+}
@@ -1,6 +1,8 @@
 //! Module containing various utility functions related to processing Geometry Dash data
 
-use serde::Serializer;
+use alloc::{string::String, vec::Vec};
+use base64::{engine::general_purpose::URL_SAFE, DecodeError, DecodeSliceError, Engine};
+use core::{num::ParseIntError, str::FromStr};
 
 /// Performs RobTop's XOR en-/decoding routine on `encoded` using `key`
 ///
@@ -18,6 +20,237 @@ where
     encoded.iter_mut().zip(key.as_ref().iter().cycle()).for_each(|(d, k)| *d ^= k);
 }
 
+/// The keys RobTop's XOR encoding (see [`cyclic_xor`]) is used with throughout the game
+pub mod xor_keys {
+    /// The key used to encode level passwords
+    ///
+    /// ## GD Internals:
+    /// Level passwords are XOR-ed with this key and then urlsafe base64 encoded
+    pub const LEVEL_PASSWORD: &str = "26364";
+
+    /// The key used to encode the subject and body of private messages
+    ///
+    /// ## GD Internals:
+    /// Message contents are XOR-ed with this key and then urlsafe base64 encoded
+    pub const MESSAGE: &str = "14251";
+
+    /// The key used to encode the `chk` of comment uploads
+    pub const COMMENT_CHK: &str = "29481";
+
+    /// The key used to encode the `seed2` of level uploads, see
+    /// [`level_seed2`](super::chk::level_seed2)
+    pub const LEVEL_SEED: &str = "41274";
+
+    /// The key used to encode the responses to `getGJChallenges` requests, which contain the
+    /// currently active quests
+    pub const QUEST: &str = "19847";
+
+    /// The key used to encode local save files (`CCGameManager.dat` and `CCLocalLevels.dat`) on
+    /// Windows
+    ///
+    /// ## GD Internals:
+    /// Every byte of the save file is XOR-ed with `11`. The result is urlsafe base64 encoded, gzip
+    /// compressed data
+    pub const SAVE_FILE: &[u8] = &[11];
+}
+
+/// The salts RobTop appends to values before hashing them, e.g. when computing a `chk` (see
+/// [`generate_chk`](chk::generate_chk))
+///
+/// ## GD Internals:
+/// Each endpoint that validates its requests or lets clients validate its responses uses one of
+/// these salts. The endpoints using a salt are listed in its documentation.
+pub mod salts {
+    /// The salt used for hashes of level data
+    ///
+    /// ## GD Internals:
+    /// Used for the `seed2` of `uploadGJLevel21.php` requests (see
+    /// [`level_seed2`](super::chk::level_seed2)) and for the hashes at the end of
+    /// `getGJLevels21.php` (see [`level_list_hash`](super::chk::level_list_hash)) and
+    /// `downloadGJLevel22.php` responses
+    pub const LEVEL: &str = "xI25fpAapCQg";
+
+    /// The salt used for the `chk` of `uploadGJComment21.php` requests
+    pub const COMMENT: &str = "xPT6iUrtws0J";
+
+    /// The salt appended to an account's password before hashing it into a `gjp2` value, see
+    /// [`Authentication::hash_password`](crate::request::Authentication::hash_password)
+    pub const GJP2: &str = "mI29fmAnxgTs";
+
+    /// The salt used for the `chk` of `likeGJItem211.php` and `rateGJStars211.php` requests
+    pub const LIKE: &str = "ysg6pUrtjn0J";
+
+    /// The salt used for the `seed2` of `updateGJUserScore22.php` requests
+    pub const USER_SCORE: &str = "xI35fsAapCRg";
+
+    /// The salt used for the `chk` of `getGJLevelScores211.php` requests
+    pub const LEVEL_SCORE: &str = "yPg6pUrtWn0J";
+}
+
+/// Encodes `data` using urlsafe base64 with padding, the variant of base64 used in RobTop's data
+/// formats
+///
+/// With the `base64-simd` feature, this uses the vectorized routines of the `base64-simd` crate.
+pub fn base64_encode<T: AsRef<[u8]> + ?Sized>(data: &T) -> String {
+    #[cfg(feature = "base64-simd")]
+    return base64_simd::URL_SAFE.encode_to_string(data);
+
+    #[cfg(not(feature = "base64-simd"))]
+    URL_SAFE.encode(data)
+}
+
+/// Decodes urlsafe base64 with padding, the inverse of [`base64_encode`]
+///
+/// With the `base64-simd` feature, this uses the vectorized routines of the `base64-simd` crate.
+pub fn base64_decode<T: AsRef<[u8]> + ?Sized>(encoded: &T) -> Result<Vec<u8>, DecodeError> {
+    #[cfg(feature = "base64-simd")]
+    if let Ok(decoded) = base64_simd::URL_SAFE.decode_to_vec(encoded) {
+        return Ok(decoded);
+    }
+
+    // base64-simd's errors do not say what went wrong, so on failure we decode again using the
+    // `base64` crate to get a proper error
+    URL_SAFE.decode(encoded)
+}
+
+/// Decodes urlsafe base64 with padding into `output`, returning the number of bytes written
+///
+/// Like [`base64_decode`], this uses `base64-simd` if the corresponding feature is enabled.
+pub(crate) fn base64_decode_slice<T: AsRef<[u8]> + ?Sized>(encoded: &T, output: &mut [u8]) -> Result<usize, DecodeSliceError> {
+    #[cfg(feature = "base64-simd")]
+    {
+        use base64_simd::AsOut;
+
+        let simd = base64_simd::URL_SAFE;
+
+        // base64-simd panics instead of erroring out if the output buffer is too small
+        if let Ok(length) = simd.decoded_length(encoded.as_ref()) {
+            if length > output.len() {
+                return Err(DecodeSliceError::OutputSliceTooSmall);
+            }
+
+            if let Ok(decoded) = simd.decode(encoded.as_ref(), output.as_out()) {
+                return Ok(decoded.len());
+            }
+        }
+    }
+
+    URL_SAFE.decode_slice(encoded, output)
+}
+
+/// Applies RobTop's XOR encoding with the given `key` to `data` and base64 encodes the result, as
+/// done for example for level passwords and private messages
+pub fn xor_base64_encode<K: AsRef<[u8]> + ?Sized>(data: &[u8], key: &K) -> String {
+    let mut data = data.to_vec();
+
+    cyclic_xor(&mut data, key);
+
+    base64_encode(&data)
+}
+
+/// Base64 decodes `encoded` and undoes RobTop's XOR encoding with the given `key`, the inverse of
+/// [`xor_base64_encode`]
+pub fn xor_base64_decode<T, K>(encoded: &T, key: &K) -> Result<Vec<u8>, DecodeError>
+where
+    T: AsRef<[u8]> + ?Sized,
+    K: AsRef<[u8]> + ?Sized,
+{
+    let mut decoded = base64_decode(encoded)?;
+
+    cyclic_xor(&mut decoded, key);
+
+    Ok(decoded)
+}
+
+/// Functions for generating the `chk` values the servers use to validate some requests
+pub mod chk {
+    use super::{salts, xor_base64_encode, xor_keys};
+    use crate::model::level::Level;
+    use alloc::string::{String, ToString};
+    use sha1_smol::Sha1;
+
+    /// The number of characters sampled from a level string to compute its `seed2`
+    const LEVEL_SEED_SAMPLES: usize = 50;
+
+    /// Generates a `chk` value from the given `values`
+    ///
+    /// ## GD Internals:
+    /// A `chk` is computed by concatenating the given values and the endpoint specific `salt`,
+    /// taking the hex-encoded SHA-1 digest of the result, XOR-ing it with `key` and then urlsafe
+    /// base64 encoding it
+    pub fn generate_chk(values: &[&str], salt: &str, key: &str) -> String {
+        let mut hasher = Sha1::new();
+
+        for value in values {
+            hasher.update(value.as_bytes());
+        }
+        hasher.update(salt.as_bytes());
+
+        xor_base64_encode(hasher.digest().to_string().as_bytes(), key)
+    }
+
+    /// Samples the characters of `level_string` that go into its `seed2`, see [`level_seed2`]
+    ///
+    /// ## GD Internals:
+    /// Level strings of fewer than 50 characters are used as-is. Otherwise, 50 characters are
+    /// sampled at evenly spaced positions, with the distance between them being the level string's
+    /// length divided by 50 (rounded down)
+    pub fn level_seed(level_string: &str) -> String {
+        let length = level_string.chars().count();
+
+        if length < LEVEL_SEED_SAMPLES {
+            return level_string.to_string();
+        }
+
+        level_string
+            .chars()
+            .step_by(length / LEVEL_SEED_SAMPLES)
+            .take(LEVEL_SEED_SAMPLES)
+            .collect()
+    }
+
+    /// Computes the `seed2` value the servers use to validate level uploads
+    ///
+    /// `level_string` is the level data as sent in the upload request, meaning base64 encoded and
+    /// compressed. Since the servers compute the same value, this can also be used to check that
+    /// downloaded level data matches what the game would have uploaded.
+    ///
+    /// ## GD Internals:
+    /// This is a `chk` (see [`generate_chk`]) whose only value is the [`level_seed`] of the level
+    /// string
+    pub fn level_seed2(level_string: &str) -> String {
+        generate_chk(&[&level_seed(level_string)], salts::LEVEL, xor_keys::LEVEL_SEED)
+    }
+
+    /// Computes the hash the servers append to `getGJLevels21.php` responses, from the levels
+    /// contained in the response
+    ///
+    /// The game uses this hash to detect tampered responses. Comparing it to the hash in a
+    /// response checks that the level section was received (and parsed) intact.
+    ///
+    /// ## GD Internals:
+    /// For each level, the first and last digit of its ID, its star count and whether its coins
+    /// are verified (`0` or `1`) are concatenated. The hash is the hex-encoded SHA-1 digest of the
+    /// result with the [`LEVEL`](salts::LEVEL) salt appended.
+    pub fn level_list_hash<'l, 'a: 'l, Data: 'l, Song: 'l, User: 'l>(
+        levels: impl IntoIterator<Item = &'l Level<'a, Data, Song, User>>,
+    ) -> String {
+        let mut hasher = Sha1::new();
+        let mut buffer = itoa::Buffer::new();
+
+        for level in levels {
+            let level_id = buffer.format(level.level_id).as_bytes();
+
+            hasher.update(&[level_id[0], level_id[level_id.len() - 1]]);
+            hasher.update(itoa::Buffer::new().format(level.stars).as_bytes());
+            hasher.update(if level.coins_verified { b"1" } else { b"0" });
+        }
+        hasher.update(salts::LEVEL.as_bytes());
+
+        hasher.digest().to_string()
+    }
+}
+
 pub fn option_variant_eq<A, B>(a: &Option<A>, b: &Option<B>) -> bool
 where
     A: PartialEq<B>,
@@ -29,53 +262,244 @@ where
     }
 }
 
-pub(crate) mod default_to_none {
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+/// Parses a list of ids separated by `separator`, as RobTop uses for example for the songs used by a
+/// level (separated by commas). The empty string is parsed into an empty list
+pub(crate) fn parse_id_list<T: FromStr<Err = ParseIntError>>(list: &str, separator: char) -> Result<Vec<T>, ParseIntError> {
+    if list.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    pub fn serialize<S, T>(to_serialize: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-        T: Default + Serialize,
-    {
-        match to_serialize {
-            None => T::default().serialize(serializer),
-            Some(ref t) => t.serialize(serializer),
+    list.split(separator).map(str::parse).collect()
+}
+
+/// Formats a list of ids into a list separated by `separator`, the inverse of [`parse_id_list`]
+pub(crate) fn format_id_list<T: itoa::Integer + Copy>(ids: &[T], separator: char) -> String {
+    let mut list = String::new();
+    let mut buffer = itoa::Buffer::new();
+
+    for (i, id) in ids.iter().enumerate() {
+        if i != 0 {
+            list.push(separator);
         }
+        list.push_str(buffer.format(*id));
     }
 
-    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
-    where
-        D: Deserializer<'de>,
-        T: Default + Deserialize<'de> + PartialEq,
-    {
-        let deserialized = T::deserialize(deserializer)?;
+    list
+}
+
+pub mod serde_adapters {
+    //! Adapters for use with serde's `with`, `serialize_with` and `deserialize_with` attributes,
+    //! implementing the conventions RobTop's data formats use for booleans and absent values.
+    //!
+    //! These are meant for models that are (de)serialized via dash-rs'
+    //! [`IndexedDeserializer`](crate::IndexedDeserializer) and
+    //! [`IndexedSerializer`](crate::IndexedSerializer), for example:
+    //!
+    //! ```
+    //! use serde::{Deserialize, Serialize};
+    //!
+    //! #[derive(Serialize, Deserialize)]
+    //! struct MyModel {
+    //!     #[serde(rename = "1")]
+    //!     #[serde(with = "dash_rs::util::serde_adapters::default_to_none")]
+    //!     copied_id: Option<u64>,
+    //!
+    //!     #[serde(rename = "2")]
+    //!     #[serde(serialize_with = "dash_rs::util::serde_adapters::false_to_empty_string")]
+    //!     is_demon: bool,
+    //! }
+    //! ```
+    //!
+    //! ## GD Internals:
+    //! RobTop encodes `false` as either `0` or the empty string, and `true` as either `1`, `2` or
+    //! `10`, depending on the field. The [`IndexedDeserializer`](crate::IndexedDeserializer)
+    //! accepts all of these, but always serializes booleans as `0` and `1`. For fields using a
+    //! different encoding, use the matching `serialize_with` adapter from this module.
+    //!
+    //! Many numeric fields use `0` to indicate that a value is absent (e.g. the ID of the level a
+    //! level was copied from). Such fields should be modelled as [`Option`]s using
+    //! [`default_to_none`].
+
+    use alloc::vec::Vec;
+    use core::fmt::Formatter;
+
+    use serde::{
+        de::{Error, SeqAccess, Unexpected, Visitor},
+        Deserializer, Serializer,
+    };
+
+    /// Maps the [`Default`] value of `T` to [`None`] during deserialization, and [`None`] to the
+    /// default value during serialization. For numeric types, this means `0` is treated as the
+    /// absence of a value.
+    ///
+    /// Use via `#[serde(with = "dash_rs::util::serde_adapters::default_to_none")]`
+    pub mod default_to_none {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-        if deserialized == T::default() {
-            Ok(None)
-        } else {
-            Ok(Some(deserialized))
+        pub fn serialize<S, T>(to_serialize: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+            T: Default + Serialize,
+        {
+            match to_serialize {
+                None => T::default().serialize(serializer),
+                Some(ref t) => t.serialize(serializer),
+            }
+        }
+
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: Default + Deserialize<'de> + PartialEq,
+        {
+            let deserialized = T::deserialize(deserializer)?;
+
+            if deserialized == T::default() {
+                Ok(None)
+            } else {
+                Ok(Some(deserialized))
+            }
         }
     }
-}
 
-pub(crate) fn false_to_empty_string<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
-    match *b {
-        true => serializer.serialize_str("1"),
-        false => serializer.serialize_str(""),
+    /// Deserializes a boolean from any of the encodings found in RobTop's data formats and in older
+    /// JSON representations of dash-rs models: actual booleans, the integers `0`, `1`, `2` and
+    /// `10`, their string representations, the empty string and `null` (which are both `false`)
+    ///
+    /// Use via `#[serde(deserialize_with = "dash_rs::util::serde_adapters::lenient_bool")]`
+    pub fn lenient_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+        struct LenientBoolVisitor;
+
+        impl<'de> Visitor<'de> for LenientBoolVisitor {
+            type Value = bool;
+
+            fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+                formatter.write_str("a boolean, 0, 1, 2, 10, the empty string or null")
+            }
+
+            fn visit_bool<E: Error>(self, v: bool) -> Result<bool, E> {
+                Ok(v)
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<bool, E> {
+                match v {
+                    0 => Ok(false),
+                    1 | 2 | 10 => Ok(true),
+                    _ => Err(E::invalid_value(Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<bool, E> {
+                if v < 0 {
+                    return Err(E::invalid_value(Unexpected::Signed(v), &self));
+                }
+
+                self.visit_u64(v as u64)
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<bool, E> {
+                match v {
+                    "" | "0" => Ok(false),
+                    "1" | "2" | "10" => Ok(true),
+                    _ => Err(E::invalid_value(Unexpected::Str(v), &self)),
+                }
+            }
+
+            fn visit_none<E: Error>(self) -> Result<bool, E> {
+                Ok(false)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+                deserializer.deserialize_any(self)
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<bool, E> {
+                Ok(false)
+            }
+        }
+
+        deserializer.deserialize_any(LenientBoolVisitor)
     }
-}
 
-pub(crate) fn true_to_two<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
-    match *b {
-        true => serializer.serialize_str("2"),
-        false => serializer.serialize_str("0"),
+    /// Deserializes an optional list of ids, either from a sequence or from its representation in
+    /// requests: a comma separated list, optionally enclosed in parenthesis. A single dash (`-`)
+    /// is an empty list, while `null` and the empty string are the absence of a list.
+    ///
+    /// This is the inverse of how requests write the `completedLevels` field, and allows
+    /// deserializing requests from their key-value pairs (see
+    /// [`GdRequest::to_pairs`](crate::request::GdRequest::to_pairs)).
+    ///
+    /// Use via `#[serde(deserialize_with = "dash_rs::util::serde_adapters::parenthesized_id_list")]`
+    pub fn parenthesized_id_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u64>>, D::Error> {
+        struct IdListVisitor;
+
+        impl<'de> Visitor<'de> for IdListVisitor {
+            type Value = Option<Vec<u64>>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+                formatter.write_str("a sequence of ids or a (parenthesized) comma separated list of ids")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                let list = match v {
+                    "" => return Ok(None),
+                    "-" => "",
+                    _ => v.strip_prefix('(').and_then(|v| v.strip_suffix(')')).unwrap_or(v),
+                };
+
+                super::parse_id_list(list, ',')
+                    .map(Some)
+                    .map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut ids = Vec::new();
+
+                while let Some(id) = seq.next_element()? {
+                    ids.push(id);
+                }
+
+                Ok(Some(ids))
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_any(self)
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+        }
+
+        deserializer.deserialize_any(IdListVisitor)
+    }
+
+    /// Serializes `true` as `1` and `false` as the empty string
+    pub fn false_to_empty_string<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        match *b {
+            true => serializer.serialize_str("1"),
+            false => serializer.serialize_str(""),
+        }
     }
-}
 
-pub(crate) fn true_to_ten<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
-    match *b {
-        true => serializer.serialize_str("10"),
-        false => serializer.serialize_str("0"),
+    /// Serializes `true` as `2` and `false` as `0`
+    pub fn true_to_two<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        match *b {
+            true => serializer.serialize_str("2"),
+            false => serializer.serialize_str("0"),
+        }
+    }
+
+    /// Serializes `true` as `10` and `false` as `0`
+    pub fn true_to_ten<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        match *b {
+            true => serializer.serialize_str("10"),
+            false => serializer.serialize_str("0"),
+        }
     }
 }
 
@@ -105,18 +529,43 @@ macro_rules! dash_rs_newtype {
         pub struct $name<'a>(pub Cow<'a, str>);
 
         impl<'a> $crate::serde::InternalProxy for $name<'a> {
-            type DeserializeProxy = &'a str;
+            type DeserializeProxy = $crate::serde::StrProxy<'a>;
             type SerializeProxy<'b> = &'b str where Self: 'b;
 
             fn to_serialize_proxy(&self) -> &str {
-                use std::borrow::Borrow;
+                use core::borrow::Borrow;
 
                 self.0.borrow()
             }
 
-            fn from_deserialize_proxy(from: &'a str) -> $name<'a> {
-                $name(Cow::Borrowed(from))
+            fn from_deserialize_proxy(from: $crate::serde::StrProxy<'a>) -> $name<'a> {
+                $name(from.0)
             }
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::URL_SAFE, DecodeSliceError, Engine};
+
+    use crate::util::{base64_decode, base64_decode_slice, base64_encode};
+
+    #[test]
+    fn base64_matches_base64_crate() {
+        let data = "A level description long enough to hit the vectorized code paths.".as_bytes();
+        let encoded = URL_SAFE.encode(data);
+
+        assert_eq!(base64_encode(data), encoded);
+        assert_eq!(base64_decode(&encoded).unwrap(), data);
+        assert_eq!(base64_decode("Zm9v!"), URL_SAFE.decode("Zm9v!"));
+
+        let mut buffer = [0; 128];
+
+        assert_eq!(base64_decode_slice(&encoded, &mut buffer), Ok(data.len()));
+        assert_eq!(
+            base64_decode_slice(&encoded, &mut buffer[..16]),
+            Err(DecodeSliceError::OutputSliceTooSmall)
+        );
+    }
+}
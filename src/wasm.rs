@@ -0,0 +1,96 @@
+//! Module containing a JSON based API surface for consumers that cannot use dash-rs' types
+//! directly, most notably browser tools that use dash-rs compiled to WebAssembly.
+//!
+//! All functions in this module take and return owned strings (or string slices), so that they
+//! can be exposed through `wasm-bindgen` by thin, non-generic wrappers. Since nothing can borrow
+//! from the input across such a boundary, parsing errors are converted into their string
+//! representation.
+//!
+//! Note that on `wasm32` targets, dash-rs should be used with `default-features = false` and the
+//! `wasm` feature, to avoid linking against the system's zlib.
+
+use alloc::string::{String, ToString};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{response, response::ResponseError, DeError, GJFormat, SerError};
+
+/// Errors that can occur when converting between RobTop's data formats and JSON
+#[derive(Debug, Error)]
+pub enum JsonError {
+    /// The input could not be parsed as RobTop's data format
+    #[error("{0}")]
+    Parse(String),
+
+    /// Serialization into RobTop's data format failed
+    #[error("{0}")]
+    Serialize(#[from] SerError),
+
+    /// Conversion from or to JSON failed
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl From<DeError<'_>> for JsonError {
+    fn from(err: DeError<'_>) -> Self {
+        JsonError::Parse(err.to_string())
+    }
+}
+
+impl From<ResponseError<'_>> for JsonError {
+    fn from(err: ResponseError<'_>) -> Self {
+        JsonError::Parse(err.to_string())
+    }
+}
+
+/// Parses `input` as an object in RobTop's data format and converts it to JSON
+pub fn gj_to_json<'a, T>(input: &'a str) -> Result<String, JsonError>
+where
+    T: GJFormat<'a> + Serialize,
+{
+    Ok(serde_json::to_string(&T::from_gj_str(input)?)?)
+}
+
+/// Parses `json` as the JSON representation of some object and converts it into RobTop's data
+/// format
+pub fn json_to_gj<'a, T>(json: &'a str) -> Result<String, JsonError>
+where
+    T: GJFormat<'a> + Deserialize<'a>,
+{
+    Ok(serde_json::from_str::<T>(json)?.to_gj_string()?)
+}
+
+/// Converts a response to a `getGJLevels` request to a JSON array of listed levels
+pub fn levels_response_to_json(response: &str) -> Result<String, JsonError> {
+    Ok(serde_json::to_string(&response::parse_get_gj_levels_response(response)?)?)
+}
+
+/// Converts a response to a `downloadGJLevel` request to the JSON representation of the level
+pub fn level_response_to_json(response: &str) -> Result<String, JsonError> {
+    Ok(serde_json::to_string(&response::parse_download_gj_level_response(response)?)?)
+}
+
+/// Converts a response to a `getGJUserInfo` request to the JSON representation of the profile
+pub fn user_info_response_to_json(response: &str) -> Result<String, JsonError> {
+    Ok(serde_json::to_string(&response::parse_get_gj_user_info_response(response)?)?)
+}
+
+/// Converts a response to a `getGJUsers` request to the JSON representation of the found user
+pub fn users_response_to_json(response: &str) -> Result<String, JsonError> {
+    Ok(serde_json::to_string(&response::parse_get_gj_users_response(response)?)?)
+}
+
+/// Converts a response to a `getGJComments` request to a JSON array of level comments
+pub fn comments_response_to_json(response: &str) -> Result<String, JsonError> {
+    let (comments, _) = response::parse_get_gj_comments_response(response)?;
+
+    Ok(serde_json::to_string(&comments)?)
+}
+
+/// Converts a response to a `getGJAccountComments` request to a JSON array of profile comments
+pub fn account_comments_response_to_json(response: &str) -> Result<String, JsonError> {
+    let comments = response::parse_get_gj_acccount_comments_response(response)?;
+
+    Ok(serde_json::to_string(&comments)?)
+}
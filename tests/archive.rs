@@ -0,0 +1,81 @@
+#![cfg(feature = "archive")]
+
+use dash_rs::{
+    archive::{self, ArchiveError, Archived},
+    model::{creator::Creator, level::Level, song::NewgroundsSong, user::profile::Profile},
+    GJFormat,
+};
+use rusqlite::Connection;
+
+macro_rules! assert_archive_roundtrip {
+    ($connection: expr, $object: expr, $key: expr, $model: ty) => {
+        archive::archive(&$connection, &$object).unwrap();
+
+        let archived = archive::load::<$model>(&$connection, $key).unwrap().unwrap();
+
+        assert_eq!(archived.parse::<$model>().unwrap(), $object);
+    };
+}
+
+#[test]
+fn test_archive_roundtrip() {
+    let connection = Connection::open_in_memory().unwrap();
+
+    archive::create_tables(&connection).unwrap();
+    // Creating the tables is idempotent
+    archive::create_tables(&connection).unwrap();
+
+    let level = Level::from_gj_str(include_str!("artifacts/level/11774780/raw").trim_end()).unwrap();
+    let creator = Creator::from_gj_str(include_str!("artifacts/creator/37573/raw").trim_end()).unwrap();
+    let song = NewgroundsSong::from_gj_str(include_str!("artifacts/song/771517/raw").trim_end()).unwrap();
+    let profile = Profile::from_gj_str(include_str!("artifacts/profile/8451/raw").trim_end()).unwrap();
+
+    assert_archive_roundtrip!(connection, level, level.level_id, Level);
+    assert_archive_roundtrip!(connection, creator, creator.user_id, Creator);
+    assert_archive_roundtrip!(connection, song, song.song_id, NewgroundsSong);
+    assert_archive_roundtrip!(connection, profile, profile.account_id, Profile);
+
+    // Archiving again replaces the existing row
+    archive::archive(&connection, &creator).unwrap();
+
+    let (count, name): (u32, String) = connection
+        .query_row("SELECT COUNT(*), name FROM creators", [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap();
+
+    assert_eq!(count, 1);
+    assert_eq!(name, creator.name);
+    assert!(archive::load::<Creator>(&connection, 1).unwrap().is_none());
+}
+
+#[test]
+fn test_archive_rejects_out_of_range_ids() {
+    let connection = Connection::open_in_memory().unwrap();
+
+    archive::create_tables(&connection).unwrap();
+
+    let mut creator = Creator::from_gj_str("4170784:Serponge:119741").unwrap();
+
+    creator.user_id = u64::MAX;
+
+    assert!(matches!(
+        archive::archive(&connection, &creator),
+        Err(ArchiveError::Sql(rusqlite::Error::ToSqlConversionFailure(_)))
+    ));
+    assert!(archive::load::<Creator>(&connection, u64::MAX).is_err());
+}
+
+#[test]
+fn test_archived_to_and_from_sql() {
+    let connection = Connection::open_in_memory().unwrap();
+    let creator = Creator::from_gj_str("4170784:Serponge:119741").unwrap();
+
+    connection.execute("CREATE TABLE custom (data TEXT)", []).unwrap();
+    connection
+        .execute("INSERT INTO custom (data) VALUES (?1)", [Archived::new(&creator).unwrap()])
+        .unwrap();
+
+    let archived: Archived = connection.query_row("SELECT data FROM custom", [], |row| row.get(0)).unwrap();
+
+    assert_eq!(archived.as_str(), "4170784:Serponge:119741");
+    assert_eq!(archived.parse::<Creator>().unwrap(), creator);
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "parallel")]
+
+use dash_rs::{batch::parse_levels_parallel, model::level::Level, GJFormat};
+
+#[test]
+fn parse_levels_in_parallel() {
+    let raw = [
+        include_str!("artifacts/listed_level/11774780/raw").trim_end(),
+        "1:not a level id",
+        include_str!("artifacts/listed_level/63355989/raw").trim_end(),
+    ];
+
+    let levels = parse_levels_parallel(&raw);
+
+    assert_eq!(levels.len(), 3);
+    assert_eq!(levels[0].as_ref().unwrap().level_id, 11774780);
+    assert_eq!(levels[1].as_ref().unwrap_err().index, 1);
+    assert_eq!(levels[2].as_ref().unwrap(), &Level::<()>::from_gj_str(raw[2]).unwrap());
+}
@@ -0,0 +1,298 @@
+#![cfg(feature = "client")]
+
+use std::time::Duration;
+
+use dash_rs::{
+    client::{
+        batch_download_levels,
+        cache::{request_key, CachingTransport, DirectoryCache, MemoryCache, ResponseCache},
+        retry::{Failure, RetryHooks, RetryPolicy, RetryPolicyError, RetryingTransport},
+        send, BatchConfig, BatchError, ClientBuilder, PageIterator, RateLimiter, Transport, CLOUDFLARE_RATE_LIMITED,
+    },
+    request::{
+        comment::ProfileCommentsRequest,
+        level::{LevelRequestType, LevelsRequest},
+        user::UserRequest,
+        GdRequest, Paginated,
+    },
+    response::ResponseError,
+};
+
+const CONFIG: BatchConfig = BatchConfig {
+    backoff: Duration::ZERO,
+    max_retries: 1,
+};
+
+#[test]
+fn rate_limiter_burst() {
+    let mut limiter = RateLimiter::new(0.001).with_burst(2);
+
+    assert!(limiter.try_acquire().is_ok());
+    assert!(limiter.try_acquire().is_ok());
+    assert!(limiter.try_acquire().is_err());
+}
+
+#[test]
+fn batch_download_partial_results() {
+    let mut requests = 0;
+    let mut transport = |_: &str, body: String| -> Result<String, ()> {
+        requests += 1;
+
+        if body.contains("levelID=1&") {
+            Ok("1:1:2:level".to_string())
+        } else if body.contains("levelID=2&") {
+            Ok("-1".to_string())
+        } else {
+            Ok(CLOUDFLARE_RATE_LIMITED.to_string())
+        }
+    };
+
+    let result = batch_download_levels([1, 2, 3, 4], &mut transport, &mut RateLimiter::new(1000.0), CONFIG);
+
+    // 1 request each for levels 1 and 2, 2 for level 3, none for level 4
+    assert_eq!(requests, 4);
+
+    assert_eq!(result.succeeded, vec![(1, "1:1:2:level".to_string())]);
+    assert!(matches!(result.failed[0], (2, BatchError::Response(ResponseError::NotFound))));
+    assert!(matches!(result.failed[1], (3, BatchError::RateLimited)));
+    assert!(matches!(result.failed[2], (4, BatchError::RateLimited)));
+    assert!(result.failed[2].1.is_retriable());
+}
+
+#[test]
+fn batch_config_backoff_saturates() {
+    let policy = RetryPolicy::from(BatchConfig {
+        backoff: Duration::from_secs(60),
+        max_retries: u32::MAX,
+    });
+
+    assert_eq!(policy.max_attempts, u32::MAX);
+    assert_eq!(policy.backoff(1), Duration::from_secs(120));
+    assert_eq!(policy.backoff(u32::MAX - 1), Duration::MAX);
+}
+
+#[test]
+fn caching_transport() {
+    let mut requests = 0;
+    let transport = |_: &str, body: String| -> Result<String, ()> {
+        requests += 1;
+
+        Ok(match &body[..] {
+            "missing" => "-1".to_string(),
+            "empty" => String::new(),
+            "limited" => CLOUDFLARE_RATE_LIMITED.to_string(),
+            _ => body,
+        })
+    };
+    let mut transport = CachingTransport::new(transport, MemoryCache::new(), Duration::from_secs(60));
+
+    assert_eq!(transport.post("url", "a".to_string()).unwrap(), "a");
+    assert_eq!(transport.post("url", "a".to_string()).unwrap(), "a");
+    assert_eq!(transport.post("other", "a".to_string()).unwrap(), "a");
+    assert_eq!(transport.post("url", "missing".to_string()).unwrap(), "-1");
+    assert_eq!(transport.post("url", "missing".to_string()).unwrap(), "-1");
+
+    for body in ["empty", "limited"] {
+        transport.post("url", body.to_string()).unwrap();
+        transport.post("url", body.to_string()).unwrap();
+    }
+
+    drop(transport);
+
+    assert_eq!(requests, 8);
+}
+
+#[test]
+fn cache_forever() {
+    let mut cache = MemoryCache::new();
+
+    cache.put(1, "forever", Duration::MAX);
+    cache.evict_expired();
+
+    assert_eq!(cache.get(1).as_deref(), Some("forever"));
+
+    let directory = std::env::temp_dir().join(format!("dash-rs-cache-forever-test-{}", std::process::id()));
+    let mut cache = DirectoryCache::new(&directory).unwrap();
+
+    cache.put(1, "forever", Duration::MAX);
+
+    assert_eq!(cache.get(1).as_deref(), Some("forever"));
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn directory_cache_expiry() {
+    let directory = std::env::temp_dir().join(format!("dash-rs-cache-test-{}", std::process::id()));
+    let mut cache = DirectoryCache::new(&directory).unwrap();
+    let key = request_key("url", "body");
+
+    cache.put(key, "response\nwith newline", Duration::from_secs(60));
+    cache.put(key + 1, "expired", Duration::ZERO);
+
+    assert_eq!(cache.get(key).as_deref(), Some("response\nwith newline"));
+    assert_eq!(cache.get(key + 1), None);
+    assert_eq!(cache.get(key + 2), None);
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn send_gd_request() {
+    let mut transport = |url: &str, body: String| -> Result<String, ()> {
+        assert_eq!(url, "https://www.boomlings.com/database/getGJUserInfo20.php");
+        assert!(body.ends_with("&targetAccountID=71"));
+
+        Ok("-1".to_string())
+    };
+
+    let response = send(&mut transport, &UserRequest::new(71)).unwrap();
+
+    assert!(matches!(UserRequest::parse_response(&response), Err(ResponseError::NotFound)));
+
+    let invalid = LevelsRequest::default().request_type(LevelRequestType::Followed);
+
+    assert!(matches!(send(&mut transport, &invalid), Err(BatchError::Invalid(_))));
+}
+
+#[derive(Default)]
+struct Metrics {
+    retries: Vec<u32>,
+    gave_up: bool,
+    succeeded_after: Option<u32>,
+}
+
+impl RetryHooks<&'static str> for Metrics {
+    fn on_retry(&mut self, _url: &str, attempt: u32, _failure: Failure<'_, &'static str>, _delay: Duration) {
+        self.retries.push(attempt)
+    }
+
+    fn on_give_up(&mut self, _url: &str, _attempts: u32, _failure: Failure<'_, &'static str>) {
+        self.gave_up = true
+    }
+
+    fn on_success(&mut self, _url: &str, attempts: u32) {
+        self.succeeded_after = Some(attempts)
+    }
+}
+
+#[test]
+fn retrying_transport() {
+    let policy = RetryPolicy {
+        max_attempts: 4,
+        initial_backoff: Duration::ZERO,
+        ..RetryPolicy::default()
+    };
+
+    // Fails with a timeout, an empty response and a rate limit before succeeding
+    let mut responses = vec![Ok("1:1:2:level"), Ok(CLOUDFLARE_RATE_LIMITED), Ok(""), Err("timeout")];
+    let transport = move |_: &str, _: String| responses.pop().unwrap().map(ToString::to_string);
+    let mut transport = RetryingTransport::new(transport, policy).with_hooks(Metrics::default());
+
+    assert_eq!(transport.post("url", String::new()).unwrap(), "1:1:2:level");
+    assert_eq!(transport.hooks().retries, vec![1, 2, 3]);
+    assert_eq!(transport.hooks().succeeded_after, Some(4));
+
+    // "-1" is a permanent failure
+    let transport = |_: &str, _: String| -> Result<String, &'static str> { Ok("-1".to_string()) };
+    let mut transport = RetryingTransport::new(transport, policy).with_hooks(Metrics::default());
+
+    assert_eq!(transport.post("url", String::new()).unwrap(), "-1");
+    assert!(transport.hooks().retries.is_empty());
+
+    let transport = |_: &str, _: String| -> Result<String, &'static str> { Err("timeout") };
+    let mut transport = RetryingTransport::new(transport, policy).with_hooks(Metrics::default());
+
+    assert_eq!(transport.post("url", String::new()), Err("timeout"));
+    assert_eq!(transport.hooks().retries, vec![1, 2, 3]);
+    assert!(transport.hooks().gave_up);
+}
+
+#[test]
+fn retry_policy_backoff() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_secs(1),
+        max_backoff: Duration::from_secs(5),
+        ..RetryPolicy::default()
+    };
+
+    assert_eq!(policy.backoff(0), Duration::from_secs(1));
+    assert_eq!(policy.backoff(2), Duration::from_secs(4));
+    assert_eq!(policy.backoff(3), Duration::from_secs(5));
+
+    // The factor overflows to infinity
+    assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(5));
+
+    let invalid = RetryPolicy {
+        multiplier: -2.0,
+        ..policy
+    };
+
+    assert_eq!(invalid.backoff(1), Duration::from_secs(5));
+    assert_eq!(invalid.validate(), Err(RetryPolicyError::InvalidMultiplier(-2.0)));
+    assert!(policy.with_jitter(f64::NAN).is_err());
+    assert!(policy
+        .with_backoff(Duration::from_secs(1), 0.0, Duration::from_secs(5))
+        .is_ok_and(|policy| policy.backoff(1) == Duration::ZERO));
+}
+
+#[test]
+fn client_builder() {
+    let client = ClientBuilder::new()
+        .base_url("https://gdps.example.com/database/")
+        .proxy("http://localhost:8080")
+        .header("X-Gdps-Key", "secret")
+        .user_agent("dash-rs")
+        .build(|config| {
+            assert_eq!(config.proxy.as_deref(), Some("http://localhost:8080"));
+            assert_eq!(config.headers, vec![("X-Gdps-Key".to_string(), "secret".to_string())]);
+
+            let user_agent = config.user_agent.clone();
+
+            Ok::<_, ()>(move |url: &str, _: String| -> Result<String, ()> { Ok(format!("{} {:?}", url, user_agent)) })
+        });
+    let mut client = client.unwrap();
+
+    assert_eq!(
+        client.send(&UserRequest::new(71)).unwrap(),
+        "https://gdps.example.com/database/getGJUserInfo20.php Some(\"dash-rs\")"
+    );
+}
+
+#[test]
+fn page_iterator_stops_at_last_page() {
+    let mut pages = Vec::new();
+    let mut transport = |_: &str, body: String| -> Result<String, ()> {
+        let page: u32 = body.split("page=").nth(1).unwrap().split('&').next().unwrap().parse().unwrap();
+
+        pages.push(page);
+
+        Ok(format!("2~SGVsbG8=~6~{}#25:{}:10", page, page * 10))
+    };
+
+    let responses = PageIterator::new(&mut transport, ProfileCommentsRequest::new(1710032).page(1))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(pages, vec![1, 2]);
+    assert_eq!(ProfileCommentsRequest::page_info(&responses[1]).unwrap().offset, 20);
+}
+
+#[test]
+fn page_iterator_stops_at_not_found() {
+    let mut transport = |_: &str, body: String| -> Result<String, ()> {
+        if body.contains("page=0&") {
+            Ok("2~SGVsbG8=~6~1#100:0:10".to_string())
+        } else {
+            Ok("-1".to_string())
+        }
+    };
+
+    let mut pages = PageIterator::new(&mut transport, ProfileCommentsRequest::new(1710032));
+
+    assert!(pages.next().unwrap().is_ok());
+    assert_eq!(pages.request().current_page(), 1);
+    assert!(pages.next().is_none());
+    assert!(pages.next().is_none());
+}
@@ -2,8 +2,7 @@ use dash_rs::model::comment::{
     level::{CommentUser, LevelComment},
     profile::ProfileComment,
 };
-use framework::load_test_units;
-use std::path::Path;
+use dash_rs_test_macros::golden_roundtrip;
 
 mod framework;
 
@@ -19,26 +18,11 @@ impl framework::Testable for LevelCommentTester {
         if let Some(ref mut cnt) = target.special_color {
             cnt.process().unwrap();
         }
+        target.time_since_post.process().unwrap();
     }
 }
 
-#[test]
-fn test_level_comment() {
-    let units = load_test_units::<LevelCommentTester>(
-        Path::new(env!("CARGO_MANIFEST_DIR"))
-            .join("tests")
-            .join("artifacts")
-            .join("level_comment"),
-    );
-
-    for (path, unit) in units {
-        println!("Testing case {:?}", path);
-
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
-    }
-}
+golden_roundtrip!(LevelCommentTester, "tests/artifacts/level_comment/*");
 
 enum CommentUserTester {}
 
@@ -46,23 +30,7 @@ impl framework::Testable for CommentUserTester {
     type Target<'a> = CommentUser<'a>;
 }
 
-#[test]
-fn test_level_comment_user() {
-    let units = load_test_units::<CommentUserTester>(
-        Path::new(env!("CARGO_MANIFEST_DIR"))
-            .join("tests")
-            .join("artifacts")
-            .join("comment_user"),
-    );
-
-    for (path, unit) in units {
-        println!("Testing case {:?}", path);
-
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
-    }
-}
+golden_roundtrip!(CommentUserTester, "tests/artifacts/comment_user/*");
 
 enum ProfileCommentTester {}
 
@@ -73,23 +41,71 @@ impl framework::Testable for ProfileCommentTester {
         if let Some(ref mut cnt) = target.content {
             cnt.process().unwrap();
         }
+        target.time_since_post.process().unwrap();
     }
 }
 
+golden_roundtrip!(ProfileCommentTester, "tests/artifacts/profile_comment/*");
+
+#[test]
+fn missing_optional_thunk() {
+    use dash_rs::GJFormat;
+
+    // Optional thunks are `None` if their index is missing, and are then also omitted on serialization
+    let comment = ProfileComment::from_gj_str("4~0~9~6 days~6~1922667").unwrap();
+
+    assert!(comment.content.is_none());
+    assert!(!comment.to_gj_string().unwrap().contains("2~"));
+}
+
 #[test]
-fn test_profile_comment() {
-    let units = load_test_units::<ProfileCommentTester>(
-        Path::new(env!("CARGO_MANIFEST_DIR"))
-            .join("tests")
-            .join("artifacts")
-            .join("profile_comment"),
+fn profile_comment_special_characters() {
+    use dash_rs::{
+        model::{RelativeAge, TimeUnit},
+        GJFormat,
+    };
+
+    // RobTop's base64 variant is the urlsafe one, using '-' and '_' instead of '+' and '/'
+    let raw = "2~Pz8_fn5-IMO_IPCfjok=~4~-3~6~1922668~9~1 year";
+    let comment = ProfileComment::from_gj_str(raw).unwrap();
+
+    assert_eq!(
+        comment.content.as_ref().unwrap().as_processed().unwrap().as_ref(),
+        "???~~~ \u{ff} \u{1f389}"
     );
+    assert_eq!(comment.likes, -3);
+    assert_eq!(
+        *comment.time_since_post.as_processed().unwrap(),
+        RelativeAge {
+            amount: 1,
+            unit: TimeUnit::Year
+        }
+    );
+    assert_eq!(comment.to_gj_string().unwrap(), raw);
+}
 
-    for (path, unit) in units {
-        println!("Testing case {:?}", path);
+#[test]
+fn level_comment_typed_fields() {
+    use dash_rs::{model::user::ModLevel, GJFormat};
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
-    }
+    let raw = "2~R3VydS4=~3~2723387~4~63~6~260007~7~1~9~5 days~10~87~11~1";
+    let comment = LevelComment::from_gj_str(raw).unwrap();
+
+    assert_eq!(comment.progress, Some(87));
+    assert_eq!(comment.mod_level, ModLevel::Normal);
+    assert!(comment.is_flagged_spam);
+    assert!(!comment.is_moderator_colored());
+    assert_eq!(comment.to_gj_string().unwrap(), raw);
+
+    let comment = LevelComment::from_gj_str(include_str!("artifacts/level_comment/260007/raw")).unwrap();
+
+    assert_eq!(comment.progress, None);
+    assert!(comment.is_moderator_colored());
+
+    // Normal moderators' comments are sent with a white color
+    let comment = LevelComment::from_gj_str(include_str!("artifacts/level_comment/259333/raw")).unwrap();
+
+    assert_eq!(comment.mod_level, ModLevel::Normal);
+    assert!(comment.special_color.is_some());
+    assert!(!comment.is_moderator_colored());
 }
@@ -0,0 +1,50 @@
+#![cfg(feature = "corpus")]
+
+use std::borrow::Cow;
+
+use dash_rs::corpus::{Corpus, CorpusError};
+
+#[test]
+fn test_corpus_levels() {
+    let path = std::env::temp_dir().join(format!("dash-rs-corpus-{}", std::process::id()));
+    let contents = format!(
+        "{}\r\n\n{}\nnot a level\n",
+        include_str!("artifacts/level/897837/raw").trim_end(),
+        include_str!("artifacts/level/11774780/raw").trim_end()
+    );
+
+    std::fs::write(&path, contents).unwrap();
+
+    let corpus = Corpus::open(&path).unwrap();
+    let lines: Vec<usize> = corpus.lines().map(|(line, _)| line).collect();
+
+    assert_eq!(lines, vec![1, 3, 4]);
+
+    let mut levels = corpus.levels();
+    let first = levels.next().unwrap().unwrap();
+
+    assert_eq!(first.level_id, 897837);
+
+    // Parsed levels borrow straight from the loaded file
+    match first.name {
+        Cow::Borrowed(name) => assert!(corpus.as_bytes().as_ptr_range().contains(&name.as_ptr())),
+        Cow::Owned(_) => panic!("level name was copied out of the corpus"),
+    }
+
+    assert_eq!(levels.next().unwrap().unwrap().level_id, 11774780);
+    assert!(matches!(levels.next(), Some(Err(CorpusError::Parse { line: 4, .. }))));
+    assert!(levels.next().is_none());
+
+    drop(levels);
+    drop(corpus);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_corpus_from_bytes() {
+    let corpus = Corpus::from_bytes(&b"-1\n\nnot a level"[..]);
+    let lines: Vec<usize> = corpus.lines().map(|(line, _)| line).collect();
+
+    assert_eq!(lines, vec![1, 3]);
+    assert!(corpus.levels().all(|level| level.is_err()));
+}
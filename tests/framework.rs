@@ -11,20 +11,6 @@ use dash_rs::{GJFormat, IndexedDeserializer};
 use pretty_assertions::assert_eq;
 use serde::Deserialize;
 
-pub fn load_test_units<D>(unit_dir: impl AsRef<Path>) -> BTreeMap<PathBuf, TestUnit<D>> {
-    let mut map = BTreeMap::new();
-
-    for dir_entry in std::fs::read_dir(unit_dir).unwrap() {
-        let dir_entry = dir_entry.unwrap();
-
-        assert!(dir_entry.metadata().unwrap().is_dir());
-
-        map.insert(dir_entry.path(), TestUnit::new(&dir_entry.path()));
-    }
-
-    map
-}
-
 pub trait Testable {
     type Target<'a>: GJFormat<'a> + Deserialize<'a> + Debug + for<'b> PartialEq<Self::Target<'b>>;
 
@@ -41,7 +27,7 @@ pub struct TestUnit<D> {
 }
 
 impl<D> TestUnit<D> {
-    fn new(unit_container: impl AsRef<Path>) -> Self {
+    pub fn new(unit_container: impl AsRef<Path>) -> Self {
         let raw = unit_container.as_ref().join("raw");
         let processed = unit_container.as_ref().join("processed");
 
@@ -1,7 +1,26 @@
-use std::path::Path;
+#[cfg(feature = "std")]
+use std::io::{BufReader, Read};
+use std::{borrow::Cow, path::Path};
 
-use dash_rs::model::level::Level;
-use framework::load_test_units;
+#[cfg(feature = "std")]
+use base64::{engine::general_purpose::URL_SAFE, Engine};
+#[cfg(feature = "std")]
+use dash_rs::model::level::{CompressionScheme, LevelProcessError};
+use dash_rs::{
+    model::{
+        creator::Creator,
+        level::{
+            save, ExtraString, FeatureTier, Featured, Level, LevelData, LevelRating, ObjectCountDiscrepancy, Objects, Password,
+            VolatileFields,
+        },
+        song::NewgroundsSong,
+    },
+    request::Authentication,
+    GJFormat, Thunk, ThunkProcessor,
+};
+use dash_rs_test_macros::golden_roundtrip;
+#[cfg(feature = "std")]
+use flate2::read::GzDecoder;
 
 mod framework;
 
@@ -17,23 +36,7 @@ impl framework::Testable for LevelTester {
     }
 }
 
-#[test]
-fn test_listed_level() {
-    let units = load_test_units::<LevelTester>(
-        Path::new(env!("CARGO_MANIFEST_DIR"))
-            .join("tests")
-            .join("artifacts")
-            .join("listed_level"),
-    );
-
-    for (path, unit) in units {
-        println!("Testing case {:?}", path);
-
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
-    }
-}
+golden_roundtrip!(LevelTester, "tests/artifacts/listed_level/*");
 
 enum FullLevelTester {}
 
@@ -46,18 +49,459 @@ impl framework::Testable for FullLevelTester {
         }
         level.level_data.level_data.process().unwrap();
         level.level_data.password.process().unwrap();
+        level.level_data.time_since_upload.process().unwrap();
+        level.level_data.time_since_update.process().unwrap();
+    }
+}
+
+// Cannot do round trip testing for onw, as the level data handling in dash-rs is incomplete
+// (to put it nicely). Index 57 is also normalized deliberately, see
+// test_unknown_verification_time_is_saved_as_zero
+golden_roundtrip!(FullLevelTester, "tests/artifacts/level/*", [test_consistency]);
+
+// "demon world" with index 25 set, turning it into an auto level
+const AUTO_LEVEL: &str = "1:72540:2:demon world:5:7:6:37573:8:10:9:50:10:382005:12:9:13:7:14:-3110:17::43:5:25:1:18:10:19:0:42:0:45:0:3:\
+                          aGFwcHkgbmV3IHllYXIhIQ==:15:3:30:0:31:0:37:0:38:0:39:0:46:1:47:2:35:0";
+
+#[test]
+fn test_auto_level_roundtrip() {
+    let level = Level::<()>::from_gj_str(AUTO_LEVEL).unwrap();
+
+    assert_eq!(level.difficulty, LevelRating::Auto);
+
+    let mut buffer = Vec::new();
+    level.write_gj(&mut buffer).unwrap();
+    let saved = std::str::from_utf8(&buffer).unwrap();
+
+    assert_eq!(Level::<()>::from_gj_str(saved).unwrap().difficulty, LevelRating::Auto);
+}
+
+#[test]
+fn test_legacy_two_player_json() {
+    let processed = include_str!("artifacts/listed_level/23298409/processed");
+    let expected: Level<()> = serde_json::from_str(processed).unwrap();
+
+    assert!(expected.two_player);
+
+    for (legacy, two_player) in [
+        (r#""index_31": "1""#, true),
+        (r#""index_31": """#, false),
+        (r#""index_31": null"#, false),
+        (r#""index_31": 0"#, false),
+    ] {
+        let json = processed.replace(r#""two_player": true"#, legacy);
+        let level: Level<()> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(level.two_player, two_player);
+    }
+
+    let invalid = processed.replace(r#""two_player": true"#, r#""index_31": "3""#);
+
+    assert!(serde_json::from_str::<Level<()>>(&invalid).is_err());
+}
+
+#[test]
+fn test_feature_tiers() {
+    let tiers = [
+        ("0", FeatureTier::None),
+        ("1", FeatureTier::Epic),
+        ("2", FeatureTier::Legendary),
+        ("3", FeatureTier::Mythic),
+        ("7", FeatureTier::Unknown(7)),
+    ];
+
+    for (raw, tier) in tiers {
+        let data = AUTO_LEVEL.replace(":42:0:", &format!(":42:{}:", raw));
+        let level = Level::<()>::from_gj_str(&data).unwrap();
+
+        assert_eq!(level.feature_tier, tier);
+
+        let saved = level.to_gj_string().unwrap();
+
+        assert_eq!(Level::<()>::from_gj_str(&saved).unwrap().feature_tier, tier);
+    }
+}
+
+#[test]
+fn test_songs_and_sfx_used() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let data = format!("{}:52:10001234,10005678:53:42", raw);
+    let level = Level::<LevelData>::from_gj_str(&data).unwrap();
+
+    assert_eq!(level.level_data.songs_used, vec![10001234, 10005678]);
+    assert_eq!(level.level_data.sfx_used, vec![42]);
+
+    let saved = level.to_gj_string().unwrap();
+    let reloaded = Level::<LevelData>::from_gj_str(&saved).unwrap();
+
+    assert_eq!(reloaded.level_data.songs_used, vec![10001234, 10005678]);
+    assert_eq!(reloaded.level_data.sfx_used, vec![42]);
+
+    assert!(Level::<LevelData>::from_gj_str(&format!("{}:52:10001234,abc", raw)).is_err());
+}
+
+#[test]
+fn test_editor_and_verification_time() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap();
+
+    assert_eq!(level.editor_seconds, Some(113));
+    assert_eq!(level.editor_seconds_copies, Some(0));
+    assert_eq!(level.level_data.verification_frames, None);
+    assert_eq!(level.level_data.verification_time(), None);
+
+    let data = raw.replace(":57:0", ":57:7200");
+    let level = Level::<LevelData>::from_gj_str(&data).unwrap();
+
+    assert_eq!(level.level_data.verification_frames, Some(7200));
+    assert_eq!(level.level_data.verification_time(), Some(std::time::Duration::from_secs(30)));
+
+    let saved = level.to_gj_string().unwrap();
+    let reloaded = Level::<LevelData>::from_gj_str(&saved).unwrap();
+
+    assert_eq!(reloaded.level_data.verification_frames, Some(7200));
+}
+
+#[test]
+fn test_unknown_verification_time_is_saved_as_zero() {
+    // This artifact provides the empty string at index 57, which is deliberately normalized to "0"
+    let raw = include_str!("artifacts/level/11774780/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap();
+
+    assert!(raw.contains(":57::") || raw.ends_with(":57:"));
+    assert_eq!(level.level_data.verification_frames, None);
+
+    let saved = level.to_gj_string().unwrap();
+    let reloaded = Level::<LevelData>::from_gj_str(&saved).unwrap();
+
+    assert!(saved.contains(":57:0:") || saved.ends_with(":57:0"), "{}", saved);
+    assert_eq!(reloaded.level_data.verification_frames, None);
+}
+
+#[test]
+fn test_extra_string_and_low_detail_mode() {
+    let raw = include_str!("artifacts/level/11774780/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap();
+
+    assert!(!level.level_data.has_low_detail_mode);
+    assert_eq!(
+        level.level_data.extra_string.raw(),
+        "29_566_73_98_29_29_54_424_29_29_237_132_177_29_29_29"
+    );
+    assert_eq!(level.level_data.extra_string.values().count(), 16);
+    assert_eq!(level.level_data.extra_string.values().nth(1), Some(Ok(566)));
+
+    let data = raw.replace(":40::", ":40:1:");
+    let level = Level::<LevelData>::from_gj_str(&data).unwrap();
+
+    assert!(level.level_data.has_low_detail_mode);
+
+    let saved = level.to_gj_string().unwrap();
+
+    assert!(Level::<LevelData>::from_gj_str(&saved).unwrap().level_data.has_low_detail_mode);
+    assert_eq!(ExtraString::default().values().count(), 0);
+}
+
+#[test]
+fn test_prepare_reupload() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap();
+    let request = level.prepare_reupload(Authentication::new(8451, "gjp2"), "stadust").unwrap();
+
+    assert_eq!(request.level_id, 0);
+    assert_eq!(request.name, "time pressure");
+    assert_eq!(request.description, "Rml4ZWQgdGhlIGJ1ZyBhdCA5MSUgMTUvMDkvMjAyMA==");
+    assert_eq!(request.original, 0);
+    assert_eq!(request.main_song, 14);
+    assert_eq!(request.password, Password::PasswordCopy(3101));
+    assert_eq!(request.editor_seconds, 113);
+    assert_eq!(request.level_string, level.level_data.level_data.as_unprocessed().unwrap());
+
+    let body = request.to_string();
+
+    assert!(body.contains("&levelID=0&levelName=time pressure&"));
+    assert!(body.contains("&password=3101&original=0&"));
+    assert!(body.ends_with(&format!("&seed2={}", dash_rs::util::chk::level_seed2(&request.level_string))));
+}
+
+#[test]
+fn test_map_parts() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap();
+    let mapped = Level::<LevelData>::from_gj_str(raw)
+        .unwrap()
+        .map_parts(|song| song.is_some(), |user_id| user_id.to_string());
+
+    assert_eq!(mapped.creator, level.creator.to_string());
+    assert_eq!(mapped.custom_song, level.custom_song.is_some());
+    assert_eq!(mapped.name, level.name);
+    assert_eq!(mapped.level_data, level.level_data);
+}
+
+#[test]
+fn test_eq_stable() {
+    let raw = include_str!("artifacts/listed_level/72540/raw").trim_end();
+    let level = Level::<()>::from_gj_str(raw).unwrap();
+    let mut updated = Level::<()>::from_gj_str(raw).unwrap();
+
+    updated.downloads += 100;
+    updated.likes += 10;
+
+    assert!(level != updated);
+    assert!(level.eq_stable(&updated));
+    assert!(level.eq_ignoring(&updated, VolatileFields::DOWNLOADS | VolatileFields::LIKES));
+    assert!(!level.eq_ignoring(&updated, VolatileFields::DOWNLOADS));
+
+    let mut featured = Level::<()>::from_gj_str(raw).unwrap();
+    let mut refeatured = Level::<()>::from_gj_str(raw).unwrap();
+
+    featured.featured = Featured::Featured(1000);
+    refeatured.featured = Featured::Featured(2000);
+
+    assert!(featured.eq_stable(&refeatured));
+    assert!(!featured.eq_ignoring(&refeatured, VolatileFields::ALL.difference(VolatileFields::FEATURED_WEIGHT)));
+    assert!(!level.eq_stable(&featured));
+
+    updated.name = "demon world v2".into();
+
+    assert!(!level.eq_stable(&updated));
+}
+
+#[test]
+fn test_star_reward_estimation() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let mut level = Level::<LevelData>::from_gj_str(raw).unwrap();
+
+    level.stars = 0;
+    level.featured = Featured::NotFeatured;
+    level.feature_tier = FeatureTier::None;
+    level.difficulty = LevelRating::Harder;
+    level.stars_requested = Some(7);
+
+    assert!(!level.is_rated());
+    assert_eq!(level.estimated_star_reward(), Some(7));
+
+    level.stars_requested = Some(2);
+    assert_eq!(level.estimated_star_reward(), Some(6));
+
+    level.difficulty = LevelRating::NotAvailable;
+    assert_eq!(level.estimated_star_reward(), Some(2));
+
+    level.stars_requested = None;
+    assert_eq!(level.estimated_star_reward(), None);
+
+    level.featured = Featured::Featured(1);
+    assert!(level.is_rated());
+
+    level.stars = 5;
+    assert_eq!(level.estimated_star_reward(), Some(5));
+
+    level.coin_amount = 2;
+    level.coins_verified = false;
+    assert!(level.has_unverified_coins());
+}
+
+#[test]
+fn test_serialize_processed_thunks() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let mut level = Level::<LevelData>::from_gj_str(raw).unwrap();
+
+    level.description.as_mut().unwrap().process().unwrap();
+    level.level_data.password.process().unwrap();
+    level.level_data.level_data.process().unwrap();
+
+    // Password and description are encoded canonically, so only the level string differs from the
+    // original response. Processed thunks are written straight into the output, which must match
+    // what going through the intermediate string representation produces.
+    let mut expected = Level::<LevelData>::from_gj_str(raw).unwrap();
+    expected.level_data.level_data = Thunk::Unprocessed(level.level_data.level_data.as_unprocessed().unwrap().into_owned().into());
+
+    assert_eq!(level.to_gj_string().unwrap(), expected.to_gj_string().unwrap());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_streaming_level_data_processing() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap();
+    // The decompressed level string is larger than the chunks the streaming parser reads, so some
+    // objects straddle chunk boundaries
+    let objects = Objects::from_reader(level.level_data.level_data.as_unprocessed().unwrap().as_bytes()).unwrap();
+
+    assert_eq!(objects, level.level_data.level_data.into_processed().unwrap());
+    assert!(matches!(
+        Objects::from_reader(&b"AAAA"[..]),
+        Err(LevelProcessError::UnknownCompression)
+    ));
+}
+
+#[cfg(feature = "arena")]
+#[test]
+fn test_arena_processing() {
+    use dash_rs::bumpalo::Bump;
+
+    let arena = Bump::new();
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let mut level = Level::<LevelData>::from_gj_str(raw).unwrap();
+    let expected = Level::<LevelData>::from_gj_str(raw).unwrap();
+
+    let description = level.description.as_mut().unwrap().process_in(&arena).unwrap();
+
+    assert!(matches!(description.0, Cow::Borrowed(_)));
+    assert_eq!(Some(&*description), expected.description.unwrap().process().ok().as_deref());
+    assert_eq!(
+        level.level_data.level_data.process_in(&arena).unwrap(),
+        &expected.level_data.level_data.into_processed().unwrap()
+    );
+    assert!(arena.allocated_bytes() > 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_level_data_size_diagnostics() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap();
+    let encoded = level.level_data.level_data.as_unprocessed().unwrap().into_owned();
+    let decoded = URL_SAFE.decode(encoded).unwrap();
+    let mut decompressed = Vec::new();
+
+    GzDecoder::new(&decoded[..]).read_to_end(&mut decompressed).unwrap();
+
+    assert_eq!(level.level_data.compression_scheme().unwrap(), CompressionScheme::Gzip);
+    assert_eq!(level.level_data.compressed_size().unwrap(), decoded.len());
+    assert_eq!(level.level_data.decompressed_size().unwrap(), decompressed.len());
+}
+
+#[test]
+fn test_verify_object_count() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let mut level = Level::<LevelData>::from_gj_str(raw).unwrap();
+    let objects = Objects::from_unprocessed(level.level_data.level_data.as_unprocessed().unwrap()).unwrap();
+
+    assert_eq!(level.verify_object_count(&objects), Ok(()));
+
+    level.object_amount = Some(7000);
+
+    let discrepancy = level.verify_object_count(&objects).unwrap_err();
+
+    assert_eq!(
+        discrepancy,
+        ObjectCountDiscrepancy {
+            reported: 7000,
+            actual: 7092
+        }
+    );
+    assert_eq!(discrepancy.difference(), 92);
+
+    level.object_amount = None;
+
+    assert_eq!(level.verify_object_count(&objects), Ok(()));
+}
+
+#[test]
+fn test_summary() {
+    let raw = include_str!("artifacts/level/11774780/raw").trim_end();
+    let level = Level::<LevelData>::from_gj_str(raw).unwrap().map_parts(
+        |_| None::<NewgroundsSong>,
+        |user_id| {
+            Some(Creator {
+                user_id,
+                name: Cow::Borrowed("Bianox"),
+                account_id: None,
+            })
+        },
+    );
+
+    assert_eq!(level.summary(), "Dark Realm (11774780) by Bianox | Hard Demon, 10 stars");
+}
+
+#[test]
+fn test_save_format_roundtrip() {
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let mut level = Level::<LevelData>::from_gj_str(raw).unwrap();
+    let save = level.to_save_string().unwrap();
+
+    assert!(save.starts_with("<d><k>kCEK</k><i>4</i><k>k1</k><i>897837</i><k>k2</k><s>time pressure</s>"));
+    assert!(save.contains("<k>k41</k><i>3101</i>"));
+
+    let indexed = save::save_to_indexed(&save).unwrap();
+    let restored = Level::<LevelData>::from_gj_str(&indexed).unwrap();
+
+    // Save files do not contain upload and update times
+    level.level_data.time_since_upload = Thunk::Unprocessed(save::UNKNOWN_AGE.into());
+    level.level_data.time_since_update = Thunk::Unprocessed(save::UNKNOWN_AGE.into());
+
+    assert_eq!(restored, level);
+}
+
+#[test]
+fn test_save_format_defaults() {
+    // The game omits fields holding default values
+    let save = "<dict><k>kCEK</k><i>4</i><k>k1</k><i>128</i><k>k2</k><s>1st &amp; 2nd</s><k>k9</k><i>10</i><k>k25</k><t /><k>k18</k><i>42</i></dict>";
+    let indexed = save::save_to_indexed(save).unwrap();
+    let level = Level::<()>::from_gj_str(&indexed).unwrap();
+
+    assert_eq!(level.level_id, 128);
+    assert_eq!(level.name, "1st & 2nd");
+    assert!(level.difficulty.is_demon());
+    assert_eq!(level.description, None);
+    assert_eq!(level.copy_of, None);
+
+    // Levels that cannot be copied have no k41 key
+    let save = "<k>kCEK</k><i>4</i><k>k1</k><i>128</i><k>k2</k><s>level</s><k>k4</k><s>H4sIAAAAAAAAC0s0NAIAn8aJVQMAAAA=</s>";
+    let indexed = save::save_to_indexed(save).unwrap();
+    let mut level = Level::<LevelData>::from_gj_str(&indexed).unwrap();
+
+    assert_eq!(level.level_data.password.process().unwrap(), &mut Password::NoCopy);
+
+    assert!(matches!(
+        save::save_to_indexed("<k>kCEK</k><i>2</i>"),
+        Err(save::SaveFormatError::NotALevel(_))
+    ));
+}
+
+fn assert_indices_ascending(serialized: &str) {
+    let indices: Vec<u32> = serialized.split(':').step_by(2).map(|index| index.parse().unwrap()).collect();
+
+    assert!(
+        indices.windows(2).all(|pair| pair[0] < pair[1]),
+        "indices not in ascending order: {:?}",
+        indices
+    );
+}
+
+#[test]
+fn test_indices_serialized_in_ascending_order() {
+    let artifacts = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts");
+
+    for entry in std::fs::read_dir(artifacts.join("level")).unwrap() {
+        let raw = std::fs::read_to_string(entry.unwrap().path().join("raw")).unwrap();
+        let level = Level::<LevelData>::from_gj_str(raw.trim_end()).unwrap();
+
+        assert_indices_ascending(&level.to_gj_string().unwrap());
+    }
+
+    for entry in std::fs::read_dir(artifacts.join("listed_level")).unwrap() {
+        let raw = std::fs::read_to_string(entry.unwrap().path().join("raw")).unwrap();
+        let level = Level::<()>::from_gj_str(raw.trim_end()).unwrap();
+
+        assert_indices_ascending(&level.to_gj_string().unwrap());
     }
 }
 
+#[cfg(feature = "std")]
 #[test]
-fn test_full_level() {
-    let units = load_test_units::<FullLevelTester>(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join("level"));
+fn test_from_gj_reader() {
+    let artifacts = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts");
+
+    for entry in std::fs::read_dir(artifacts.join("level")).unwrap() {
+        let raw = std::fs::read_to_string(entry.unwrap().path().join("raw")).unwrap();
+        let level = Level::<LevelData>::from_gj_str(raw.trim_end()).unwrap();
 
-    for (path, unit) in units {
-        println!("Testing case {:?}", path);
+        // A small buffer forces the level data to be read in many chunks
+        let reader = BufReader::with_capacity(64, raw.trim_end().as_bytes());
+        let read: Level<'static, LevelData<'static>> = Level::from_gj_reader(reader).unwrap();
 
-        unit.test_consistency();
-        // Cannot do round trip testing for onw, as the level data handling in dash-rs is incomplete
-        // (to put it nicely)
+        assert_eq!(read.to_gj_string().unwrap(), level.to_gj_string().unwrap());
     }
 }
@@ -1,7 +1,15 @@
 use dash_rs::{
-    model::{creator::Creator, song::NewgroundsSong},
-    GJFormat,
+    model::{
+        creator::Creator,
+        level::{Level, LevelData},
+        song::NewgroundsSong,
+        user::profile::Profile,
+        GameVersion, RelativeAge, TimeUnit,
+    },
+    schema::{self, FieldSchema},
+    util, Dash, GJBytes, GJFormat, ThunkProcessor, Utf8Policy,
 };
+use std::cmp::Ordering;
 
 mod framework;
 
@@ -13,8 +21,250 @@ const CREO_DUNE_DATA_TOO_MANY_FIELDS: &str = "1~|~771277~|~54~|~should be ignore
 const CREATOR_REGISTERED_DATA_TOO_MANY_FIELDS: &str = "4170784:Serponge:119741:34:fda:32:asd:3";
 
 #[test]
+#[cfg(not(feature = "strict-format"))]
 fn deserialize_too_many_fields() {
     // Superfluous fields should just be ignored
     NewgroundsSong::from_gj_str(CREO_DUNE_DATA_TOO_MANY_FIELDS).unwrap();
     Creator::from_gj_str(CREATOR_REGISTERED_DATA_TOO_MANY_FIELDS).unwrap();
 }
+
+#[test]
+#[cfg(feature = "strict-format")]
+fn deserialize_too_many_fields_strict() {
+    assert!(NewgroundsSong::from_gj_str(CREO_DUNE_DATA_TOO_MANY_FIELDS).is_err());
+    // Creators are not map-like, so there are no indices that could be unknown
+    Creator::from_gj_str(CREATOR_REGISTERED_DATA_TOO_MANY_FIELDS).unwrap();
+}
+
+#[test]
+#[cfg_attr(feature = "strict-format", ignore = "parses a song with superfluous fields")]
+fn raw_fields() {
+    assert_eq!(Creator::INDICES, &["1", "2", "3"]);
+
+    // The name contains the delimiter of creators, which splitting the serialized string would
+    // trip over
+    let creator = Creator::from_gj_str("4170784:Serponge:119741").unwrap();
+    let creator = Creator {
+        name: "Ser:ponge".into(),
+        ..creator
+    };
+
+    assert_eq!(
+        creator.raw_fields().unwrap().collect::<Vec<_>>(),
+        vec![
+            ("1", "4170784".to_string()),
+            ("2", "Ser:ponge".to_string()),
+            ("3", "119741".to_string())
+        ]
+    );
+
+    let song = NewgroundsSong::from_gj_str(CREO_DUNE_DATA_TOO_MANY_FIELDS).unwrap();
+    let fields = song.raw_fields().unwrap();
+
+    assert_eq!(fields.len(), NewgroundsSong::INDICES.len());
+    assert!(fields.map(|(index, _)| index).eq(NewgroundsSong::INDICES.iter().copied()));
+}
+
+#[test]
+fn describe_schema() {
+    let creator = schema::describe::<Creator>();
+
+    assert!(creator.model.starts_with("dash_rs::model::creator::Creator"));
+    assert_eq!(
+        creator.field("2"),
+        Some(&FieldSchema {
+            index: "2",
+            field: "name",
+            rust_type: "Cow<'a, str>",
+            optional: false,
+            processor: None,
+        })
+    );
+    assert!(creator.field("3").unwrap().optional);
+    assert_eq!(creator.field("4"), None);
+
+    let profile = schema::describe::<Profile>().field("55").copied().unwrap();
+
+    assert_eq!(profile.rust_type, "Option<Thunk<'a, DemonBreakdown>>");
+    assert_eq!(profile.processor, Some("DemonBreakdown"));
+
+    type Listed<'a> = Level<'a, (), Option<u64>, u64>;
+
+    let listed = schema::describe::<Listed>();
+
+    assert_eq!(listed.field("43").unwrap().field, "difficulty");
+    assert_eq!(listed.field("4"), None);
+    assert_eq!(schema::describe::<Level>().field("4").unwrap().processor, Some("Objects"));
+
+    // The level schemas are maintained by hand, so check them against what actually gets written
+    fn serialized_indices<'de, T: Dash<'de>>(model: &T) -> Vec<&'static str> {
+        model.raw_fields().unwrap().map(|(index, _)| index).collect()
+    }
+
+    let raw = include_str!("artifacts/level/897837/raw").trim_end();
+    let data = format!("{}:41:3:52:10001234:53:42", raw);
+
+    assert_eq!(serialized_indices(&Listed::from_gj_str(raw).unwrap()), Listed::INDICES);
+    assert_eq!(
+        serialized_indices(&Level::<LevelData>::from_gj_str(&data).unwrap()),
+        <Level as Dash>::INDICES
+    );
+
+    let json = serde_json::to_value(schema::describe::<NewgroundsSong>()).unwrap();
+
+    assert_eq!(json["fields"][0]["index"], "1");
+    assert_eq!(json["fields"][0]["field"], "song_id");
+}
+
+#[test]
+fn process_relative_age() {
+    let cases = [
+        ("8 years", 8, TimeUnit::Year, "8 years"),
+        ("1 month", 1, TimeUnit::Month, "1 month"),
+        ("5 days ago", 5, TimeUnit::Day, "5 days"),
+        ("1 Minutes", 1, TimeUnit::Minute, "1 minute"),
+        ("0 seconds", 0, TimeUnit::Second, "0 seconds"),
+    ];
+
+    for (raw, amount, unit, canonical) in cases {
+        let age = RelativeAge::from_unprocessed(raw.into()).unwrap();
+
+        assert_eq!(age, RelativeAge { amount, unit });
+        assert_eq!(RelativeAge::as_unprocessed(&age).unwrap(), canonical);
+    }
+
+    for invalid in ["", "years", "8", "8 fortnights", "8 years from now"] {
+        assert!(RelativeAge::from_unprocessed(invalid.into()).is_err());
+    }
+}
+
+#[test]
+fn game_version_from_str() {
+    for version in [10u8, 7, 18, 19, 20, 21, 22] {
+        let version = GameVersion::from(version);
+
+        assert_eq!(version.to_string().parse::<GameVersion>().unwrap(), version);
+    }
+
+    assert_eq!("Pre 1.6".parse::<GameVersion>().unwrap(), GameVersion::Unknown);
+    assert_eq!("2.2".parse::<GameVersion>().unwrap(), GameVersion::CURRENT);
+
+    for invalid in ["", "2", "2.x", "1.10", "26.0"] {
+        assert!(invalid.parse::<GameVersion>().is_err());
+    }
+}
+
+#[test]
+fn game_version_ordering() {
+    let versions = ["Pre 1.6", "1.6", "1.7", "1.9", "2.0", "2.1", "2.2"].map(|version| version.parse::<GameVersion>().unwrap());
+
+    assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(GameVersion::from(21) < GameVersion::from(30));
+
+    // Ordering agrees with equality for both spellings of 1.6, and 1.0 is indistinguishable from
+    // "Pre 1.6" in the numeric representation
+    let one_six = GameVersion::Version { major: 1, minor: 6 };
+    let one_zero = GameVersion::Version { major: 1, minor: 0 };
+
+    assert_eq!(one_six, "1.6".parse().unwrap());
+    assert_eq!(one_six.cmp(&GameVersion::from(7)), Ordering::Equal);
+    assert_eq!(one_zero, GameVersion::Unknown);
+    assert_eq!("1.0".parse::<GameVersion>().unwrap(), GameVersion::Unknown);
+    assert_eq!(GameVersion::from(u8::from(one_zero)), one_zero);
+    assert!(GameVersion::Unknown < GameVersion::from(0));
+    assert_ne!(GameVersion::Unknown, GameVersion::from(0));
+}
+
+#[test]
+fn xor_base64_helpers() {
+    // A level password of 123456, as sent by the servers
+    let decoded = util::xor_base64_decode("AwcBBQAHAA==", util::xor_keys::LEVEL_PASSWORD).unwrap();
+
+    assert_eq!(decoded, b"1123456");
+    assert_eq!(util::xor_base64_encode(&decoded, util::xor_keys::LEVEL_PASSWORD), "AwcBBQAHAA==");
+
+    let message = util::xor_base64_encode(b"Hello World!", util::xor_keys::MESSAGE);
+
+    assert_eq!(util::xor_base64_decode(&message, util::xor_keys::MESSAGE).unwrap(), b"Hello World!");
+    assert_eq!(util::base64_decode(&util::base64_encode(&[0xfb, 0xff])).unwrap(), [0xfb, 0xff]);
+    assert!(util::base64_decode("not base64!").is_err());
+
+    let mut save = *b"<?xml";
+    util::cyclic_xor(&mut save, util::xor_keys::SAVE_FILE);
+    util::cyclic_xor(&mut save, util::xor_keys::SAVE_FILE);
+
+    assert_eq!(&save, b"<?xml");
+}
+
+#[test]
+fn deserialize_invalid_utf8() {
+    let level = b"1:11774780:2:Dark R\xe9alm:5:2";
+    let creator = b"4170784:Serp\xf0nge:119741";
+
+    let error = Creator::from_gj_bytes(creator).unwrap_err();
+    assert_eq!(error.position(), Some(8));
+
+    let error = GJBytes::decode::<Level<()>>(level, Utf8Policy::LossyFields(&["3"])).unwrap_err();
+    assert_eq!(error.index(), Some("2"));
+    assert_eq!(error.position(), Some(13));
+
+    let decoded = GJBytes::decode::<Level<()>>(level, Utf8Policy::LossyFields(&["2"])).unwrap();
+    assert!(decoded.is_lossy());
+    assert_eq!(decoded.as_str(), "1:11774780:2:Dark R\u{FFFD}alm:5:2");
+
+    let decoded = GJBytes::decode::<Creator>(creator, Utf8Policy::Lossy).unwrap();
+    assert_eq!(decoded.parse::<Creator>().unwrap().name, "Serp\u{FFFD}nge");
+
+    let decoded = GJBytes::decode::<Creator>(b"4170784:Serponge:119741", Utf8Policy::Strict).unwrap();
+    assert!(!decoded.is_lossy());
+    assert_eq!(Creator::from_gj_bytes(b"4170784:Serponge:119741").unwrap().name, "Serponge");
+}
+
+#[test]
+fn level_seed2() {
+    let level_string = "abcdefghijklmnopqrstuvwxyz".repeat(6);
+
+    assert_eq!(util::chk::level_seed("short"), "short");
+    assert_eq!(
+        util::chk::level_seed(&level_string),
+        "adgjmpsvybehknqtwzcfiloruxadgjmpsvybehknqtwzcfilor"
+    );
+    assert_eq!(
+        util::chk::level_seed2(&level_string),
+        "UQFXBAACBQFVBlJTV1ZQUAYAD1YDVwYBUg0BA1JXBAIBD1EGA1cFVw=="
+    );
+}
+
+#[test]
+fn indexed_plain_serde_types() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Chest<'a> {
+        #[serde(rename = "1")]
+        id: u32,
+
+        #[serde(rename = "2")]
+        name: &'a str,
+
+        #[serde(rename = "5")]
+        orbs: u16,
+    }
+
+    let chest: Chest = dash_rs::from_indexed_str("1:3:5:200:2:Small", ":", true).unwrap();
+
+    assert_eq!(
+        chest,
+        Chest {
+            id: 3,
+            name: "Small",
+            orbs: 200
+        }
+    );
+    assert_eq!(dash_rs::to_indexed_string(&chest, ":", true).unwrap(), "1:3:2:Small:5:200");
+    assert_eq!(dash_rs::to_indexed_string(&chest, "~", false).unwrap(), "3~Small~200");
+
+    let list: Vec<u32> = dash_rs::from_indexed_str("1,2,3", ",", false).unwrap();
+
+    assert_eq!(list, [1, 2, 3]);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "pointercrate")]
+
+use std::borrow::Cow;
+
+use dash_rs::{
+    interop::pointercrate::{DemonSubmission, Player},
+    model::{
+        creator::Creator,
+        level::{Level, LevelData},
+    },
+    GJFormat,
+};
+use serde_json::json;
+
+const LEVEL: &str = include_str!("artifacts/level/11774780/raw");
+
+#[test]
+fn test_level_to_demon_submission() {
+    let level = Level::<LevelData>::from_gj_str(LEVEL.trim_end()).unwrap().map_parts(
+        |song| song,
+        |user_id| {
+            Some(Creator {
+                user_id,
+                name: Cow::Borrowed("Bianox"),
+                account_id: None,
+            })
+        },
+    );
+    let submission = DemonSubmission::from(&level);
+
+    assert_eq!(
+        serde_json::to_value(&submission).unwrap(),
+        json!({
+            "name": "Dark Realm",
+            "position": null,
+            "requirement": null,
+            "verifier": null,
+            "publisher": "Bianox",
+            "creators": ["Bianox"],
+            "video": null,
+            "level_id": 11774780,
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Player::from(level.creator.as_ref().unwrap())).unwrap(),
+        json!({"name": "Bianox", "banned": false})
+    );
+}
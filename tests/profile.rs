@@ -1,6 +1,12 @@
-use dash_rs::model::user::profile::Profile;
-use framework::load_test_units;
-use std::path::Path;
+use dash_rs::{
+    model::user::{
+        icons::{IconLayer, LayerRole},
+        profile::{ClassicCompletions, DemonBreakdown, IconChange, IconSlot, ModeStats, Profile},
+        FriendState,
+    },
+    GJFormat,
+};
+use dash_rs_test_macros::golden_roundtrip;
 
 mod framework;
 
@@ -8,22 +14,215 @@ enum ProfileTester {}
 
 impl framework::Testable for ProfileTester {
     type Target<'a> = Profile<'a>;
+
+    fn canonicalize(target: &mut Self::Target<'_>) {
+        if let Some(ref mut breakdown) = target.demon_breakdown {
+            breakdown.process().unwrap();
+        }
+        if let Some(ref mut completions) = target.classic_completions {
+            completions.process().unwrap();
+        }
+        if let Some(ref mut completions) = target.platformer_completions {
+            completions.process().unwrap();
+        }
+    }
 }
 
+golden_roundtrip!(ProfileTester, "tests/artifacts/profile/*");
+
 #[test]
-fn test_profile() {
-    let units = load_test_units::<ProfileTester>(
-        Path::new(env!("CARGO_MANIFEST_DIR"))
-            .join("tests")
-            .join("artifacts")
-            .join("profile"),
+fn test_profile_diff() {
+    let raw = include_str!("artifacts/profile/8451/raw").trim_end();
+    let old = Profile::from_gj_str(raw).unwrap();
+    let mut new = old.clone();
+
+    assert!(old.diff(&new).is_empty());
+
+    new.stars += 100;
+    new.demons -= 1;
+    new.ship_index += 1;
+    new.has_glow = !old.has_glow;
+
+    let delta = old.diff(&new);
+
+    assert_eq!(delta.stars, 100);
+    assert_eq!(delta.demons, -1);
+    assert_eq!(delta.moons, 0);
+    assert_eq!(delta.global_rank, None);
+    assert_eq!(
+        delta.icon_changes,
+        vec![
+            IconChange::Icon {
+                slot: IconSlot::Ship,
+                old: old.ship_index.into(),
+                new: new.ship_index.into()
+            },
+            IconChange::Glow(new.has_glow),
+        ]
     );
+}
 
-    for (path, unit) in units {
-        println!("Testing case {:?}", path);
+#[test]
+fn test_own_profile_fields() {
+    let raw = include_str!("artifacts/profile/8451/raw").trim_end();
+    let data = format!("{}:38:2:39:1:40:0", raw.replace(":31:0:", ":31:3:"));
+    let profile = Profile::from_gj_str(&data).unwrap();
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
-    }
+    assert_eq!(profile.friend_state, FriendState::IncomingRequest);
+    assert_eq!(profile.new_messages, Some(2));
+    assert_eq!(profile.new_friend_requests, Some(1));
+    assert_eq!(profile.new_friends, Some(0));
+
+    let saved = profile.to_gj_string().unwrap();
+
+    assert_eq!(Profile::from_gj_str(&saved).unwrap(), profile);
+}
+
+#[test]
+fn test_demon_breakdown() {
+    let raw = include_str!("artifacts/profile/8451/raw").trim_end();
+    let data = format!("{}:55:120,98,151,76,30,2,1,0,0,0,5,3", raw);
+    let mut profile = Profile::from_gj_str(&data).unwrap();
+
+    let breakdown = *profile.demon_breakdown.as_mut().unwrap().process().unwrap();
+
+    assert_eq!(
+        breakdown,
+        DemonBreakdown {
+            easy: 120,
+            medium: 98,
+            hard: 151,
+            insane: 76,
+            extreme: 30,
+            weekly: 5,
+            gauntlet: 3,
+            platformer_tiers: [2, 1, 0, 0, 0],
+        }
+    );
+    assert_eq!(breakdown.classic_total(), 475);
+    assert_eq!(breakdown.platformer_total(), 3);
+    assert!(profile.to_gj_string().unwrap().ends_with(":55:120,98,151,76,30,2,1,0,0,0,5,3"));
+
+    let malformed = format!("{}:55:1,2,3", raw);
+    let mut profile = Profile::from_gj_str(&malformed).unwrap();
+
+    assert!(profile.demon_breakdown.as_mut().unwrap().process().is_err());
+}
+
+#[test]
+fn test_classic_platformer_split() {
+    let raw = include_str!("artifacts/profile/8451/raw").trim_end();
+    let profile = Profile::from_gj_str(raw).unwrap();
+
+    assert_eq!(
+        profile.platformer_stats().unwrap(),
+        ModeStats {
+            rewards: 11,
+            demons: None,
+            levels: None
+        }
+    );
+
+    let data = format!(
+        "{}:55:120,98,151,76,30,2,1,0,0,0,5,3:56:20,30,40,50,60,70,80,90:57:1,2,3,4,5,6",
+        raw
+    );
+    let mut profile = Profile::from_gj_str(&data).unwrap();
+
+    assert_eq!(
+        profile.classic_stats().unwrap(),
+        ModeStats {
+            rewards: 13728,
+            demons: Some(475),
+            levels: Some(270)
+        }
+    );
+    assert_eq!(
+        profile.platformer_stats().unwrap(),
+        ModeStats {
+            rewards: 11,
+            demons: Some(3),
+            levels: Some(21)
+        }
+    );
+    assert_eq!(
+        *profile.classic_completions.as_mut().unwrap().process().unwrap(),
+        ClassicCompletions {
+            auto: 20,
+            easy: 30,
+            normal: 40,
+            hard: 50,
+            harder: 60,
+            insane: 70,
+            daily: 80,
+            gauntlet: 90,
+        }
+    );
+    assert!(profile
+        .to_gj_string()
+        .unwrap()
+        .ends_with(":56:20,30,40,50,60,70,80,90:57:1,2,3,4,5,6"));
+}
+
+#[test]
+fn test_icon_descriptors() {
+    let raw = include_str!("artifacts/profile/8451/raw").trim_end();
+    let mut profile = Profile::from_gj_str(raw).unwrap();
+
+    profile.cube_index = 7;
+    profile.has_glow = false;
+
+    let cube = profile.icon(IconSlot::Cube).unwrap();
+
+    assert_eq!(cube.sheet, "player_07");
+    assert_eq!(
+        cube.layers,
+        vec![
+            IconLayer {
+                frame: "player_07_2_001.png".to_string(),
+                role: LayerRole::Secondary,
+                color: Some(profile.secondary_color),
+                part: None
+            },
+            IconLayer {
+                frame: "player_07_001.png".to_string(),
+                role: LayerRole::Primary,
+                color: Some(profile.primary_color),
+                part: None
+            },
+            IconLayer {
+                frame: "player_07_extra_001.png".to_string(),
+                role: LayerRole::Extra,
+                color: None,
+                part: None
+            },
+        ]
+    );
+
+    profile.has_glow = true;
+    profile.robot_index = 12;
+
+    let robot = profile.icon(IconSlot::Robot).unwrap();
+
+    assert_eq!(robot.sheet, "robot_12");
+    assert_eq!(robot.layers.len(), 16);
+    assert_eq!(robot.layers[0].frame, "robot_12_01_glow_001.png");
+    assert_eq!(robot.layers[15].part, Some(4));
+
+    let ball = profile.icon(IconSlot::Ball).unwrap();
+
+    assert_eq!(ball.layers.len(), 3);
+    assert!(ball.layers.iter().all(|layer| layer.role != LayerRole::Extra));
+
+    assert!(profile.icon(IconSlot::DeathEffect).is_none());
+}
+
+#[test]
+fn test_summary() {
+    let profile = Profile::from_gj_str(include_str!("artifacts/profile/8451/raw").trim_end()).unwrap();
+
+    assert_eq!(
+        profile.summary(),
+        "stardust1971 (#8073) | 13728 stars, 11 moons, 483 demons, 19 creator points"
+    );
 }
@@ -23,3 +23,231 @@ fn process_get_gj_levels_response() {
         }
     }
 }
+
+#[test]
+fn process_get_gj_levels_response_lenient() {
+    // Corrupt the level ID of "Noice" and the user ID of the creator "Cdpre"
+    let response = GET_GJ_LEVELS_RESPONSE
+        .replacen("1:62953227:", "1:notanumber:", 1)
+        .replacen("4123296:Cdpre", "x:Cdpre", 1);
+
+    assert!(dash_rs::response::parse_get_gj_levels_response(&response).is_err());
+
+    let result = dash_rs::response::parse_get_gj_levels_response_lenient(&response).unwrap();
+
+    assert_eq!(result.parsed.len(), 9);
+    assert_eq!(result.failed.len(), 2);
+    assert!(result.failed[0].0.starts_with("1:notanumber:"));
+    assert_eq!(result.failed[1].0, "x:Cdpre:1478680");
+
+    for level in result.parsed {
+        // "Sound Visualization" by Cdpre, whose creator entry is now unparsable, and "AnnoZone", whose
+        // creator was never part of the response
+        if level.level_id == 63336521 || level.level_id == 63292359 {
+            assert!(level.creator.is_none())
+        } else {
+            assert!(level.creator.is_some())
+        }
+    }
+}
+
+#[test]
+fn process_download_gj_special_level_response() {
+    let level = include_str!("artifacts/level/897837/raw");
+    let response = format!("{}:41:100123#hash#hash#4170784:Serponge:119741", level.trim_end());
+
+    let (level, creator) = dash_rs::response::parse_download_gj_special_level_response(&response).unwrap();
+
+    assert_eq!(level.level_data.daily_index, Some(100123));
+    assert_eq!(creator.unwrap().name, "Serponge");
+
+    let level = dash_rs::response::parse_download_gj_level_response(&response).unwrap();
+
+    assert_eq!(level.level_data.daily_index, Some(100123));
+}
+
+#[test]
+fn process_upload_gj_comment_response() {
+    assert_eq!(dash_rs::response::parse_upload_gj_comment_response("12345678").unwrap(), 12345678);
+    assert!(matches!(
+        dash_rs::response::parse_upload_gj_comment_response("-1"),
+        Err(dash_rs::response::ResponseError::NotFound)
+    ));
+
+    match dash_rs::response::parse_upload_gj_comment_response("temp_3600_Spamming_the comments") {
+        Err(dash_rs::response::ResponseError::CommentBan(ban)) => {
+            assert_eq!(ban.duration, Some(std::time::Duration::from_secs(3600)));
+            assert_eq!(ban.reason.as_deref(), Some("Spamming_the comments"));
+        },
+        other => panic!("expected comment ban, got {:?}", other),
+    }
+
+    match dash_rs::response::parse_upload_gj_comment_response("-10") {
+        Err(dash_rs::response::ResponseError::CommentBan(ban)) => assert!(ban.is_permanent()),
+        other => panic!("expected comment ban, got {:?}", other),
+    }
+}
+
+#[test]
+fn unresolve_listed_levels() {
+    use dash_rs::GJFormat;
+
+    let levels = dash_rs::response::parse_get_gj_levels_response(GET_GJ_LEVELS_RESPONSE).unwrap();
+
+    for level in levels {
+        let creator = level.creator.as_ref().map(|creator| creator.user_id).unwrap_or(0);
+        let song = level.custom_song.as_ref().map(|song| song.song_id);
+        let serialized = level.into_unresolved().to_gj_string().unwrap();
+        let unresolved = dash_rs::model::level::Level::<()>::from_gj_str(&serialized).unwrap();
+
+        assert_eq!(unresolved.creator, creator);
+        assert_eq!(unresolved.custom_song, song);
+    }
+}
+
+#[test]
+fn verify_get_gj_levels_response_hash() {
+    assert!(dash_rs::response::verify_get_gj_levels_response(GET_GJ_LEVELS_RESPONSE).unwrap());
+
+    let tampered = GET_GJ_LEVELS_RESPONSE.replacen(":18:5:", ":18:10:", 1);
+
+    assert!(!dash_rs::response::verify_get_gj_levels_response(&tampered).unwrap());
+}
+
+#[test]
+fn accumulate_get_gj_levels_responses() {
+    let mut accumulator = dash_rs::response::LevelsAccumulator::new();
+
+    assert!(accumulator.has_more());
+    assert_eq!(accumulator.ingest(GET_GJ_LEVELS_RESPONSE).unwrap(), 10);
+    assert_eq!(accumulator.ingest(GET_GJ_LEVELS_RESPONSE).unwrap(), 0);
+    assert_eq!(accumulator.len(), 10);
+    assert!(accumulator.has_more());
+
+    let page_info = accumulator.page_info().unwrap();
+
+    assert_eq!((page_info.total, page_info.offset, page_info.page_size), (11389, 0, 10));
+
+    assert_eq!(accumulator.ingest("-1").unwrap(), 0);
+    assert!(!accumulator.has_more());
+
+    let levels = accumulator.into_levels();
+
+    assert_eq!(levels.len(), 10);
+    assert!(levels.iter().filter(|level| level.creator.is_some()).count() == 9);
+}
+
+#[test]
+fn process_sync_gj_account_response() {
+    let synced =
+        dash_rs::response::parse_sync_gj_account_response("H4sIAAAAAAACA0sEAEO-t-gBAAAA;H4sIAAAAAAACA0sCAPnvvnEBAAAA;22;38;a;a").unwrap();
+
+    assert_eq!(synced.game_version, dash_rs::model::GameVersion::Version { major: 2, minor: 2 });
+    assert_eq!(synced.binary_version, dash_rs::model::GameVersion::Version { major: 3, minor: 8 });
+    assert_eq!(synced.save_data.decode_game_manager().unwrap(), "a");
+    assert_eq!(synced.save_data.decode_local_levels().unwrap(), "b");
+
+    assert!(matches!(
+        dash_rs::response::parse_sync_gj_account_response("-1"),
+        Err(dash_rs::response::ResponseError::NotFound)
+    ));
+}
+
+#[test]
+fn parse_get_gj_levels_response_with_raw() {
+    use dash_rs::request::{level::LevelsRequest, GdRequest};
+
+    let parsed = LevelsRequest::parse_response_with_raw(GET_GJ_LEVELS_RESPONSE).unwrap();
+
+    assert_eq!(parsed.value.len(), 10);
+    assert_eq!(parsed.raw_sections.len(), 5);
+    assert_eq!(parsed.section(3), Some("11389:0:10"));
+    assert_eq!(parsed.section(4), Some("f687963dcfd37f857633563ee28b0cfadc727c97"));
+    assert_eq!(parsed.section(5), None);
+}
+
+#[test]
+fn process_get_gj_levels_response_with_context() {
+    use dash_rs::{
+        model::creator::Creator,
+        response::{parse_get_gj_levels_response_with, ResolveContext},
+    };
+    use std::borrow::Cow;
+
+    // Stands in for a database of previously seen creators
+    struct KnownCreators(Vec<u64>);
+
+    impl<'a> ResolveContext<'a> for KnownCreators {
+        fn creator(&mut self, user_id: u64) -> Option<Creator<'a>> {
+            self.0.push(user_id);
+
+            Some(Creator {
+                user_id,
+                name: Cow::Borrowed("Unknown"),
+                account_id: None,
+            })
+        }
+    }
+
+    let mut context = KnownCreators(Vec::new());
+    let levels = parse_get_gj_levels_response_with(GET_GJ_LEVELS_RESPONSE, &mut context).unwrap();
+
+    // Only the creator missing from the response was looked up
+    assert_eq!(context.0, [5897998]);
+
+    let anno_zone = levels.iter().find(|level| level.level_id == 63292359).unwrap();
+
+    assert_eq!(
+        anno_zone.creator.as_ref().map(|creator| creator.name.clone()),
+        Some(Cow::Borrowed("Unknown"))
+    );
+    assert!(levels.iter().all(|level| level.creator.is_some()));
+}
+
+#[test]
+fn process_get_gj_comments_response() {
+    let comment = include_str!("artifacts/level_comment/258976/raw").trim_end();
+    let user = include_str!("artifacts/comment_user/1705254/raw").trim_end();
+    let response = format!("{}:{}|{}:1~~9~~10~~11~~14~~15~~16~#1234:20:10", comment, user, comment);
+
+    let (comments, page_info) = dash_rs::response::parse_get_gj_comments_response(&response).unwrap();
+
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].user.as_ref().unwrap().name, "Pauze");
+    assert!(comments[1].user.is_none());
+    assert_eq!(
+        page_info,
+        dash_rs::response::PageInfo {
+            total: 1234,
+            offset: 20,
+            page_size: 10
+        }
+    );
+    assert!(page_info.has_more());
+}
+
+#[test]
+fn split_get_gj_levels_response_sections() {
+    use dash_rs::response::{sections::ResponseSections, PageInfo, ResponseError};
+
+    let sections = ResponseSections::new(GET_GJ_LEVELS_RESPONSE);
+
+    assert_eq!(sections.levels().unwrap().count(), 10);
+    assert_eq!(sections.creators().unwrap().count(), 9);
+    assert_eq!(sections.songs().unwrap().count(), 9);
+    assert_eq!(
+        sections.page_info().unwrap(),
+        PageInfo {
+            total: 11389,
+            offset: 0,
+            page_size: 10
+        }
+    );
+    assert_eq!(sections.hash().unwrap(), "f687963dcfd37f857633563ee28b0cfadc727c97");
+
+    let truncated = ResponseSections::new("1:1#2:2:3");
+
+    assert_eq!(truncated.creators().unwrap().collect::<Vec<_>>(), ["2:2:3"]);
+    assert!(matches!(truncated.songs(), Err(ResponseError::UnexpectedFormat)));
+    assert_eq!(ResponseSections::new("1:1##").creators().unwrap().count(), 0);
+}
@@ -1,5 +1,12 @@
-use dash_rs::model::song::NewgroundsSong;
-use framework::load_test_units;
+use dash_rs::{
+    model::{
+        song::{main_songs, MainSong, NewgroundsSong, SongLinkError, MAIN_SONGS},
+        GameVersion,
+    },
+    GJFormat, LegacySet, PercentDecoder, PercentEncodeSet, Rfc3986Set, RobtopSet, Thunk, ThunkProcessor,
+};
+use dash_rs_test_macros::golden_roundtrip;
+use std::borrow::Cow;
 use std::path::Path;
 
 mod framework;
@@ -14,15 +21,130 @@ impl framework::Testable for NewgroundsSongTester {
     }
 }
 
+golden_roundtrip!(NewgroundsSongTester, "tests/artifacts/song/*");
+
+fn reencode<S: PercentEncodeSet>(link: &str) -> String {
+    let decoded = PercentDecoder::<S>::from_unprocessed(Cow::Borrowed(link)).unwrap();
+
+    encode::<S>(&decoded)
+}
+
+fn encode<S: PercentEncodeSet>(link: &str) -> String {
+    PercentDecoder::<S>::as_unprocessed(&Cow::Borrowed(link)).unwrap().into_owned()
+}
+
 #[test]
-fn test_newgrounds_song() {
-    let units = load_test_units::<NewgroundsSongTester>(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join("song"));
+fn test_robtop_set_against_corpus() {
+    let artifacts = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join("song");
 
-    for (path, unit) in units {
-        println!("Testing case {:?}", path);
+    for entry in std::fs::read_dir(artifacts).unwrap() {
+        let raw = std::fs::read_to_string(entry.unwrap().path().join("raw")).unwrap();
+        let song = NewgroundsSong::from_gj_str(raw.trim_end()).unwrap();
+        let link = song.link.as_unprocessed().unwrap();
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        assert_eq!(reencode::<RobtopSet>(&link), link);
     }
 }
+
+#[test]
+fn test_percent_encode_sets_differ() {
+    // Spaces are only left alone by the legacy set, tildes are only encoded by RobTop's set, and
+    // only RFC 3986 encodes '=' and '&'
+    let link = "https://audio.ngfiles.com/a b~c.mp3?f=1&g=2";
+
+    let robtop = encode::<RobtopSet>(link);
+    let rfc3986 = encode::<Rfc3986Set>(link);
+    let legacy = encode::<LegacySet>(link);
+
+    assert_eq!(robtop, "https%3A%2F%2Faudio.ngfiles.com%2Fa%20b%7Ec.mp3%3Ff=1&g=2");
+    assert_eq!(rfc3986, "https%3A%2F%2Faudio.ngfiles.com%2Fa%20b~c.mp3%3Ff%3D1%26g%3D2");
+    assert_eq!(legacy, "https%3A%2F%2Faudio.ngfiles.com%2Fa b~c.mp3%3Ff=1&g=2");
+
+    assert_ne!(robtop, rfc3986);
+    assert_ne!(robtop, legacy);
+    assert_ne!(rfc3986, legacy);
+
+    // Decoding does not depend on the set, so all encodings round-trip to the same link
+    for encoded in [&robtop, &rfc3986, &legacy] {
+        assert_eq!(PercentDecoder::<RobtopSet>::from_unprocessed(Cow::Borrowed(encoded)).unwrap(), link);
+    }
+}
+
+#[test]
+fn test_summary() {
+    let song = NewgroundsSong::from_gj_str(include_str!("artifacts/song/771517/raw").trim_end()).unwrap();
+
+    assert_eq!(song.summary(), "Rebirth by AeronMusic (771517, 8.34 MB)");
+}
+
+#[test]
+fn test_legacy_index_json() {
+    let processed = include_str!("artifacts/song/771517/processed");
+    let expected: NewgroundsSong = serde_json::from_str(processed).unwrap();
+    let legacy = processed
+        .replace(r#""artist_id""#, r#""index_3""#)
+        .replace(r#""youtube_video_id""#, r#""index_6""#)
+        .replace(r#""youtube_channel_id""#, r#""index_7""#)
+        .replace(r#""is_verified": true"#, r#""index_8": "1""#);
+    let song: NewgroundsSong = serde_json::from_str(&legacy).unwrap();
+
+    assert_eq!(song, expected);
+    assert_eq!(song.artist_id, 948);
+    assert!(song.is_verified);
+}
+
+#[test]
+fn test_empty_verified_flag() {
+    let raw = include_str!("artifacts/song/771517/raw").trim_end();
+    let unverified = format!("{}8~|~", raw.strip_suffix("8~|~1").unwrap());
+    let song = NewgroundsSong::from_gj_str(&unverified).unwrap();
+
+    assert!(!song.is_verified);
+
+    // The empty string is not preserved, unverified artists are always written as "0"
+    let saved = song.to_gj_string().unwrap();
+
+    assert!(saved.contains("~|~8~|~0~|~"), "{}", saved);
+    assert_eq!(NewgroundsSong::from_gj_str(&saved).unwrap(), song);
+}
+
+#[test]
+fn test_direct_download_url() {
+    let mut song = NewgroundsSong::from_gj_str(include_str!("artifacts/song/771517/raw").trim_end()).unwrap();
+
+    assert_eq!(
+        song.direct_download_url().unwrap(),
+        "https://audio.ngfiles.com/771000/771517_Rebirth.mp3?f1508862699"
+    );
+
+    song.link = Thunk::Unprocessed(Cow::Borrowed("-2"));
+    assert!(matches!(song.direct_download_url(), Err(SongLinkError::Removed)));
+
+    song.link = Thunk::Unprocessed(Cow::Borrowed("https%3A%2F%2Fexample.com%2F771517.mp3"));
+    assert!(matches!(song.direct_download_url(), Err(SongLinkError::UnknownHost(host)) if host == "example.com"));
+}
+
+#[test]
+fn test_main_song_lookup() {
+    assert_eq!(MainSong::by_name("cant let go"), Some(MAIN_SONGS[5]));
+    assert_eq!(MainSong::by_name("Base After Base!"), Some(MAIN_SONGS[4]));
+    assert_eq!(MainSong::by_name("Theory"), None);
+    assert_eq!(MainSong::fuzzy_find("Theory of Everythng 2"), Some(MAIN_SONGS[17]));
+    assert_eq!(MainSong::fuzzy_find("clubstep"), Some(MAIN_SONGS[13]));
+    assert_eq!(MainSong::fuzzy_find(""), None);
+    assert_eq!(MainSong::iter().count(), 22);
+}
+
+#[test]
+fn test_main_songs_by_version() {
+    let version = |version: &str| version.parse::<GameVersion>().unwrap();
+
+    assert_eq!(main_songs(GameVersion::Unknown).len(), 7);
+    assert_eq!(main_songs(version("1.0")).len(), 7);
+    assert_eq!(main_songs(version("1.6")).len(), 13);
+    assert_eq!(main_songs(version("2.1")).len(), 21);
+    assert_eq!(main_songs(version("2.2")), &MAIN_SONGS[..]);
+    assert_eq!(MAIN_SONGS[0].introduced_in(), Some(version("Pre 1.6")));
+    assert_eq!(MAIN_SONGS[12].introduced_in(), Some(version("1.6")));
+    assert_eq!(MAIN_SONGS[21].introduced_in(), Some(version("2.2")));
+}
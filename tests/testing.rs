@@ -0,0 +1,35 @@
+#![cfg(feature = "testing")]
+
+use dash_rs::{
+    model::{creator::Creator, song::NewgroundsSong, user::profile::Profile},
+    testing::{roundtrip_check, FieldDifference, RoundtripError},
+};
+
+#[test]
+fn test_artifact_roundtrips() {
+    roundtrip_check::<Profile>(include_str!("artifacts/profile/8451/raw")).unwrap();
+    roundtrip_check::<Creator>("4170784:Serponge:119741").unwrap();
+}
+
+#[test]
+fn test_roundtrip_mismatch() {
+    // Superfluous fields are dropped when saving
+    let result = roundtrip_check::<Creator>("4170784:Serponge:119741:34");
+
+    match result {
+        Err(RoundtripError::Mismatch(differences)) => assert_eq!(
+            differences,
+            vec![FieldDifference {
+                index: "3".to_string(),
+                original: Some("34".to_string()),
+                saved: None
+            }]
+        ),
+        _ => panic!("expected mismatch, got {:?}", result),
+    }
+
+    assert!(matches!(
+        roundtrip_check::<NewgroundsSong>("1~|~notanumber"),
+        Err(RoundtripError::Load(_))
+    ));
+}
@@ -0,0 +1,18 @@
+#![cfg(feature = "wasm")]
+
+use dash_rs::{model::user::profile::Profile, wasm, GJFormat};
+
+const PROFILE: &str = include_str!("artifacts/profile/8451/raw");
+
+#[test]
+fn test_profile_json_roundtrip() {
+    let json = wasm::gj_to_json::<Profile>(PROFILE).unwrap();
+    let gj = wasm::json_to_gj::<Profile>(&json).unwrap();
+
+    assert_eq!(Profile::from_gj_str(PROFILE).unwrap(), Profile::from_gj_str(&gj).unwrap());
+}
+
+#[test]
+fn test_not_found_response() {
+    assert!(matches!(wasm::user_info_response_to_json("-1"), Err(wasm::JsonError::Parse(_))));
+}